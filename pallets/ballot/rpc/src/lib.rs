@@ -0,0 +1,179 @@
+//! RPC interface for the ballot pallet, so voters can check their receipts.
+
+use std::sync::Arc;
+
+use codec::Codec;
+use jsonrpsee::{
+	core::{async_trait, Error as JsonRpseeError, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use primitives::{
+	schulze::PairwiseMatrix, AbstentionCount, CertifiedResults, ElectionId, Participation, SpoiledBallot,
+};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+
+pub use pallet_ballot_runtime_api::BallotApi as BallotRuntimeApi;
+
+#[rpc(client, server)]
+pub trait BallotApi<BlockHash, Hash, BlockNumber> {
+	#[method(name = "ballot_ballotReceipt")]
+	fn ballot_receipt(
+		&self,
+		election_id: ElectionId,
+		tracking_hash: Hash,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<BlockNumber>>;
+
+	#[method(name = "ballot_spoiledBallot")]
+	fn spoiled_ballot(
+		&self,
+		election_id: ElectionId,
+		tracking_hash: Hash,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<SpoiledBallot>>;
+
+	#[method(name = "ballot_pairwiseMatrix")]
+	fn pairwise_matrix(&self, election_id: ElectionId, at: Option<BlockHash>) -> RpcResult<Option<PairwiseMatrix>>;
+
+	#[method(name = "ballot_abstentions")]
+	fn abstentions(&self, election_id: ElectionId, at: Option<BlockHash>) -> RpcResult<Option<AbstentionCount>>;
+
+	#[method(name = "ballot_certifiedResults")]
+	fn certified_results(
+		&self,
+		election_id: ElectionId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<CertifiedResults<Hash, BlockNumber>>>;
+
+	#[method(name = "ballot_participation")]
+	fn participation(&self, election_id: ElectionId, at: Option<BlockHash>) -> RpcResult<Participation>;
+
+	#[method(name = "ballot_ballotsPerBlock")]
+	fn ballots_per_block(
+		&self,
+		election_id: ElectionId,
+		from: BlockNumber,
+		to: BlockNumber,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<(BlockNumber, u32)>>;
+}
+
+/// Provides RPC methods to query ballots.
+pub struct Ballot<C, P> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<P>,
+}
+
+impl<C, P> Ballot<C, P> {
+	/// Creates a new instance of the Ballot RPC helper.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error code for runtime API failures.
+const RUNTIME_ERROR: i32 = 1;
+
+fn runtime_error_into_rpc_err(err: impl std::fmt::Debug) -> JsonRpseeError {
+	CallError::Custom(ErrorObject::owned(
+		RUNTIME_ERROR,
+		"Unable to query ballot.",
+		Some(format!("{:?}", err)),
+	))
+	.into()
+}
+
+#[async_trait]
+impl<C, Block, Hash, BlockNumber> BallotApiServer<<Block as BlockT>::Hash, Hash, BlockNumber>
+	for Ballot<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: BallotRuntimeApi<Block, Hash, BlockNumber>,
+	Hash: Codec + Send + Sync + 'static,
+	BlockNumber: Codec + Send + Sync + 'static,
+{
+	fn ballot_receipt(
+		&self,
+		election_id: ElectionId,
+		tracking_hash: Hash,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<BlockNumber>> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.ballot_receipt(at_hash, election_id, tracking_hash).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn spoiled_ballot(
+		&self,
+		election_id: ElectionId,
+		tracking_hash: Hash,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<SpoiledBallot>> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.spoiled_ballot(at_hash, election_id, tracking_hash).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn pairwise_matrix(
+		&self,
+		election_id: ElectionId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<PairwiseMatrix>> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.pairwise_matrix(at_hash, election_id).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn abstentions(
+		&self,
+		election_id: ElectionId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<AbstentionCount>> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.abstentions(at_hash, election_id).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn certified_results(
+		&self,
+		election_id: ElectionId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<CertifiedResults<Hash, BlockNumber>>> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.certified_results(at_hash, election_id).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn participation(
+		&self,
+		election_id: ElectionId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Participation> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.participation(at_hash, election_id).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn ballots_per_block(
+		&self,
+		election_id: ElectionId,
+		from: BlockNumber,
+		to: BlockNumber,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<(BlockNumber, u32)>> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.ballots_per_block(at_hash, election_id, from, to).map_err(runtime_error_into_rpc_err)
+	}
+}
@@ -0,0 +1,34 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// Runtime API for looking up ballots from outside the runtime.
+use codec::Codec;
+use primitives::{
+	schulze::PairwiseMatrix, AbstentionCount, CertifiedResults, ElectionId, Participation, SpoiledBallot,
+};
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	pub trait BallotApi<Hash, BlockNumber> where
+		Hash: Codec,
+		BlockNumber: Codec,
+	{
+		/// Block in which the ballot with this tracking hash was cast, if it was
+		fn ballot_receipt(election_id: ElectionId, tracking_hash: Hash) -> Option<BlockNumber>;
+		/// Revealed opening of a ballot that was spoiled instead of cast
+		fn spoiled_ballot(election_id: ElectionId, tracking_hash: Hash) -> Option<SpoiledBallot>;
+		/// Pairwise preference counts of a Schulze election, for checking its winner.
+		/// `None` while the results are embargoed.
+		fn pairwise_matrix(election_id: ElectionId) -> Option<PairwiseMatrix>;
+		/// NOTA and blank ballots counted in an election, reported apart from the candidates.
+		/// `None` while the results are embargoed.
+		fn abstentions(election_id: ElectionId) -> Option<AbstentionCount>;
+		/// Results certified by the commissioners, with the digest they signed. `None`
+		/// until they are certified and while they are embargoed.
+		fn certified_results(election_id: ElectionId) -> Option<CertifiedResults<Hash, BlockNumber>>;
+		/// Registered, approved and voted counts of an election, overall and by region
+		/// and constituency. Turnout is not embargoed.
+		fn participation(election_id: ElectionId) -> Participation;
+		/// Ballots cast in each block from `from` to `to`, leaving out blocks without any
+		fn ballots_per_block(election_id: ElectionId, from: BlockNumber, to: BlockNumber) -> Vec<(BlockNumber, u32)>;
+	}
+}
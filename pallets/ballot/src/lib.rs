@@ -6,9 +6,25 @@
 pub use pallet::*;
 use frame_support::pallet_prelude::*;
 use frame_system::pallet_prelude::*;
-use sp_runtime::ArithmeticError;
-use primitives::{ElectionId, CandidateId, VoteCount, ZKPCommitmentValue, Vote, VotingSystem};
-use pallet_election::ElectionInfo;
+use frame_support::traits::EnsureOriginWithArg;
+use sp_runtime::{traits::Hash, ArithmeticError, FixedPointNumber, FixedU128, Perbill};
+use sp_std::vec::Vec;
+use primitives::{
+	irv::{IrvOutcome, IrvRound, RoundCount},
+	stv::{StageCount, StvParcel, StvStage},
+	schulze::{self, PairwiseMatrix, MAX_PREFERENCE_ENTRIES},
+	voting_method::{self, BallotError},
+	zkp,
+	referendum::{self, Answers, OptionIndex, QuestionIndex, QuestionOutcome, MAX_OPTIONS, MAX_QUESTIONS},
+	ElectionId, CandidateId, ConstituencyId, VoteCount, ZKPCommitmentValue, ZKPRandomness, SpoiledBallot, SealedWeight,
+	BallotIndex, BallotChoice, CastBallot, CountingMethod, RankedChoices, TallyStatus,
+	Abstention, AbstentionCount, EncryptedWriteIn, WriteInResults, WRITE_IN_CANDIDATE, ScoredChoices,
+	MAX_RANKED_CHOICES, RoleScope, ConstituencyResult, ResultsSummary, CertifiedResults,
+	Participation, RegionId, SealedCredential, BallotResolution, DelegationTopic,
+};
+use pallet_election::{ElectionControl, ElectionInfo, ResultStanding};
+use pallet_candidate::{CandidateLookup, CertificationInfo, ElectionResults};
+use pallet_voter::VoterInfo;
 
 #[cfg(test)]
 mod mock;
@@ -36,25 +52,340 @@ pub mod pallet {
 		/// Because this pallet emits events, it depends on the runtime's definition of an event.
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 		type ElectionInfo: ElectionInfo;
+		type CandidateLookup: CandidateLookup;
+		type VoterInfo: VoterInfo<Self::AccountId>;
 		/// Allowed origins for only election commission
-		type ElectionCommissionApproveOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		type ElectionCommissionApproveOrigin: EnsureOriginWithArg<Self::RuntimeOrigin, RoleScope>;
+		/// Allowed origins for the commissioners who co-sign the results of an election
+		type CertifyOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = Self::AccountId>;
+		/// Number of commissioners' signatures that certifies the results
+		type RequiredSignatures: Get<u32>;
+		/// Allowed origins for the trustees who decrypt and aggregate write-ins
+		type TrusteeOrigin: EnsureOriginWithArg<Self::RuntimeOrigin, RoleScope>;
+		/// Maximum number of ballots, or distinct rankings in a runoff round, counted by a
+		/// single tally call
+		#[pallet::constant]
+		type MaxBallotsPerTally: Get<u32>;
+		type ElectionControl: ElectionControl;
 	}
 
 	// The pallet's runtime storage items.
 	// https://docs.substrate.io/main-docs/build/runtime-storage/
 	#[pallet::storage]
 	#[pallet::getter(fn ballot)]
-	// Votes for candiates
+	// Votes for candiates, or their points under approval, score and Borda counting
 	pub type Ballot<T: Config> = StorageDoubleMap<_, Blake2_128Concat, ElectionId, Blake2_128Concat, CandidateId, VoteCount>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn ballots)]
+	// Every ballot cast in an election, in casting order
+	pub type Ballots<T: Config> = StorageDoubleMap<_, Blake2_128Concat, ElectionId, Blake2_128Concat, BallotIndex, CastBallot<T::Hash>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn ballot_count)]
+	// Number of ballots cast in an election
+	pub type BallotCount<T: Config> = StorageMap<_, Blake2_128Concat, ElectionId, BallotIndex, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn sealed_credential)]
+	// Credential sealed to the trustees with each anonymous ballot
+	pub type SealedCredentials<T: Config> = StorageDoubleMap<_, Blake2_128Concat, ElectionId, Blake2_128Concat, BallotIndex, SealedCredential>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn ballot_resolution)]
+	// How the trustees resolved each anonymous ballot once voting closed
+	pub type BallotResolutions<T: Config> = StorageDoubleMap<_, Blake2_128Concat, ElectionId, Blake2_128Concat, BallotIndex, BallotResolution>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn pending_resolutions)]
+	// Anonymous ballots of the current poll the trustees have not resolved yet
+	pub type PendingResolutions<T: Config> = StorageMap<_, Blake2_128Concat, ElectionId, u32, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn voter_ballot)]
+	// Latest ballot cast by each voter
+	pub type VoterBallot<T: Config> = StorageDoubleMap<_, Blake2_128Concat, ElectionId, Blake2_128Concat, AccountIdOf<T>, BallotIndex>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn tally_status)]
+	// Progress of the tally once voting is closed
+	pub type TallyProgress<T: Config> = StorageMap<_, Blake2_128Concat, ElectionId, TallyStatus>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn ranking)]
+	// Distinct rankings on ranked ballots with the voting weight of the ballots that cast them
+	pub type Rankings<T: Config> = StorageDoubleMap<_, Blake2_128Concat, ElectionId, Blake2_128Concat, u32, (RankedChoices, VoteCount)>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn ranking_index)]
+	// Position of each distinct ranking in `Rankings`
+	pub type RankingIndex<T: Config> = StorageDoubleMap<_, Blake2_128Concat, ElectionId, Blake2_128Concat, RankedChoices, u32>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn ranking_count)]
+	// Number of distinct rankings in an election
+	pub type RankingCount<T: Config> = StorageMap<_, Blake2_128Concat, ElectionId, u32, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn pending_round)]
+	// Instant-runoff round being counted
+	pub type PendingRound<T: Config> = StorageMap<_, Blake2_128Concat, ElectionId, RoundCount>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn runoff_round)]
+	// Every counted instant-runoff round, kept for audit
+	pub type RunoffRounds<T: Config> = StorageDoubleMap<_, Blake2_128Concat, ElectionId, Blake2_128Concat, u32, IrvRound>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn stv_stage)]
+	// Every STV counting stage, kept for audit
+	pub type StvStages<T: Config> = StorageDoubleMap<_, Blake2_128Concat, ElectionId, Blake2_128Concat, u32, StvStage>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn stv_parcel)]
+	// Value and holding preference of the ballots of each distinct ranking in the STV count
+	pub type StvParcels<T: Config> = StorageDoubleMap<_, Blake2_128Concat, ElectionId, Blake2_128Concat, u32, StvParcel>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn pending_stage)]
+	// STV stage being counted
+	pub type PendingStage<T: Config> = StorageMap<_, Blake2_128Concat, ElectionId, StageCount>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn stv_quota)]
+	// Droop quota an STV election was counted with
+	pub type StvQuota<T: Config> = StorageMap<_, Blake2_128Concat, ElectionId, VoteCount>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn ranked_by)]
+	// Number of counted Schulze ballots ranking each candidate
+	pub type RankedBy<T: Config> = StorageDoubleMap<_, Blake2_128Concat, ElectionId, Blake2_128Concat, CandidateId, VoteCount, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn ranked_above)]
+	// Number of counted Schulze ballots ranking the first candidate above the second
+	pub type RankedAbove<T: Config> = StorageDoubleMap<_, Blake2_128Concat, ElectionId, Blake2_128Concat, (CandidateId, CandidateId), VoteCount, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn abstentions)]
+	// NOTA and blank ballots, counted apart from the candidates
+	pub type AbstentionTallies<T: Config> = StorageMap<_, Blake2_128Concat, ElectionId, AbstentionCount, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn write_in)]
+	// Encrypted write-in name cast with each write-in ballot
+	pub type WriteIns<T: Config> = StorageDoubleMap<_, Blake2_128Concat, ElectionId, Blake2_128Concat, BallotIndex, EncryptedWriteIn>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn write_in_count)]
+	// Write-in ballots counted by the tally
+	pub type WriteInCount<T: Config> = StorageMap<_, Blake2_128Concat, ElectionId, VoteCount, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn write_in_results)]
+	// Write-in totals submitted by the trustees
+	pub type WriteInResultsOf<T: Config> = StorageMap<_, Blake2_128Concat, ElectionId, WriteInResults>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn question_tally)]
+	// Answers choosing each option of each question
+	pub type QuestionTallies<T: Config> = StorageDoubleMap<_, Blake2_128Concat, ElectionId, Blake2_128Concat, (QuestionIndex, OptionIndex), VoteCount, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn question_outcome)]
+	// Result of each question once its thresholds are applied at certification
+	pub type QuestionOutcomes<T: Config> = StorageDoubleMap<_, Blake2_128Concat, ElectionId, Blake2_128Concat, QuestionIndex, QuestionOutcome>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn certified)]
+	// Block in which the results of an election were certified
+	pub type Certified<T: Config> = StorageMap<_, Blake2_128Concat, ElectionId, T::BlockNumber>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn certification_count)]
+	// Number of times the results of an election have been certified
+	pub type CertificationCount<T: Config> = StorageMap<_, Blake2_128Concat, ElectionId, u32, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn winners)]
+	// Candidates elected once the tally is complete
+	pub type Winners<T: Config> = StorageMap<_, Blake2_128Concat, ElectionId, Vec<CandidateId>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn ballot_receipt)]
+	// Block in which a cast ballot was included, keyed by its tracking hash
+	pub type BallotReceipts<T: Config> = StorageDoubleMap<_, Blake2_128Concat, ElectionId, Blake2_128Concat, T::Hash, T::BlockNumber>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn spoiled_ballot)]
+	// Challenged ballots with their revealed opening, never counted
+	pub type SpoiledBallots<T: Config> = StorageDoubleMap<_, Blake2_128Concat, ElectionId, Blake2_128Concat, T::Hash, SpoiledBallot>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn proxy_ballot)]
+	// Tracking hash of the ballot each voter's proxy cast; a proxy casts only once
+	pub type ProxyBallots<T: Config> = StorageDoubleMap<_, Blake2_128Concat, ElectionId, Blake2_128Concat, AccountIdOf<T>, T::Hash>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn voided_votes)]
+	// Points cast for candidates withdrawn or disqualified before the tally, kept apart from the results
+	pub type VoidedVotes<T: Config> = StorageDoubleMap<_, Blake2_128Concat, ElectionId, Blake2_128Concat, CandidateId, VoteCount, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn ballot_generation)]
+	// Poll generation the stored ballots belong to, and the first ballot index cast in it
+	pub type BallotGeneration<T: Config> = StorageMap<_, Blake2_128Concat, ElectionId, (u32, BallotIndex), ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn constituency_repolls_counted)]
+	// Constituency re-polls the tally of each election has been restarted for
+	pub type ConstituencyRepollsCounted<T: Config> = StorageMap<_, Blake2_128Concat, ElectionId, u32, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn delegated_weight)]
+	// Weight delegated to each ballot, resolved at the start of the tally
+	pub type DelegatedWeight<T: Config> = StorageDoubleMap<_, Blake2_128Concat, ElectionId, Blake2_128Concat, BallotIndex, VoteCount, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn question_delegated_weight)]
+	// Weight delegated to each ballot on one of its questions only
+	pub type QuestionDelegatedWeight<T: Config> = StorageDoubleMap<_, Blake2_128Concat, ElectionId, Blake2_128Concat, (BallotIndex, QuestionIndex), VoteCount, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn withheld_weight)]
+	// Weight delegated to each ballot as a whole but delegated elsewhere on one of its questions
+	pub type WithheldWeight<T: Config> = StorageDoubleMap<_, Blake2_128Concat, ElectionId, Blake2_128Concat, (BallotIndex, QuestionIndex), VoteCount, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn rebuild_preferences)]
+	// Schulze elections whose pairwise counts are rebuilt from the ballots as they are counted again
+	pub type RebuildPreferences<T: Config> = StorageMap<_, Blake2_128Concat, ElectionId, bool, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn recount_baseline)]
+	// Certified winners and per-candidate votes a recount in progress is compared with
+	pub type RecountBaselines<T: Config> = StorageMap<_, Blake2_128Concat, ElectionId, (Vec<CandidateId>, Option<Vec<(CandidateId, VoteCount)>>)>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn recount_matched)]
+	// Whether the last recount of each election reproduced the certified results
+	pub type RecountMatched<T: Config> = StorageMap<_, Blake2_128Concat, ElectionId, bool>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn turnout)]
+	// Votes counted in each election, abstentions and delegated weight included
+	pub type Turnout<T: Config> = StorageMap<_, Blake2_128Concat, ElectionId, VoteCount, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn constituency_turnout)]
+	// Votes counted in each constituency of an election
+	pub type ConstituencyTurnout<T: Config> = StorageDoubleMap<_, Blake2_128Concat, ElectionId, Blake2_128Concat, ConstituencyId, VoteCount, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn constituency_votes)]
+	// Votes per candidate in each constituency; first preferences under the ranked methods
+	pub type ConstituencyTotals<T: Config> = StorageDoubleMap<_, Blake2_128Concat, ElectionId, Blake2_128Concat, (ConstituencyId, CandidateId), VoteCount, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn results_digest)]
+	// Hash of the results of each tallied election, for the commissioners to sign
+	pub type ResultsDigests<T: Config> = StorageMap<_, Blake2_128Concat, ElectionId, T::Hash>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn result_signature)]
+	// Commissioners who signed the results of each election
+	pub type ResultSignatures<T: Config> = StorageDoubleMap<_, Blake2_128Concat, ElectionId, Blake2_128Concat, AccountIdOf<T>, bool, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn signature_count)]
+	// Number of commissioners who signed the results of each election
+	pub type SignatureCount<T: Config> = StorageMap<_, Blake2_128Concat, ElectionId, u32, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn results_embargo)]
+	// Block until which the results of each election are withheld from the runtime API
+	pub type ResultsEmbargo<T: Config> = StorageMap<_, Blake2_128Concat, ElectionId, T::BlockNumber>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn voted_count)]
+	// Number of voters who cast a ballot in the current poll of each election
+	pub type VotedCount<T: Config> = StorageMap<_, Blake2_128Concat, ElectionId, u32, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn region_voted)]
+	// Number of voters of each registration region who cast a ballot in the current poll
+	pub type RegionVoted<T: Config> = StorageDoubleMap<_, Blake2_128Concat, ElectionId, Blake2_128Concat, RegionId, u32, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn constituency_voted)]
+	// Number of voters of each constituency who cast a ballot, by re-poll generation
+	pub type ConstituencyVoted<T: Config> = StorageDoubleMap<_, Blake2_128Concat, ElectionId, Blake2_128Concat, (ConstituencyId, u32), u32, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn block_ballots)]
+	// Number of ballots cast in each block, revotes included
+	pub type BlockBallots<T: Config> = StorageDoubleMap<_, Blake2_128Concat, ElectionId, Blake2_128Concat, T::BlockNumber, u32, ValueQuery>;
+
 	// Pallets use events to inform users when important changes are made.
 	// https://docs.substrate.io/main-docs/build/events-errors/
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
 		/// Vote is successfully casted
-		/// parameters. []
-		VoteCasted,
+		/// parameters. [election_id, tracking_hash]
+		VoteCasted{election_id: ElectionId, tracking_hash: T::Hash},
+		/// Ballot is challenged and its opening published instead of being counted
+		/// parameters. [election_id, tracking_hash]
+		BallotSpoiled{election_id: ElectionId, tracking_hash: T::Hash},
+		/// Proxy cast a ballot on behalf of a voter
+		/// parameters. [election_id, voter, proxy, tracking_hash]
+		ProxyVoteCasted{election_id: ElectionId, voter: AccountIdOf<T>, proxy: AccountIdOf<T>, tracking_hash: T::Hash},
+		/// A batch of ballots is aggregated into the results
+		/// parameters. [election_id, counted]
+		TallyProgressed{election_id: ElectionId, counted: BallotIndex},
+		/// Tally moved the weight of the next batch of delegators onto their delegates' ballots
+		/// parameters. [election_id, resolved]
+		DelegationsResolved{election_id: ElectionId, resolved: u32},
+		/// Every ballot that was not superseded is aggregated
+		/// parameters. [election_id]
+		TallyCompleted{election_id: ElectionId},
+		/// Candidate with the fewest votes is eliminated in a runoff round or STV stage
+		/// parameters. [election_id, round, candidate_id]
+		CandidateEliminated{election_id: ElectionId, round: u32, candidate_id: CandidateId},
+		/// Candidate reaches a majority in a runoff round, is elected in an STV stage or
+		/// wins a Schulze count
+		/// parameters. [election_id, round, candidate_id]
+		CandidateElected{election_id: ElectionId, round: u32, candidate_id: CandidateId},
+		/// Thresholds of a question are applied to its tally
+		/// parameters. [election_id, question, outcome]
+		QuestionDecided{election_id: ElectionId, question: QuestionIndex, outcome: QuestionOutcome},
+		/// Commissioner signed the results of an election
+		/// parameters. [election_id, commissioner, signatures]
+		ResultsSigned{election_id: ElectionId, commissioner: AccountIdOf<T>, signatures: u32},
+		/// Results of an election are certified
+		/// parameters. [election_id, digest]
+		ResultsCertified{election_id: ElectionId, digest: T::Hash},
+		/// Results of an election are withheld from the runtime API until a block
+		/// parameters. [election_id, until]
+		ResultsEmbargoSet{election_id: ElectionId, until: T::BlockNumber},
+		/// Trustees resolved a batch of anonymous ballots
+		/// parameters. [election_id, resolved, pending]
+		AnonymousBallotsResolved{election_id: ElectionId, resolved: u32, pending: u32},
+		/// Trustees submitted the aggregated write-in names
+		/// parameters. [election_id, names]
+		WriteInResultsSubmitted{election_id: ElectionId, names: u32},
+		/// Ballots cast before a countermand or re-poll are set aside
+		/// parameters. [election_id, generation, first_ballot]
+		BallotsSetAside{election_id: ElectionId, generation: u32, first_ballot: BallotIndex},
+		/// Results are discarded and the stored ballots are to be counted again
+		/// parameters. [election_id]
+		TallyRestarted{election_id: ElectionId},
+		/// Certified results are counted again from the stored ballots
+		/// parameters. [election_id]
+		RecountStarted{election_id: ElectionId},
+		/// Recount completed and was compared with the certified results
+		/// parameters. [election_id, matched]
+		RecountCompared{election_id: ElectionId, matched: bool},
 	}
 
 	// Errors inform users that something went wrong.
@@ -62,6 +393,85 @@ pub mod pallet {
 	pub enum Error<T> {
 		/// Error: Invalid Election Id Or Not Open For Voting.
 		InvalidElectionIdOrNotOpenForVoting,
+		/// Error: Ballot With This Tracking Hash Was Already Spoiled.
+		BallotAlreadySpoiled,
+		/// Error: Ballot With This Tracking Hash Was Already Cast.
+		BallotAlreadyCast,
+		/// Error: Voter Already Voted And Election Does Not Allow Revoting.
+		AlreadyVoted,
+		/// Error: Election Is Not Closed For Voting.
+		ElectionNotClosedForVoting,
+		/// Error: Tally Already Complete.
+		TallyAlreadyComplete,
+		/// Error: Candidate Is Not Registered For This Election.
+		InvalidCandidate,
+		/// Error: Ballot Type Does Not Match The Election's Counting Method.
+		WrongBallotType,
+		/// Error: Ballot Is Empty Or Marks A Candidate Twice.
+		InvalidRanking,
+		/// Error: Score Is Above The Election's Maximum.
+		InvalidScore,
+		/// Error: Ballot Does Not Answer Every Question With One Of Its Options.
+		InvalidAnswers,
+		/// Error: Tally Is Not Complete.
+		TallyNotComplete,
+		/// Error: Results Already Certified.
+		ResultsAlreadyCertified,
+		/// Error: Election Does Not Allow Write-Ins.
+		WriteInsNotAllowed,
+		/// Error: Write-In Totals Do Not Match The Write-In Ballots Counted.
+		WriteInTotalsMismatch,
+		/// Error: Write-In Results Not Submitted.
+		WriteInResultsMissing,
+		/// Error: Write-In Results Already Submitted.
+		WriteInResultsAlreadySubmitted,
+		/// Error: Voter Not Registered For This Election.
+		NotRegisteredVoter,
+		/// Error: Commitment Does Not Open To The Voter's Weight.
+		CommitmentWeightMismatch,
+		/// Error: Votes Cost More Credits Than The Budget.
+		OverBudget,
+		/// Error: Account Is Not An Approved Proxy For This Election.
+		NotApprovedProxy,
+		/// Error: Proxy Already Cast A Ballot For This Voter.
+		ProxyAlreadyVoted,
+		/// Error: Candidate Was Withdrawn Or Disqualified.
+		CandidateNotStanding,
+		/// Error: Results Not Certified.
+		ResultsNotCertified,
+		/// Error: Recount Already In Progress.
+		RecountInProgress,
+		/// Error: Constituency Re-Poll Still Open.
+		RepollStillOpen,
+		/// Error: Digest Does Not Match The Tallied Results.
+		ResultsDigestMismatch,
+		/// Error: Commissioner Already Signed These Results.
+		ResultsAlreadySigned,
+		/// Error: Election Takes Anonymous Ballots Only.
+		AnonymousBallotsOnly,
+		/// Error: Election Does Not Take Anonymous Ballots.
+		NotAnonymousElection,
+		/// Error: Anonymous Ballots Not Yet Resolved By The Trustees.
+		BallotsUnresolved,
+		/// Error: Ballot Is Not An Anonymous Ballot Awaiting Resolution.
+		BallotNotPending,
+		/// Error: Too Many Ballots Resolved At Once.
+		TooManyResolutions,
+		/// Error: Counted Ballot Must Carry A Weight.
+		InvalidResolution,
+		/// Error: Voter Registration Not Approved By The Registrar.
+		NotApprovedVoter,
+	}
+
+	impl<T> From<BallotError> for Error<T> {
+		fn from(error: BallotError) -> Self {
+			match error {
+				BallotError::WrongBallotType => Error::<T>::WrongBallotType,
+				BallotError::Empty | BallotError::Duplicate => Error::<T>::InvalidRanking,
+				BallotError::ScoreOutOfRange => Error::<T>::InvalidScore,
+				BallotError::OverBudget => Error::<T>::OverBudget,
+			}
+		}
 	}
 
 	// Dispatchable functions allows users to interact with the pallet and invoke state changes.
@@ -69,29 +479,462 @@ pub mod pallet {
 	// Dispatchable functions must be annotated with a weight and must return a DispatchResult.
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		/// Self register by voter
+		/// Cast a vote and record its tracking hash as the voter's receipt. The weight
+		/// commitment must open to the voter's public registered weight, and its proof
+		/// must be made for this ballot. In elections that allow revoting a later ballot supersedes the
+		/// earlier one.
 		#[pallet::call_index(0)]
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1).ref_time())]
-		pub fn cast_vote(origin: OriginFor<T>, election_id: ElectionId, candidate_id: CandidateId, zkp_value: ZKPCommitmentValue, transcript: Vec<u8>, zkp_randomness: Vec<u8>) -> DispatchResult {
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(5, 5).ref_time())]
+		pub fn cast_vote(origin: OriginFor<T>, election_id: ElectionId, candidate_id: CandidateId, sealed: SealedWeight) -> DispatchResult {
+			let voter = ensure_signed(origin)?;
+			Self::ensure_open_for(election_id, &voter)?;
+			let choice = BallotChoice::Single(candidate_id);
+			Self::check_ballot(election_id, &choice)?;
+			let weight = Self::voter_weight(election_id, &voter)?;
+			Self::check_weight(&(election_id, &choice).encode(), &sealed, weight)?;
+
+			let tracking_hash = Self::tracking_hash(election_id, &choice, &sealed.commitment);
+			Self::store_ballot(election_id, &voter, choice, tracking_hash, weight)?;
+
+			// Emit an event.
+			Self::deposit_event(Event::VoteCasted{election_id, tracking_hash});
+			// Return a successful DispatchResultWithPostInfo
+			Ok(())
+		}
+		/// Challenge a ballot instead of casting it. The opening is published so the
+		/// voter can check offline that the ballot encoded their choice; it is never counted.
+		/// Only approved voters may spoil, as only they may cast.
+		#[pallet::call_index(1)]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4, 1).ref_time())]
+		pub fn spoil_ballot(origin: OriginFor<T>, election_id: ElectionId, candidate_id: CandidateId, zkp_value: ZKPCommitmentValue, zkp_randomness: ZKPRandomness) -> DispatchResult {
+			let voter = ensure_signed(origin)?;
+			Self::ensure_open_for(election_id, &voter)?;
+			Self::voter_weight(election_id, &voter)?;
+
+			let commitment = zkp::commit(VoteCount::from(zkp_value), &zkp_randomness);
+			let tracking_hash = Self::tracking_hash(election_id, &BallotChoice::Single(candidate_id), &commitment);
+			ensure!(
+				!<BallotReceipts<T>>::contains_key(election_id, tracking_hash),
+				Error::<T>::BallotAlreadyCast
+			);
+			ensure!(
+				!<SpoiledBallots<T>>::contains_key(election_id, tracking_hash),
+				Error::<T>::BallotAlreadySpoiled
+			);
+			<SpoiledBallots<T>>::insert(election_id, tracking_hash, SpoiledBallot{
+				candidate: candidate_id,
+				zkp_value,
+				zkp_randomness,
+			});
+
+			// Emit an event.
+			Self::deposit_event(Event::BallotSpoiled{election_id, tracking_hash});
+			// Return a successful DispatchResultWithPostInfo
+			Ok(())
+		}
+		/// Aggregate the next batch of ballots once voting is closed, skipping superseded
+		/// ballots. Delegated votes are first moved onto their delegates' ballots in
+		/// batches. For instant-runoff elections each further call counts (part of) one
+		/// runoff round, and for STV elections (part of) one stage with its transfers. Schulze elections are decided from the pairwise counts kept while
+		/// voting was open once the last batch is through. NOTA and blank ballots are
+		/// counted apart. In anonymous elections the trustees must first have resolved
+		/// every ballot.
+		/// Anyone may call this until the tally is complete.
+		#[pallet::call_index(2)]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3 + T::CandidateLookup::max_candidates() as u64 + (4 + 4 * T::VoterInfo::max_delegation_depth() as u64) * T::MaxBallotsPerTally::get() as u64, 4 + T::MaxBallotsPerTally::get() as u64).ref_time())]
+		pub fn tally_ballots(origin: OriginFor<T>, election_id: ElectionId) -> DispatchResult {
+			let _ = ensure_signed(origin)?;
+			ensure!(
+				T::ElectionInfo::is_election_closed_for_voting(&election_id),
+				Error::<T>::ElectionNotClosedForVoting
+			);
+			ensure!(
+				!T::ElectionInfo::has_open_repoll(&election_id),
+				Error::<T>::RepollStillOpen
+			);
+			Self::sync_generation(election_id);
+			ensure!(
+				<PendingResolutions<T>>::get(election_id) == 0,
+				Error::<T>::BallotsUnresolved
+			);
+			match <TallyProgress<T>>::get(election_id) {
+				None => Self::resolve_delegations(election_id, 0),
+				Some(TallyStatus::Delegations { next }) => Self::resolve_delegations(election_id, next),
+				Some(TallyStatus::Counting { next }) => Self::count_ballots(election_id, next),
+				Some(TallyStatus::Rounds { round, next }) => Self::count_runoff_round(election_id, round, next),
+				Some(TallyStatus::Stages { stage, next }) => Self::count_stv(election_id, stage, next),
+				Some(TallyStatus::Complete) => Err(Error::<T>::TallyAlreadyComplete.into()),
+			}
+		}
+		/// Cast a ranked ballot, most preferred candidate first, with the voter's weight commitment
+		#[pallet::call_index(3)]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(5 + MAX_RANKED_CHOICES as u64 + 2 * MAX_PREFERENCE_ENTRIES as u64, 5 + 2 * MAX_PREFERENCE_ENTRIES as u64).ref_time())]
+		pub fn cast_ranked_vote(origin: OriginFor<T>, election_id: ElectionId, ranking: RankedChoices, sealed: SealedWeight) -> DispatchResult {
+			let voter = ensure_signed(origin)?;
+			Self::ensure_open_for(election_id, &voter)?;
+			let choice = BallotChoice::Ranked(ranking);
+			Self::check_ballot(election_id, &choice)?;
+			let weight = Self::voter_weight(election_id, &voter)?;
+			Self::check_weight(&(election_id, &choice).encode(), &sealed, weight)?;
+			let tracking_hash = Self::tracking_hash(election_id, &choice, &sealed.commitment);
+			Self::store_ballot(election_id, &voter, choice, tracking_hash, weight)?;
+
+			// Emit an event.
+			Self::deposit_event(Event::VoteCasted{election_id, tracking_hash});
+			// Return a successful DispatchResultWithPostInfo
+			Ok(())
+		}
+		/// Cast a ballot of the shape the election's counting method takes: a single
+		/// candidate, a ranking, approved candidates or scored candidates, with the voter's
+		/// weight commitment
+		#[pallet::call_index(4)]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(5 + MAX_RANKED_CHOICES as u64 + 2 * MAX_PREFERENCE_ENTRIES as u64, 5 + 2 * MAX_PREFERENCE_ENTRIES as u64).ref_time())]
+		pub fn cast_ballot(origin: OriginFor<T>, election_id: ElectionId, choice: BallotChoice, sealed: SealedWeight) -> DispatchResult {
+			let voter = ensure_signed(origin)?;
+			Self::ensure_open_for(election_id, &voter)?;
+			Self::check_ballot(election_id, &choice)?;
+			let weight = Self::voter_weight(election_id, &voter)?;
+			Self::check_weight(&(election_id, &choice).encode(), &sealed, weight)?;
+
+			let tracking_hash = Self::tracking_hash(election_id, &choice, &sealed.commitment);
+			Self::store_ballot(election_id, &voter, choice, tracking_hash, weight)?;
+
+			// Emit an event.
+			Self::deposit_event(Event::VoteCasted{election_id, tracking_hash});
+			// Return a successful DispatchResultWithPostInfo
+			Ok(())
+		}
+		/// Co-sign the results of a tallied election, by a commissioner. The digest signed
+		/// must be the hash of the results per candidate, per constituency and of the
+		/// turnout kept in `ResultsDigests`. The results are certified, and the election
+		/// complete, once `RequiredSignatures` commissioners have signed. The turnout and
+		/// approval thresholds of each question are applied then, against the registered
+		/// voters.
+		#[pallet::call_index(5)]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(7 + (MAX_QUESTIONS * MAX_OPTIONS) as u64, 5 + MAX_QUESTIONS as u64).ref_time())]
+		pub fn certify_results(origin: OriginFor<T>, election_id: ElectionId, digest: T::Hash) -> DispatchResult {
+			// Check if the sender is an approved origin or not
+			let commissioner = T::CertifyOrigin::ensure_origin(origin)?;
+			ensure!(
+				<TallyProgress<T>>::get(election_id) == Some(TallyStatus::Complete),
+				Error::<T>::TallyNotComplete
+			);
+			ensure!(
+				!<Certified<T>>::contains_key(election_id),
+				Error::<T>::ResultsAlreadyCertified
+			);
+
+			ensure!(
+				<WriteInCount<T>>::get(election_id) == 0 || <WriteInResultsOf<T>>::contains_key(election_id),
+				Error::<T>::WriteInResultsMissing
+			);
+			ensure!(
+				<ResultsDigests<T>>::get(election_id) == Some(digest),
+				Error::<T>::ResultsDigestMismatch
+			);
+			ensure!(
+				!<ResultSignatures<T>>::get(election_id, &commissioner),
+				Error::<T>::ResultsAlreadySigned
+			);
+			<ResultSignatures<T>>::insert(election_id, &commissioner, true);
+			let signatures = <SignatureCount<T>>::mutate(election_id, |count| {
+				*count = count.saturating_add(1);
+				*count
+			});
+			Self::deposit_event(Event::ResultsSigned{election_id, commissioner, signatures});
+			if signatures < T::RequiredSignatures::get() {
+				return Ok(())
+			}
+
+			let electorate = T::VoterInfo::total_weight(&election_id);
+			for (index, question) in T::ElectionInfo::election_questions(&election_id).iter().enumerate() {
+				let question_index = index as QuestionIndex;
+				let counts: Vec<VoteCount> = (0..question.options.len() as OptionIndex)
+					.map(|option| <QuestionTallies<T>>::get(election_id, (question_index, option)))
+					.collect();
+				let outcome = referendum::outcome(question, &counts, electorate);
+				<QuestionOutcomes<T>>::insert(election_id, question_index, outcome);
+				Self::deposit_event(Event::QuestionDecided{election_id, question: question_index, outcome});
+			}
+			<Certified<T>>::insert(election_id, <frame_system::Pallet<T>>::block_number());
+			<CertificationCount<T>>::mutate(election_id, |count| *count = count.saturating_add(1));
+			if T::ElectionInfo::result_standing(&election_id) == Some(ResultStanding::RecountOrdered) {
+				T::ElectionControl::set_result_standing(&election_id, ResultStanding::Recounted);
+			}
+			T::ElectionControl::set_election_complete(&election_id, true);
+
+			// Emit an event.
+			Self::deposit_event(Event::ResultsCertified{election_id, digest});
+			// Return a successful DispatchResultWithPostInfo
+			Ok(())
+		}
+		/// Cast a write-in ballot. The name is encrypted to the trustees and only
+		/// decrypted after mixing, so it is never readable alongside the voter.
+		#[pallet::call_index(6)]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(5, 6).ref_time())]
+		pub fn cast_write_in(origin: OriginFor<T>, election_id: ElectionId, encrypted_name: EncryptedWriteIn, sealed: SealedWeight) -> DispatchResult {
+			let voter = ensure_signed(origin)?;
+			Self::ensure_open_for(election_id, &voter)?;
+			ensure!(
+				T::ElectionInfo::election_config(&election_id).allow_write_ins,
+				Error::<T>::WriteInsNotAllowed
+			);
+			let weight = Self::voter_weight(election_id, &voter)?;
+			let choice = BallotChoice::Single(WRITE_IN_CANDIDATE);
+			Self::check_weight(&(election_id, &choice, &encrypted_name).encode(), &sealed, weight)?;
+
+			let tracking_hash = T::Hashing::hash_of(&(election_id, &choice, sealed.commitment, &encrypted_name));
+			let index = <BallotCount<T>>::get(election_id);
+			Self::store_ballot(election_id, &voter, choice, tracking_hash, weight)?;
+			<WriteIns<T>>::insert(election_id, index, encrypted_name);
+
+			// Emit an event.
+			Self::deposit_event(Event::VoteCasted{election_id, tracking_hash});
+			// Return a successful DispatchResultWithPostInfo
+			Ok(())
+		}
+		/// Submit the write-in names aggregated off-chain once the tally is complete.
+		/// The totals must add up to the write-in ballots the tally counted.
+		#[pallet::call_index(7)]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4, 1).ref_time())]
+		pub fn submit_write_in_results(origin: OriginFor<T>, election_id: ElectionId, results: WriteInResults) -> DispatchResult {
+			T::TrusteeOrigin::ensure_origin(origin, &RoleScope::Election(election_id))?;
+			ensure!(
+				<TallyProgress<T>>::get(election_id) == Some(TallyStatus::Complete),
+				Error::<T>::TallyNotComplete
+			);
+			ensure!(
+				!<Certified<T>>::contains_key(election_id),
+				Error::<T>::ResultsAlreadyCertified
+			);
+			ensure!(
+				!<WriteInResultsOf<T>>::contains_key(election_id),
+				Error::<T>::WriteInResultsAlreadySubmitted
+			);
+			let total = results.totals.iter().fold(0 as VoteCount, |acc, (_, n)| acc.saturating_add(*n));
+			ensure!(
+				total == <WriteInCount<T>>::get(election_id),
+				Error::<T>::WriteInTotalsMismatch
+			);
+			let names = results.totals.len() as u32;
+			<WriteInResultsOf<T>>::insert(election_id, results);
+
+			// Emit an event.
+			Self::deposit_event(Event::WriteInResultsSubmitted{election_id, names});
+			// Return a successful DispatchResultWithPostInfo
+			Ok(())
+		}
+		/// Cast a quadratic ballot with the voter's weight commitment. The votes are public:
+		/// `n` votes for a candidate cost `n²` credits and the runtime checks that the
+		/// ballot spends no more than the election's budget, as it does for a quadratic
+		/// ballot cast with `cast_ballot`.
+		#[pallet::call_index(8)]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(5 + MAX_RANKED_CHOICES as u64, 5).ref_time())]
+		pub fn cast_quadratic_vote(origin: OriginFor<T>, election_id: ElectionId, votes: ScoredChoices, sealed: SealedWeight) -> DispatchResult {
+			let voter = ensure_signed(origin)?;
+			Self::ensure_open_for(election_id, &voter)?;
+			ensure!(
+				matches!(T::ElectionInfo::election_config(&election_id).counting_method, CountingMethod::Quadratic { .. }),
+				Error::<T>::WrongBallotType
+			);
+			let choice = BallotChoice::Scored(votes);
+			Self::check_ballot(election_id, &choice)?;
+			let weight = Self::voter_weight(election_id, &voter)?;
+			Self::check_weight(&(election_id, &choice).encode(), &sealed, weight)?;
+
+			let tracking_hash = Self::tracking_hash(election_id, &choice, &sealed.commitment);
+			Self::store_ballot(election_id, &voter, choice, tracking_hash, weight)?;
+
+			// Emit an event.
+			Self::deposit_event(Event::VoteCasted{election_id, tracking_hash});
+			// Return a successful DispatchResultWithPostInfo
+			Ok(())
+		}
+		/// Cast the ballot of the voter who appointed the caller as their proxy. The ballot
+		/// carries the voter's weight commitment and the proxy can cast only once.
+		#[pallet::call_index(9)]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(7 + MAX_RANKED_CHOICES as u64 + 2 * MAX_PREFERENCE_ENTRIES as u64, 6 + 2 * MAX_PREFERENCE_ENTRIES as u64).ref_time())]
+		pub fn cast_proxy_ballot(origin: OriginFor<T>, election_id: ElectionId, choice: BallotChoice, sealed: SealedWeight) -> DispatchResult {
+			let proxy = ensure_signed(origin)?;
+			let voter = T::VoterInfo::proxy_principal(&election_id, &proxy).ok_or(Error::<T>::NotApprovedProxy)?;
+			Self::ensure_open_for(election_id, &voter)?;
+			Self::sync_generation(election_id);
+			ensure!(
+				!<ProxyBallots<T>>::contains_key(election_id, &voter),
+				Error::<T>::ProxyAlreadyVoted
+			);
+			Self::check_ballot(election_id, &choice)?;
+			let weight = Self::voter_weight(election_id, &voter)?;
+			Self::check_weight(&(election_id, &choice).encode(), &sealed, weight)?;
+
+			let tracking_hash = Self::tracking_hash(election_id, &choice, &sealed.commitment);
+			Self::store_ballot(election_id, &voter, choice, tracking_hash, weight)?;
+			<ProxyBallots<T>>::insert(election_id, &voter, tracking_hash);
+
+			// Emit an event.
+			Self::deposit_event(Event::ProxyVoteCasted{election_id, voter, proxy, tracking_hash});
+			// Return a successful DispatchResultWithPostInfo
+			Ok(())
+		}
+		/// Count the stored ballots of a certified election again. The certified results
+		/// are kept aside and compared with the recount once its tally completes; the
+		/// recounted results must then be certified again.
+		#[pallet::call_index(10)]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(6 + T::CandidateLookup::max_candidates() as u64, 20).ref_time())]
+		pub fn recount_ballots(origin: OriginFor<T>, election_id: ElectionId) -> DispatchResult {
+			// Check if the sender is an approved origin or not
+			T::ElectionCommissionApproveOrigin::ensure_origin(origin, &RoleScope::Election(election_id))?;
+			ensure!(
+				<Certified<T>>::contains_key(election_id),
+				Error::<T>::ResultsNotCertified
+			);
+			ensure!(
+				!<RecountBaselines<T>>::contains_key(election_id),
+				Error::<T>::RecountInProgress
+			);
+			let winners = <Winners<T>>::get(election_id).unwrap_or_default();
+			<RecountBaselines<T>>::insert(election_id, (winners, Self::candidate_totals(&election_id)));
+			Self::restart_tally(election_id);
+
+			// Emit an event.
+			Self::deposit_event(Event::RecountStarted{election_id});
+			// Return a successful DispatchResultWithPostInfo
+			Ok(())
+		}
+		/// Withhold the results of an election from the runtime API until a block, such as
+		/// the end of the last phase of polling. The results can still be tallied and
+		/// certified meanwhile. Schulze elections then keep no pairwise counts while voting
+		/// is open: the counts so far are dropped and rebuilt from the ballots in the tally.
+		#[pallet::call_index(11)]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 2 + T::CandidateLookup::max_candidates() as u64 * (1 + T::CandidateLookup::max_candidates() as u64)).ref_time())]
+		pub fn set_results_embargo(origin: OriginFor<T>, election_id: ElectionId, until: T::BlockNumber) -> DispatchResult {
+			// Check if the sender is an approved origin or not
+			T::ElectionCommissionApproveOrigin::ensure_origin(origin, &RoleScope::Election(election_id))?;
+			ensure!(
+				!<Certified<T>>::contains_key(election_id),
+				Error::<T>::ResultsAlreadyCertified
+			);
+			<ResultsEmbargo<T>>::insert(election_id, until);
+			if T::ElectionInfo::election_config(&election_id).counting_method == CountingMethod::Schulze {
+				let _ = <RankedBy<T>>::clear_prefix(election_id, u32::MAX, None);
+				let _ = <RankedAbove<T>>::clear_prefix(election_id, u32::MAX, None);
+				<RebuildPreferences<T>>::insert(election_id, true);
+			}
+
+			// Emit an event.
+			Self::deposit_event(Event::ResultsEmbargoSet{election_id, until});
+			// Return a successful DispatchResultWithPostInfo
+			Ok(())
+		}
+		/// Cast a ballot in an anonymous election, from any account such as a relayer's.
+		/// The credential seals the voter's account and their signature over the ballot to
+		/// the trustees, so nothing on chain links the ballot to the voter or to their
+		/// other ballots. The constituency is declared in the clear so the ballot can be
+		/// set aside by a re-poll; the trustees check it against the voter's.
+		#[pallet::call_index(12)]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(6 + MAX_RANKED_CHOICES as u64, 6).ref_time())]
+		pub fn cast_anonymous_ballot(origin: OriginFor<T>, election_id: ElectionId, constituency: Option<ConstituencyId>, choice: BallotChoice, credential: SealedCredential) -> DispatchResult {
 			let _ = ensure_signed(origin)?;
+			let config = T::ElectionInfo::election_config(&election_id);
+			ensure!(config.anonymous, Error::<T>::NotAnonymousElection);
+			let repolling = constituency.map_or(false, |c| T::ElectionInfo::is_open_for_repoll(&election_id, &c));
 			ensure!(
-				!&T::ElectionInfo::is_election_open_for_voting(&election_id),
+				T::ElectionInfo::is_election_open_for_voting(&election_id) || repolling,
 				Error::<T>::InvalidElectionIdOrNotOpenForVoting
 			);
-			
-			let transcript_ref: &'static [u8] = unsafe { std::mem::transmute(transcript.as_slice()) };
+			Self::check_ballot(election_id, &choice)?;
+			let tracking_hash = T::Hashing::hash_of(&(election_id, &choice, &credential));
+			ensure!(
+				!<BallotReceipts<T>>::contains_key(election_id, tracking_hash),
+				Error::<T>::BallotAlreadyCast
+			);
+			Self::sync_generation(election_id);
+			// Weights are only known once the trustees resolve the ballots
+			if config.counting_method == CountingMethod::Schulze {
+				<RebuildPreferences<T>>::insert(election_id, true);
+			}
 
-			let vs = VotingSystem::new(transcript_ref);
-	        let vote = vs.cast_vote(
-	            candidate_id, 
-	            zkp_value, 
-	            &zkp_randomness, 
-	        );
-			// Update storage for vote count for particular election id and candidate id
-			Self::vote_inc(election_id, candidate_id);
+			let index = <BallotCount<T>>::get(election_id);
+			<BallotCount<T>>::insert(election_id, index.checked_add(1).ok_or(ArithmeticError::Overflow)?);
+			let scope = constituency.map(|c| (c, T::ElectionInfo::constituency_generation(&election_id, &c)));
+			<Ballots<T>>::insert(election_id, index, CastBallot{
+				choice,
+				tracking_hash,
+				superseded: false,
+				weight: 0,
+				scope,
+			});
+			<SealedCredentials<T>>::insert(election_id, index, credential);
+			<PendingResolutions<T>>::mutate(election_id, |pending| *pending = pending.saturating_add(1));
+			let now = <frame_system::Pallet<T>>::block_number();
+			<BallotReceipts<T>>::insert(election_id, tracking_hash, now);
+			<BlockBallots<T>>::mutate(election_id, now, |count| *count = count.saturating_add(1));
 
 			// Emit an event.
-			Self::deposit_event(Event::VoteCasted);
+			Self::deposit_event(Event::VoteCasted{election_id, tracking_hash});
+			// Return a successful DispatchResultWithPostInfo
+			Ok(())
+		}
+		/// Resolve a batch of anonymous ballots once voting is closed, by the trustees who
+		/// open the sealed credentials off-chain. Only the latest ballot of each voter is
+		/// counted, with the voter's weight; earlier ballots are superseded, and ballots of
+		/// voters whose registration was not approved are invalid. Which voter
+		/// cast a ballot is never published, and the tally waits until every ballot of the
+		/// current poll is resolved.
+		#[pallet::call_index(13)]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4 + 3 * T::MaxBallotsPerTally::get() as u64, 2 + 5 * T::MaxBallotsPerTally::get() as u64).ref_time())]
+		pub fn resolve_anonymous_ballots(origin: OriginFor<T>, election_id: ElectionId, resolutions: Vec<(BallotIndex, BallotResolution)>) -> DispatchResult {
+			T::TrusteeOrigin::ensure_origin(origin, &RoleScope::Election(election_id))?;
+			ensure!(
+				T::ElectionInfo::is_election_closed_for_voting(&election_id),
+				Error::<T>::ElectionNotClosedForVoting
+			);
+			ensure!(
+				!T::ElectionInfo::has_open_repoll(&election_id),
+				Error::<T>::RepollStillOpen
+			);
+			ensure!(
+				resolutions.len() as u32 <= T::MaxBallotsPerTally::get(),
+				Error::<T>::TooManyResolutions
+			);
+			Self::sync_generation(election_id);
+			let (_, first_ballot) = <BallotGeneration<T>>::get(election_id);
+			for (index, resolution) in resolutions.iter() {
+				ensure!(
+					*index >= first_ballot &&
+						<SealedCredentials<T>>::contains_key(election_id, index) &&
+						!<BallotResolutions<T>>::contains_key(election_id, index),
+					Error::<T>::BallotNotPending
+				);
+				let mut ballot = <Ballots<T>>::get(election_id, index).ok_or(Error::<T>::BallotNotPending)?;
+				match resolution {
+					BallotResolution::Counted { weight, region } => {
+						ensure!(*weight > 0, Error::<T>::InvalidResolution);
+						ensure!(
+							<VotedCount<T>>::get(election_id) < T::VoterInfo::voter_count(&election_id),
+							Error::<T>::InvalidResolution
+						);
+						ballot.weight = *weight;
+						<VotedCount<T>>::mutate(election_id, |count| *count = count.saturating_add(1));
+						if let Some(region) = region {
+							<RegionVoted<T>>::mutate(election_id, region, |count| *count = count.saturating_add(1));
+						}
+						if let Some(scope) = ballot.scope {
+							<ConstituencyVoted<T>>::mutate(election_id, scope, |count| *count = count.saturating_add(1));
+						}
+					},
+					BallotResolution::Superseded | BallotResolution::Invalid => ballot.superseded = true,
+				}
+				<Ballots<T>>::insert(election_id, index, ballot);
+				<BallotResolutions<T>>::insert(election_id, index, resolution);
+			}
+			let pending = <PendingResolutions<T>>::mutate(election_id, |pending| {
+				*pending = pending.saturating_sub(resolutions.len() as u32);
+				*pending
+			});
+
+			// Emit an event.
+			Self::deposit_event(Event::AnonymousBallotsResolved{election_id, resolved: resolutions.len() as u32, pending});
 			// Return a successful DispatchResultWithPostInfo
 			Ok(())
 		}
@@ -99,23 +942,813 @@ pub mod pallet {
 }
 
 impl<T: Config> Pallet<T> {
-	pub fn vote_inc(election_id: ElectionId, candidate_id: CandidateId) -> DispatchResult {
-		if <Ballot<T>>::contains_key(election_id, candidate_id) {
-				<Ballot<T>>::mutate(
-					election_id,
-					candidate_id,
-					|count| -> DispatchResult {
-						if let Some(c) = count {
-							*count = Option::from(*c+1);
-						} else {
-							*count = Option::from(1);
+	/// Tracking hash shown to the voter before they choose to cast or spoil
+	pub fn tracking_hash(election_id: ElectionId, choice: &BallotChoice, commitment: &[u8; 32]) -> T::Hash {
+		T::Hashing::hash_of(&(election_id, choice, commitment))
+	}
+
+	/// Check that the commitment cast with a ballot opens to the voter's registered
+	/// weight, which is public; every signed cast path checks it. The context is the
+	/// encoded ballot the proof was made for.
+	fn check_weight(context: &[u8], sealed: &SealedWeight, weight: VoteCount) -> DispatchResult {
+		ensure!(
+			zkp::verify_weight(sealed, weight, context),
+			Error::<T>::CommitmentWeightMismatch
+		);
+		Ok(())
+	}
+
+	/// Check a ballot against the election's counting method and its candidates, which
+	/// holds a quadratic ballot to its budget. NOTA and blank are valid in every election.
+	fn check_ballot(election_id: ElectionId, choice: &BallotChoice) -> DispatchResult {
+		if choice.abstention().is_some() {
+			return Ok(())
+		}
+		let method = T::ElectionInfo::election_config(&election_id).counting_method;
+		match voting_method::for_counting(method, 0) {
+			Some(counting) => counting.validate(choice).map_err(Error::<T>::from)?,
+			None => match (method, choice) {
+				(CountingMethod::Referendum, BallotChoice::Answers(answers)) => Self::check_answers(election_id, answers)?,
+				(CountingMethod::Referendum, _) | (_, BallotChoice::Answers(_)) => {
+					return Err(Error::<T>::WrongBallotType.into())
+				},
+				(_, BallotChoice::Ranked(ranking)) => voting_method::check_distinct(ranking).map_err(Error::<T>::from)?,
+				_ => return Err(Error::<T>::WrongBallotType.into()),
+			},
+		}
+		for candidate_id in choice.candidates().iter() {
+			ensure!(
+				T::CandidateLookup::is_candidate(&election_id, candidate_id),
+				Error::<T>::InvalidCandidate
+			);
+			ensure!(
+				T::CandidateLookup::is_standing(&election_id, candidate_id),
+				Error::<T>::CandidateNotStanding
+			);
+		}
+		Ok(())
+	}
+
+	/// Voting is open for the voter, in the whole election or in a re-poll of their
+	/// constituency
+	fn ensure_open_for(election_id: ElectionId, voter: &AccountIdOf<T>) -> DispatchResult {
+		// A ballot cast from the voter's own account would link their revotes
+		ensure!(
+			!T::ElectionInfo::election_config(&election_id).anonymous,
+			Error::<T>::AnonymousBallotsOnly
+		);
+		let repolling = T::VoterInfo::constituency_of(&election_id, voter)
+			.map_or(false, |constituency| T::ElectionInfo::is_open_for_repoll(&election_id, &constituency));
+		ensure!(
+			T::ElectionInfo::is_election_open_for_voting(&election_id) || repolling,
+			Error::<T>::InvalidElectionIdOrNotOpenForVoting
+		);
+		Ok(())
+	}
+
+	/// Constituency of the voter and its re-poll generation, stamped on their ballot
+	fn ballot_scope(election_id: ElectionId, voter: &AccountIdOf<T>) -> Option<(ConstituencyId, u32)> {
+		T::VoterInfo::constituency_of(&election_id, voter)
+			.map(|constituency| (constituency, T::ElectionInfo::constituency_generation(&election_id, &constituency)))
+	}
+
+	/// A ballot cast before its constituency was re-polled no longer counts
+	fn in_current_scope(election_id: ElectionId, ballot: &CastBallot<T::Hash>) -> bool {
+		ballot.scope.map_or(true, |(constituency, generation)| {
+			T::ElectionInfo::constituency_generation(&election_id, &constituency) == generation
+		})
+	}
+
+	/// Set aside the ballots of earlier generations once the poll has been countermanded
+	/// or re-polled: they stay stored for audit but are neither counted nor superseded,
+	/// and every voter may vote again. Any tally or certified result of the earlier
+	/// poll is discarded. A constituency re-poll restarts the tally the same way, but
+	/// only the ballots of that constituency are set aside.
+	fn sync_generation(election_id: ElectionId) {
+		let repolls = T::ElectionInfo::constituency_repolls(&election_id);
+		if <ConstituencyRepollsCounted<T>>::get(election_id) != repolls {
+			<ConstituencyRepollsCounted<T>>::insert(election_id, repolls);
+			let _ = <ProxyBallots<T>>::clear_prefix(election_id, u32::MAX, None);
+			Self::restart_tally(election_id);
+		}
+		let generation = T::ElectionInfo::repoll_generation(&election_id);
+		if <BallotGeneration<T>>::get(election_id).0 == generation {
+			return
+		}
+		let first_ballot = <BallotCount<T>>::get(election_id);
+		<BallotGeneration<T>>::insert(election_id, (generation, first_ballot));
+		// Schulze pairwise counts and proxy ballots belong to the old poll
+		let _ = <RankedBy<T>>::clear_prefix(election_id, u32::MAX, None);
+		let _ = <RankedAbove<T>>::clear_prefix(election_id, u32::MAX, None);
+		let _ = <ProxyBallots<T>>::clear_prefix(election_id, u32::MAX, None);
+		// Every voter may vote again in the new poll
+		<PendingResolutions<T>>::remove(election_id);
+		<VotedCount<T>>::remove(election_id);
+		let _ = <RegionVoted<T>>::clear_prefix(election_id, u32::MAX, None);
+		let _ = <ConstituencyVoted<T>>::clear_prefix(election_id, u32::MAX, None);
+		Self::reset_tally(election_id);
+		Self::deposit_event(Event::BallotsSetAside{election_id, generation, first_ballot});
+	}
+
+	/// Discard the results so the stored ballots are counted again from the start.
+	/// Schulze pairwise counts are then rebuilt as the ballots are counted rather than
+	/// kept as they are cast.
+	fn restart_tally(election_id: ElectionId) {
+		Self::reset_tally(election_id);
+		if T::ElectionInfo::election_config(&election_id).counting_method == CountingMethod::Schulze {
+			let _ = <RankedBy<T>>::clear_prefix(election_id, u32::MAX, None);
+			let _ = <RankedAbove<T>>::clear_prefix(election_id, u32::MAX, None);
+			<RebuildPreferences<T>>::insert(election_id, true);
+		}
+		Self::deposit_event(Event::TallyRestarted{election_id});
+	}
+
+	/// Mark the tally complete and, during a recount, compare it with the results that
+	/// were certified
+	fn complete_tally(election_id: ElectionId) {
+		<TallyProgress<T>>::insert(election_id, TallyStatus::Complete);
+		<ResultsDigests<T>>::insert(election_id, T::Hashing::hash_of(&(election_id, Self::results_summary(election_id))));
+		Self::deposit_event(Event::TallyCompleted{election_id});
+		if let Some((winners, totals)) = <RecountBaselines<T>>::take(election_id) {
+			let sorted = |totals: Option<Vec<(CandidateId, VoteCount)>>| {
+				totals.map(|mut t| {
+					t.sort();
+					t
+				})
+			};
+			let matched = <Winners<T>>::get(election_id).unwrap_or_default() == winners &&
+				sorted(Self::candidate_totals(&election_id)) == sorted(totals);
+			<RecountMatched<T>>::insert(election_id, matched);
+			Self::deposit_event(Event::RecountCompared{election_id, matched});
+		}
+	}
+
+	/// Remove every result aggregated from the stored ballots, so they can be counted again
+	fn reset_tally(election_id: ElectionId) {
+		<TallyProgress<T>>::remove(election_id);
+		let _ = <Ballot<T>>::clear_prefix(election_id, u32::MAX, None);
+		let _ = <Rankings<T>>::clear_prefix(election_id, u32::MAX, None);
+		let _ = <RankingIndex<T>>::clear_prefix(election_id, u32::MAX, None);
+		<RankingCount<T>>::remove(election_id);
+		<PendingRound<T>>::remove(election_id);
+		let _ = <RunoffRounds<T>>::clear_prefix(election_id, u32::MAX, None);
+		let _ = <StvStages<T>>::clear_prefix(election_id, u32::MAX, None);
+		let _ = <StvParcels<T>>::clear_prefix(election_id, u32::MAX, None);
+		<PendingStage<T>>::remove(election_id);
+		<StvQuota<T>>::remove(election_id);
+		<AbstentionTallies<T>>::remove(election_id);
+		<WriteInCount<T>>::remove(election_id);
+		<WriteInResultsOf<T>>::remove(election_id);
+		let _ = <QuestionTallies<T>>::clear_prefix(election_id, u32::MAX, None);
+		let _ = <QuestionOutcomes<T>>::clear_prefix(election_id, u32::MAX, None);
+		let _ = <VoidedVotes<T>>::clear_prefix(election_id, u32::MAX, None);
+		let _ = <DelegatedWeight<T>>::clear_prefix(election_id, u32::MAX, None);
+		let _ = <QuestionDelegatedWeight<T>>::clear_prefix(election_id, u32::MAX, None);
+		let _ = <WithheldWeight<T>>::clear_prefix(election_id, u32::MAX, None);
+		<Winners<T>>::remove(election_id);
+		<Turnout<T>>::remove(election_id);
+		let _ = <ConstituencyTurnout<T>>::clear_prefix(election_id, u32::MAX, None);
+		let _ = <ConstituencyTotals<T>>::clear_prefix(election_id, u32::MAX, None);
+		<ResultsDigests<T>>::remove(election_id);
+		let _ = <ResultSignatures<T>>::clear_prefix(election_id, u32::MAX, None);
+		<SignatureCount<T>>::remove(election_id);
+		if <Certified<T>>::take(election_id).is_some() {
+			T::ElectionControl::set_election_complete(&election_id, false);
+		}
+	}
+
+	/// Index of the voter's ballot in the current generation of the poll and of their
+	/// constituency's re-poll
+	fn current_ballot(election_id: ElectionId, voter: &AccountIdOf<T>) -> Option<BallotIndex> {
+		let (_, first_ballot) = <BallotGeneration<T>>::get(election_id);
+		<VoterBallot<T>>::get(election_id, voter).filter(|index| {
+			*index >= first_ballot && <Ballots<T>>::get(election_id, index)
+				.map_or(false, |ballot| Self::in_current_scope(election_id, &ballot))
+		})
+	}
+
+	/// Voting weight of a registered voter whose registration a registrar has
+	/// approved; only they may cast
+	fn voter_weight(election_id: ElectionId, voter: &AccountIdOf<T>) -> Result<VoteCount, DispatchError> {
+		let weight = T::VoterInfo::voter_weight(&election_id, voter).ok_or(Error::<T>::NotRegisteredVoter)?;
+		ensure!(T::VoterInfo::is_approved(&election_id, voter), Error::<T>::NotApprovedVoter);
+		Ok(weight)
+	}
+
+	/// Check that a referendum ballot answers every question with one of its options
+	fn check_answers(election_id: ElectionId, answers: &Answers) -> DispatchResult {
+		let questions = T::ElectionInfo::election_questions(&election_id);
+		ensure!(
+			!questions.is_empty() && answers.len() == questions.len(),
+			Error::<T>::InvalidAnswers
+		);
+		for (question, answer) in questions.iter().zip(answers.iter()) {
+			ensure!((*answer as usize) < question.options.len(), Error::<T>::InvalidAnswers);
+		}
+		Ok(())
+	}
+
+	/// Store a ballot until the tally, superseding the voter's earlier ballot when the
+	/// election allows revoting. Schulze ballots go into the pairwise counts right away.
+	/// A ballot whose opening was published by spoiling it can no longer be cast.
+	fn store_ballot(election_id: ElectionId, voter: &AccountIdOf<T>, choice: BallotChoice, tracking_hash: T::Hash, weight: VoteCount) -> DispatchResult {
+		ensure!(
+			!<BallotReceipts<T>>::contains_key(election_id, tracking_hash),
+			Error::<T>::BallotAlreadyCast
+		);
+		ensure!(
+			!<SpoiledBallots<T>>::contains_key(election_id, tracking_hash),
+			Error::<T>::BallotAlreadySpoiled
+		);
+		Self::sync_generation(election_id);
+		let config = T::ElectionInfo::election_config(&election_id);
+		let live_preferences = !<RebuildPreferences<T>>::get(election_id);
+		let (_, first_ballot) = <BallotGeneration<T>>::get(election_id);
+		let voted_in_poll = <VoterBallot<T>>::get(election_id, voter).map_or(false, |index| index >= first_ballot);
+		let current = Self::current_ballot(election_id, voter);
+		if let Some(previous) = current {
+			ensure!(config.allow_revoting, Error::<T>::AlreadyVoted);
+			<Ballots<T>>::mutate(election_id, previous, |ballot| {
+				if let Some(b) = ballot {
+					b.superseded = true;
+					if let (CountingMethod::Schulze, BallotChoice::Ranked(ranking)) = (config.counting_method, &b.choice) {
+						if live_preferences {
+							Self::retract_preferences(election_id, ranking, b.weight);
 						}
+					}
+				}
+			});
+		}
+		if let (CountingMethod::Schulze, BallotChoice::Ranked(ranking)) = (config.counting_method, &choice) {
+			if live_preferences {
+				Self::record_preferences(election_id, ranking, weight)?;
+			}
+		}
+		let index = <BallotCount<T>>::get(election_id);
+		<BallotCount<T>>::insert(election_id, index.checked_add(1).ok_or(ArithmeticError::Overflow)?);
+		let scope = Self::ballot_scope(election_id, voter);
+		<Ballots<T>>::insert(election_id, index, CastBallot{
+			choice,
+			tracking_hash,
+			superseded: false,
+			weight,
+			scope,
+		});
+		<VoterBallot<T>>::insert(election_id, voter, index);
+		<BallotReceipts<T>>::insert(election_id, tracking_hash, <frame_system::Pallet<T>>::block_number());
+		Self::participation_inc(election_id, voter, !voted_in_poll, current.is_none(), scope);
+		Ok(())
+	}
+
+	/// Count a ballot in the block it was cast in, and its voter as having voted: once
+	/// in the poll, overall and in their region, and once in each re-poll of their
+	/// constituency
+	fn participation_inc(election_id: ElectionId, voter: &AccountIdOf<T>, first_in_poll: bool, first_in_scope: bool, scope: Option<(ConstituencyId, u32)>) {
+		let now = <frame_system::Pallet<T>>::block_number();
+		<BlockBallots<T>>::mutate(election_id, now, |count| *count = count.saturating_add(1));
+		if first_in_poll {
+			<VotedCount<T>>::mutate(election_id, |count| *count = count.saturating_add(1));
+			if let Some(region) = T::VoterInfo::region_of(&election_id, voter) {
+				<RegionVoted<T>>::mutate(election_id, region, |count| *count = count.saturating_add(1));
+			}
+		}
+		if let (true, Some(scope)) = (first_in_scope, scope) {
+			<ConstituencyVoted<T>>::mutate(election_id, scope, |count| *count = count.saturating_add(1));
+		}
+	}
+
+	/// Add the weight of the next batch of delegators to the ballot of the first voter
+	/// along their chain of delegates. A delegator who voted directly keeps their own
+	/// ballot, and one whose chain reaches no ballot within the depth limit abstains.
+	/// A question delegated on its own is withheld from the ballot the delegator's
+	/// whole-ballot delegation reaches, and counts on that question only.
+	fn resolve_delegations(election_id: ElectionId, next: u32) -> DispatchResult {
+		let config = T::ElectionInfo::election_config(&election_id);
+		let live_preferences = !<RebuildPreferences<T>>::get(election_id);
+		// The trustees add delegated weight when they resolve anonymous ballots
+		let count = if config.anonymous { 0 } else { T::VoterInfo::delegator_count(&election_id) };
+		let end = next.saturating_add(T::MaxBallotsPerTally::get()).min(count);
+		for index in next..end {
+			let (delegator, topic) = match T::VoterInfo::delegator(&election_id, index) {
+				Some(delegation) => delegation,
+				None => continue,
+			};
+			// A direct vote overrides the delegation
+			if Self::current_ballot(election_id, &delegator).is_some() {
+				continue
+			}
+			// Only an approved voter's weight is delegated
+			let weight = T::VoterInfo::voter_weight(&election_id, &delegator)
+				.filter(|_| T::VoterInfo::is_approved(&election_id, &delegator))
+				.unwrap_or_default();
+			let delegated_ballot = |topic| {
+				T::VoterInfo::resolve_delegate(&election_id, &delegator, topic, |account| {
+					Self::current_ballot(election_id, account).is_some()
+				})
+				.and_then(|d| Self::current_ballot(election_id, &d))
+			};
+			if let DelegationTopic::Question(question) = topic {
+				if let Some(ballot_index) = delegated_ballot(DelegationTopic::All) {
+					<WithheldWeight<T>>::try_mutate(election_id, (ballot_index, question), |withheld| -> DispatchResult {
+						*withheld = withheld.checked_add(weight).ok_or(ArithmeticError::Overflow)?;
 						Ok(())
+					})?;
+				}
+				if let Some(ballot_index) = delegated_ballot(topic) {
+					<QuestionDelegatedWeight<T>>::try_mutate(election_id, (ballot_index, question), |delegated| -> DispatchResult {
+						*delegated = delegated.checked_add(weight).ok_or(ArithmeticError::Overflow)?;
+						Ok(())
+					})?;
+				}
+				continue
+			}
+			if let Some(ballot_index) = delegated_ballot(DelegationTopic::All) {
+				<DelegatedWeight<T>>::try_mutate(election_id, ballot_index, |delegated| -> DispatchResult {
+					*delegated = delegated.checked_add(weight).ok_or(ArithmeticError::Overflow)?;
+					Ok(())
+				})?;
+				// Schulze ballots went into the pairwise counts when they were cast
+				if let (CountingMethod::Schulze, true) = (config.counting_method, live_preferences) {
+					if let Some(BallotChoice::Ranked(ranking)) = <Ballots<T>>::get(election_id, ballot_index).map(|b| b.choice) {
+						Self::record_preferences(election_id, &ranking, weight)?;
+					}
+				}
+			}
+		}
+
+		let status = if end < count {
+			TallyStatus::Delegations { next: end }
+		} else {
+			TallyStatus::Counting { next: <BallotGeneration<T>>::get(election_id).1 }
+		};
+		<TallyProgress<T>>::insert(election_id, status);
+		Self::deposit_event(Event::DelegationsResolved{election_id, resolved: end});
+		Ok(())
+	}
+
+	/// Aggregate the next batch of stored ballots, adding up points under the summing
+	/// methods and grouping identical rankings for instant-runoff and STV
+	fn count_ballots(election_id: ElectionId, next: BallotIndex) -> DispatchResult {
+		let config = T::ElectionInfo::election_config(&election_id);
+		let standing = match config.counting_method {
+			CountingMethod::Borda => T::CandidateLookup::candidates(&election_id).len() as u32,
+			_ => 0,
+		};
+		let method = voting_method::for_counting(config.counting_method, standing);
+		let rebuild = <RebuildPreferences<T>>::get(election_id);
+
+		let count = <BallotCount<T>>::get(election_id);
+		let end = next.saturating_add(T::MaxBallotsPerTally::get()).min(count);
+		for index in next..end {
+			match <Ballots<T>>::get(election_id, index) {
+				Some(ballot) if !ballot.superseded && Self::in_current_scope(election_id, &ballot) => {
+					let weight = ballot.weight
+						.checked_add(<DelegatedWeight<T>>::get(election_id, index))
+						.ok_or(ArithmeticError::Overflow)?;
+					let constituency = ballot.scope.map(|(constituency, _)| constituency);
+					Self::turnout_inc(election_id, constituency, weight)?;
+					if let Some(abstention) = ballot.choice.abstention() {
+						Self::abstention_inc(election_id, abstention, weight)?;
+						continue
+					}
+					if ballot.choice.is_write_in() {
+						<WriteInCount<T>>::try_mutate(election_id, |count| -> DispatchResult {
+							*count = count.checked_add(weight).ok_or(ArithmeticError::Overflow)?;
+							Ok(())
+						})?;
+						continue
+					}
+					// Ranked methods report first preferences by constituency
+					if let (None, Some(constituency), BallotChoice::Ranked(ranking)) = (&method, constituency, &ballot.choice) {
+						if let Some(first) = ranking.first() {
+							Self::constituency_vote_inc(election_id, constituency, *first, weight)?;
+						}
+					}
+					match (&method, ballot.choice) {
+						(Some(counting), choice) => {
+							for (candidate_id, points) in counting.accumulate(&choice) {
+								let count = points.checked_mul(weight).ok_or(ArithmeticError::Overflow)?;
+								if T::CandidateLookup::is_standing(&election_id, &candidate_id) {
+									Self::vote_inc(election_id, candidate_id, count)?;
+									if let Some(constituency) = constituency {
+										Self::constituency_vote_inc(election_id, constituency, candidate_id, count)?;
+									}
+								} else {
+									Self::voided_inc(election_id, candidate_id, count)?;
+								}
+							}
+						},
+						// Schulze ballots are already in the pairwise counts, unless counted again
+						(None, BallotChoice::Ranked(ranking)) if config.counting_method == CountingMethod::Schulze => {
+							if rebuild {
+								Self::record_preferences(election_id, &ranking, weight)?;
+							}
+						},
+						(None, BallotChoice::Ranked(ranking)) => Self::ranking_inc(election_id, ranking, weight)?,
+						(None, BallotChoice::Answers(answers)) => Self::answers_inc(election_id, index, answers, weight)?,
+						(None, _) => {},
+					}
+				},
+				_ => {},
+			}
+		}
+
+		if end < count {
+			<TallyProgress<T>>::insert(election_id, TallyStatus::Counting { next: end });
+			Self::deposit_event(Event::TallyProgressed{election_id, counted: end});
+			return Ok(())
+		}
+		match config.counting_method {
+			CountingMethod::InstantRunoff => {
+				<TallyProgress<T>>::insert(election_id, TallyStatus::Rounds { round: 0, next: 0 });
+				Self::deposit_event(Event::TallyProgressed{election_id, counted: end});
+			},
+			CountingMethod::SingleTransferableVote => {
+				<TallyProgress<T>>::insert(election_id, TallyStatus::Stages { stage: 0, next: 0 });
+				Self::deposit_event(Event::TallyProgressed{election_id, counted: end});
+			},
+			CountingMethod::Schulze => return Self::count_schulze(election_id),
+			_ => {
+				if let Some(counting) = method {
+					let totals: Vec<(CandidateId, VoteCount)> = <Ballot<T>>::iter_prefix(election_id).collect();
+					<Winners<T>>::insert(election_id, counting.result(&totals, config.seats));
+				}
+				Self::complete_tally(election_id);
+			},
+		}
+		Ok(())
+	}
+
+	/// Add a Schulze ballot to the election's pairwise counts
+	fn record_preferences(election_id: ElectionId, ranking: &RankedChoices, weight: VoteCount) -> DispatchResult {
+		for candidate_id in ranking.iter() {
+			<RankedBy<T>>::try_mutate(election_id, candidate_id, |count| -> DispatchResult {
+				*count = count.checked_add(weight).ok_or(ArithmeticError::Overflow)?;
+				Ok(())
+			})?;
+		}
+		for pair in schulze::ranked_pairs(ranking) {
+			<RankedAbove<T>>::try_mutate(election_id, pair, |count| -> DispatchResult {
+				*count = count.checked_add(weight).ok_or(ArithmeticError::Overflow)?;
+				Ok(())
+			})?;
+		}
+		Ok(())
+	}
+
+	/// Take a superseded Schulze ballot back out of the pairwise counts
+	fn retract_preferences(election_id: ElectionId, ranking: &RankedChoices, weight: VoteCount) {
+		for candidate_id in ranking.iter() {
+			<RankedBy<T>>::mutate(election_id, candidate_id, |count| *count = count.saturating_sub(weight));
+		}
+		for pair in schulze::ranked_pairs(ranking) {
+			<RankedAbove<T>>::mutate(election_id, pair, |count| *count = count.saturating_sub(weight));
+		}
+	}
+
+	/// Number of ballots preferring each candidate to each other one, as counted so far
+	pub fn pairwise_matrix(election_id: ElectionId) -> PairwiseMatrix {
+		schulze::matrix(
+			&T::CandidateLookup::candidates(&election_id),
+			|c| <RankedBy<T>>::get(election_id, c),
+			|a, b| <RankedAbove<T>>::get(election_id, (a, b)),
+		)
+	}
+
+	/// Compute the strongest paths over the pairwise counts kept while voting was open
+	fn count_schulze(election_id: ElectionId) -> DispatchResult {
+		let winners = schulze::winners(&Self::pairwise_matrix(election_id));
+		for candidate_id in winners.iter() {
+			Self::deposit_event(Event::CandidateElected{election_id, round: 0, candidate_id: *candidate_id});
+		}
+		<Winners<T>>::insert(election_id, winners);
+		Self::complete_tally(election_id);
+		Ok(())
+	}
+
+	/// Transfer and count the next batch of distinct rankings for an STV stage and, once
+	/// all are counted, elect the candidates reaching the quota or exclude the weakest
+	/// one. The first stage sets the quota.
+	fn count_stv(election_id: ElectionId, stage: u32, next: u32) -> DispatchResult {
+		let seats = T::ElectionInfo::election_config(&election_id).seats;
+		let mut pending = match <PendingStage<T>>::get(election_id) {
+			Some(pending) => pending,
+			None => StageCount::first(&T::CandidateLookup::candidates(&election_id)),
+		};
+		if stage == 0 && next == 0 && (seats == 0 || pending.continuing.is_empty()) {
+			<Winners<T>>::insert(election_id, Vec::<CandidateId>::new());
+			Self::complete_tally(election_id);
+			return Ok(())
+		}
+
+		let rankings = <RankingCount<T>>::get(election_id);
+		let end = next.saturating_add(T::MaxBallotsPerTally::get()).min(rankings);
+		for index in next..end {
+			if let Some((ranking, votes)) = <Rankings<T>>::get(election_id, index) {
+				let parcel = pending.add(&ranking, votes, <StvParcels<T>>::get(election_id, index));
+				<StvParcels<T>>::insert(election_id, index, parcel);
+			}
+		}
+		if end < rankings {
+			<PendingStage<T>>::insert(election_id, pending);
+			<TallyProgress<T>>::insert(election_id, TallyStatus::Stages { stage, next: end });
+			return Ok(())
+		}
+
+		let history: Vec<StvStage> = (0..stage).filter_map(|s| <StvStages<T>>::get(election_id, s)).collect();
+		let mut winners = <Winners<T>>::get(election_id).unwrap_or_default();
+		let outcome = pending.finish(<StvQuota<T>>::get(election_id), seats, winners.len() as u32, &history);
+		for candidate_id in outcome.stage.elected.iter() {
+			Self::deposit_event(Event::CandidateElected{election_id, round: stage, candidate_id: *candidate_id});
+		}
+		if let Some(candidate_id) = outcome.stage.excluded {
+			Self::deposit_event(Event::CandidateEliminated{election_id, round: stage, candidate_id});
+		}
+		winners.extend(outcome.stage.elected.iter().copied());
+		<StvStages<T>>::insert(election_id, stage, outcome.stage);
+		<StvQuota<T>>::insert(election_id, outcome.quota);
+		<Winners<T>>::insert(election_id, winners);
+		match outcome.next {
+			Some(following) => {
+				<PendingStage<T>>::insert(election_id, following);
+				<TallyProgress<T>>::insert(election_id, TallyStatus::Stages { stage: stage + 1, next: 0 });
+			},
+			None => {
+				<PendingStage<T>>::remove(election_id);
+				Self::complete_tally(election_id);
+			},
+		}
+		Ok(())
+	}
+
+	/// Count the next batch of distinct rankings for a runoff round and, once all are
+	/// counted, elect the majority candidate or eliminate the weakest one
+	fn count_runoff_round(election_id: ElectionId, round: u32, next: u32) -> DispatchResult {
+		let history: Vec<IrvRound> = (0..round).filter_map(|r| <RunoffRounds<T>>::get(election_id, r)).collect();
+		let mut pending = <PendingRound<T>>::get(election_id).unwrap_or_else(|| {
+			let continuing: Vec<CandidateId> = T::CandidateLookup::candidates(&election_id)
+				.into_iter()
+				.filter(|c| !history.iter().any(|r| r.outcome == IrvOutcome::Eliminated(*c)))
+				.collect();
+			RoundCount::new(&continuing)
+		});
+
+		let rankings = <RankingCount<T>>::get(election_id);
+		let end = next.saturating_add(T::MaxBallotsPerTally::get()).min(rankings);
+		for index in next..end {
+			if let Some((ranking, votes)) = <Rankings<T>>::get(election_id, index) {
+				pending.add(&ranking, votes);
+			}
+		}
+		if end < rankings {
+			<PendingRound<T>>::insert(election_id, pending);
+			<TallyProgress<T>>::insert(election_id, TallyStatus::Rounds { round, next: end });
+			return Ok(())
+		}
+
+		<PendingRound<T>>::remove(election_id);
+		match pending.finish(&history) {
+			Some(result) => {
+				<RunoffRounds<T>>::insert(election_id, round, &result);
+				match result.outcome {
+					IrvOutcome::Elected(candidate_id) => {
+						<Winners<T>>::insert(election_id, vec![candidate_id]);
+						Self::deposit_event(Event::CandidateElected{election_id, round, candidate_id});
+						Self::complete_tally(election_id);
+					},
+					IrvOutcome::Eliminated(candidate_id) => {
+						<TallyProgress<T>>::insert(election_id, TallyStatus::Rounds { round: round + 1, next: 0 });
+						Self::deposit_event(Event::CandidateEliminated{election_id, round, candidate_id});
 					},
-				)
-			} else {
-				<Ballot<T>>::insert(election_id, candidate_id, 1);
+				}
+			},
+			None => {
+				// No candidates registered, nothing to elect
+				Self::complete_tally(election_id);
+			},
+		}
+		Ok(())
+	}
+
+	fn ranking_inc(election_id: ElectionId, ranking: RankedChoices, weight: VoteCount) -> DispatchResult {
+		match <RankingIndex<T>>::get(election_id, &ranking) {
+			Some(index) => <Rankings<T>>::try_mutate(election_id, index, |entry| -> DispatchResult {
+				if let Some((_, votes)) = entry {
+					*votes = votes.checked_add(weight).ok_or(ArithmeticError::Overflow)?;
+				}
+				Ok(())
+			}),
+			None => {
+				let index = <RankingCount<T>>::get(election_id);
+				<RankingCount<T>>::insert(election_id, index.checked_add(1).ok_or(ArithmeticError::Overflow)?);
+				<RankingIndex<T>>::insert(election_id, &ranking, index);
+				<Rankings<T>>::insert(election_id, index, (ranking, weight));
+				Ok(())
+			},
+		}
+	}
+
+	/// Add a counted ballot to the turnout, overall and in its constituency
+	fn turnout_inc(election_id: ElectionId, constituency: Option<ConstituencyId>, weight: VoteCount) -> DispatchResult {
+		<Turnout<T>>::try_mutate(election_id, |count| -> DispatchResult {
+			*count = count.checked_add(weight).ok_or(ArithmeticError::Overflow)?;
+			Ok(())
+		})?;
+		if let Some(constituency) = constituency {
+			<ConstituencyTurnout<T>>::try_mutate(election_id, constituency, |count| -> DispatchResult {
+				*count = count.checked_add(weight).ok_or(ArithmeticError::Overflow)?;
+				Ok(())
+			})?;
+		}
+		Ok(())
+	}
+
+	fn constituency_vote_inc(election_id: ElectionId, constituency: ConstituencyId, candidate_id: CandidateId, points: VoteCount) -> DispatchResult {
+		<ConstituencyTotals<T>>::try_mutate(election_id, (constituency, candidate_id), |count| -> DispatchResult {
+			*count = count.checked_add(points).ok_or(ArithmeticError::Overflow)?;
+			Ok(())
+		})
+	}
+
+	fn abstention_inc(election_id: ElectionId, abstention: Abstention, weight: VoteCount) -> DispatchResult {
+		<AbstentionTallies<T>>::try_mutate(election_id, |count| -> DispatchResult {
+			let tally = match abstention {
+				Abstention::NoneOfTheAbove => &mut count.none_of_the_above,
+				Abstention::Blank => &mut count.blank,
+			};
+			*tally = tally.checked_add(weight).ok_or(ArithmeticError::Overflow)?;
+			Ok(())
+		})
+	}
+
+	/// Count a referendum ballot's answers, each with the weight delegated to the ballot
+	/// on its question
+	fn answers_inc(election_id: ElectionId, index: BallotIndex, answers: Answers, weight: VoteCount) -> DispatchResult {
+		for (question, option) in answers.into_iter().enumerate() {
+			let question = question as QuestionIndex;
+			let weight = weight
+				.saturating_sub(<WithheldWeight<T>>::get(election_id, (index, question)))
+				.checked_add(<QuestionDelegatedWeight<T>>::get(election_id, (index, question)))
+				.ok_or(ArithmeticError::Overflow)?;
+			<QuestionTallies<T>>::try_mutate(election_id, (question, option), |count| -> DispatchResult {
+				*count = count.checked_add(weight).ok_or(ArithmeticError::Overflow)?;
 				Ok(())
+			})?;
+		}
+		Ok(())
+	}
+
+
+	fn voided_inc(election_id: ElectionId, candidate_id: CandidateId, points: VoteCount) -> DispatchResult {
+		<VoidedVotes<T>>::try_mutate(election_id, candidate_id, |count| -> DispatchResult {
+			*count = count.checked_add(points).ok_or(ArithmeticError::Overflow)?;
+			Ok(())
+		})
+	}
+
+	pub fn vote_inc(election_id: ElectionId, candidate_id: CandidateId, points: VoteCount) -> DispatchResult {
+		<Ballot<T>>::try_mutate(
+			election_id,
+			candidate_id,
+			|count| -> DispatchResult {
+				let total = count.unwrap_or(0).checked_add(points).ok_or(ArithmeticError::Overflow)?;
+				*count = Option::from(total);
+				Ok(())
+			},
+		)
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Votes per candidate behind the results. Ranked methods counted in rounds or
+	/// stages give their first preferences; Schulze and referendums give none.
+	fn candidate_totals(election_id: &ElectionId) -> Option<Vec<(CandidateId, VoteCount)>> {
+		match T::ElectionInfo::election_config(election_id).counting_method {
+			CountingMethod::InstantRunoff => Some(<RunoffRounds<T>>::get(election_id, 0)?.counts),
+			CountingMethod::SingleTransferableVote => Some(
+				<StvStages<T>>::get(election_id, 0)?
+					.totals
+					.into_iter()
+					.map(|(c, v)| (c, v.into_inner() / FixedU128::DIV))
+					.collect(),
+			),
+			CountingMethod::Schulze | CountingMethod::Referendum => None,
+			_ => Some(<Ballot<T>>::iter_prefix(election_id).collect()),
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Results of a tallied election as hashed into its digest: the winners and the votes
+	/// per candidate, overall and per constituency, with the turnout and the candidates
+	/// removed along with the votes voided for them
+	pub fn results_summary(election_id: ElectionId) -> ResultsSummary {
+		let mut totals = Self::candidate_totals(&election_id).unwrap_or_default();
+		totals.sort();
+		let mut constituencies: Vec<ConstituencyResult> = <ConstituencyTurnout<T>>::iter_prefix(election_id)
+			.map(|(constituency, turnout)| ConstituencyResult{constituency, turnout, totals: Vec::new()})
+			.collect();
+		constituencies.sort_by_key(|result| result.constituency);
+		let mut votes: Vec<_> = <ConstituencyTotals<T>>::iter_prefix(election_id).collect();
+		votes.sort();
+		for ((constituency, candidate_id), count) in votes {
+			if let Ok(index) = constituencies.binary_search_by_key(&constituency, |result| result.constituency) {
+				constituencies[index].totals.push((candidate_id, count));
 			}
+		}
+		let mut removals = T::CandidateLookup::removals(&election_id);
+		for removal in removals.iter_mut() {
+			removal.voided = <VoidedVotes<T>>::get(election_id, removal.candidate_id);
+		}
+		ResultsSummary{
+			winners: <Winners<T>>::get(election_id).unwrap_or_default(),
+			totals,
+			constituencies,
+			turnout: <Turnout<T>>::get(election_id),
+			abstentions: <AbstentionTallies<T>>::get(election_id),
+			removals,
+		}
+	}
+
+	/// Live participation in an election: the registrations kept by the voter pallet,
+	/// with the voters who cast a ballot in the current poll. Choices are not revealed.
+	pub fn participation(election_id: ElectionId) -> Participation {
+		let mut participation = T::VoterInfo::registrations(&election_id);
+		participation.total.voted = <VotedCount<T>>::get(election_id);
+		for (region, count) in participation.regions.iter_mut() {
+			count.voted = <RegionVoted<T>>::get(election_id, *region);
+		}
+		for (constituency, count) in participation.constituencies.iter_mut() {
+			let generation = T::ElectionInfo::constituency_generation(&election_id, constituency);
+			count.voted = <ConstituencyVoted<T>>::get(election_id, (*constituency, generation));
+		}
+		participation
+	}
+
+	/// Ballots cast in each block from `from` to `to`, leaving out blocks without any
+	pub fn ballots_per_block(election_id: ElectionId, from: T::BlockNumber, to: T::BlockNumber) -> Vec<(T::BlockNumber, u32)> {
+		let mut blocks: Vec<_> = <BlockBallots<T>>::iter_prefix(election_id)
+			.filter(|(block, _)| *block >= from && *block <= to)
+			.collect();
+		blocks.sort_by_key(|(block, _)| *block);
+		blocks
+	}
+
+	/// Whether the results of an election are still withheld from the runtime API
+	pub fn results_embargoed(election_id: ElectionId) -> bool {
+		<ResultsEmbargo<T>>::get(election_id)
+			.map_or(false, |until| <frame_system::Pallet<T>>::block_number() < until)
+	}
+
+	/// Certified results of an election, once its embargo has passed
+	pub fn certified_results(election_id: ElectionId) -> Option<CertifiedResults<T::Hash, T::BlockNumber>> {
+		if Self::results_embargoed(election_id) || !<Self as ElectionResults>::is_certified(&election_id) {
+			return None
+		}
+		Some(CertifiedResults{
+			digest: <ResultsDigests<T>>::get(election_id)?,
+			certified_at: <Certified<T>>::get(election_id)?,
+			results: Self::results_summary(election_id),
+		})
+	}
+}
+
+impl<T: Config> ElectionResults for Pallet<T> {
+	fn is_certified(election_id: &ElectionId) -> bool {
+		// A result under recount or voided by a petition no longer stands
+		<Certified<T>>::contains_key(election_id) && !matches!(
+			T::ElectionInfo::result_standing(election_id),
+			Some(ResultStanding::RecountOrdered) | Some(ResultStanding::Voided)
+		)
+	}
+	fn candidate_votes(election_id: &ElectionId, candidate_id: &CandidateId) -> Option<VoteCount> {
+		let totals = Self::candidate_totals(election_id)?;
+		Some(totals.iter().find(|(c, _)| c == candidate_id).map(|(_, n)| *n).unwrap_or(0))
+	}
+	fn winners(election_id: &ElectionId) -> Vec<CandidateId> {
+		<Winners<T>>::get(election_id).unwrap_or_default()
+	}
+	fn vote_totals(election_id: &ElectionId) -> Option<Vec<(CandidateId, VoteCount)>> {
+		Self::candidate_totals(election_id)
+	}
+	fn certifications(election_id: &ElectionId) -> u32 {
+		<CertificationCount<T>>::get(election_id)
+	}
+	fn vote_share(election_id: &ElectionId, candidate_id: &CandidateId) -> Option<Perbill> {
+		let totals = Self::candidate_totals(election_id)?;
+		let total = totals.iter().fold(0 as VoteCount, |acc, (_, n)| acc.saturating_add(*n));
+		let votes = totals.iter().find(|(c, _)| c == candidate_id).map(|(_, n)| *n).unwrap_or(0);
+		(total > 0).then(|| Perbill::from_rational(votes, total))
+	}
+	fn stood_in(election_id: &ElectionId, constituency: &ConstituencyId, candidate_id: &CandidateId) -> bool {
+		// Candidates stand on the ballot of every constituency of their election
+		<ConstituencyTurnout<T>>::contains_key(election_id, constituency) &&
+			T::CandidateLookup::is_candidate(election_id, candidate_id)
+	}
+}
+
+impl<T: Config> CertificationInfo<T::BlockNumber> for Pallet<T> {
+	fn certified_at(election_id: &ElectionId) -> Option<T::BlockNumber> {
+		<Certified<T>>::get(election_id)
 	}
 }
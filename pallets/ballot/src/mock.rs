@@ -1,5 +1,15 @@
-use crate as pallet_template;
-use frame_support::traits::{ConstU16, ConstU64};
+use crate as pallet_ballot;
+use frame_support::{
+	parameter_types,
+	traits::{AsEnsureOriginWithArg, ConstU16, ConstU32, ConstU64},
+};
+use frame_system::{EnsureRoot, EnsureSigned};
+use pallet_candidate::CandidateLookup;
+use pallet_voter::VoterInfo;
+use primitives::{
+	CandidateId, ConstituencyId, DelegationTopic, ElectionId, Participation, RegionId, RemovalReason, RemovedCandidate,
+	VoteCount, VoteRule,
+};
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
@@ -17,7 +27,8 @@ frame_support::construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system,
-		BallotModule: pallet_ballot,
+		Election: pallet_election,
+		Ballot: pallet_ballot,
 	}
 );
 
@@ -45,14 +56,127 @@ impl frame_system::Config for Test {
 	type SystemWeightInfo = ();
 	type SS58Prefix = ConstU16<42>;
 	type OnSetCode = ();
-	type MaxConsumers = frame_support::traits::ConstU32<16>;
+	type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_election::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type ElectionCommissionApproveOrigin = AsEnsureOriginWithArg<EnsureRoot<u64>>;
+	type CreateElectionOrigin = EnsureRoot<u64>;
+	type VotingWindowOrigin = EnsureRoot<u64>;
 }
 
-impl pallet_template::Config for Test {
+impl pallet_ballot::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
+	type ElectionInfo = Election;
+	type CandidateLookup = MockCandidates;
+	type VoterInfo = MockVoters;
+	type ElectionCommissionApproveOrigin = AsEnsureOriginWithArg<EnsureRoot<u64>>;
+	type CertifyOrigin = EnsureSigned<u64>;
+	type RequiredSignatures = ConstU32<2>;
+	type TrusteeOrigin = AsEnsureOriginWithArg<EnsureRoot<u64>>;
+	type MaxBallotsPerTally = ConstU32<10>;
+	type ElectionControl = Election;
+}
+
+parameter_types! {
+	/// Candidates of every election, and those withdrawn or disqualified
+	pub static Candidates: Vec<CandidateId> = vec![1, 2, 3];
+	pub static Removed: Vec<CandidateId> = vec![];
+	/// Registered voters with their weight and whether the registrar approved them
+	pub static Voters: Vec<(u64, VoteCount, bool)> = vec![];
+	/// Delegations in the order they were made, as (delegator, topic, delegate)
+	pub static Delegations: Vec<(u64, DelegationTopic, u64)> = vec![];
+	/// Approved proxies, as (proxy, voter)
+	pub static Proxies: Vec<(u64, u64)> = vec![];
+}
+
+/// Candidates as the candidate pallet would report them
+pub struct MockCandidates;
+
+impl CandidateLookup for MockCandidates {
+	fn is_candidate(_: &ElectionId, candidate_id: &CandidateId) -> bool {
+		Candidates::get().contains(candidate_id)
+	}
+	fn is_standing(election_id: &ElectionId, candidate_id: &CandidateId) -> bool {
+		Self::is_candidate(election_id, candidate_id) && !Removed::get().contains(candidate_id)
+	}
+	fn candidates(election_id: &ElectionId) -> Vec<CandidateId> {
+		Candidates::get().into_iter().filter(|c| Self::is_standing(election_id, c)).collect()
+	}
+	/// Removed candidates are withdrawn with their votes voided
+	fn removals(_: &ElectionId) -> Vec<RemovedCandidate> {
+		Removed::get()
+			.into_iter()
+			.map(|candidate_id| RemovedCandidate { candidate_id, reason: RemovalReason::Withdrawn, rule: VoteRule::Void, voided: 0 })
+			.collect()
+	}
+	fn max_candidates() -> u32 {
+		Candidates::get().len() as u32
+	}
+}
+
+/// Registrations as the voter pallet would report them
+pub struct MockVoters;
+
+impl MockVoters {
+	fn delegate_of(delegator: &u64, topic: DelegationTopic) -> Option<u64> {
+		Delegations::get().into_iter().find(|(d, t, _)| d == delegator && *t == topic).map(|(_, _, delegate)| delegate)
+	}
+}
+
+impl VoterInfo<u64> for MockVoters {
+	fn is_voter(_: &ElectionId, account: &u64) -> bool {
+		Voters::get().iter().any(|(a, _, _)| a == account)
+	}
+	fn voter_weight(_: &ElectionId, account: &u64) -> Option<VoteCount> {
+		Voters::get().into_iter().find(|(a, _, _)| a == account).map(|(_, weight, _)| weight)
+	}
+	fn is_approved(_: &ElectionId, account: &u64) -> bool {
+		Voters::get().iter().any(|(a, _, approved)| a == account && *approved)
+	}
+	fn voter_count(_: &ElectionId) -> u32 {
+		Voters::get().len() as u32
+	}
+	fn total_weight(_: &ElectionId) -> VoteCount {
+		Voters::get().iter().map(|(_, weight, _)| weight).sum()
+	}
+	fn delegator_count(_: &ElectionId) -> u32 {
+		Delegations::get().len() as u32
+	}
+	fn delegator(_: &ElectionId, index: u32) -> Option<(u64, DelegationTopic)> {
+		Delegations::get().get(index as usize).map(|(delegator, topic, _)| (*delegator, *topic))
+	}
+	fn max_delegation_depth() -> u32 {
+		3
+	}
+	fn proxy_principal(_: &ElectionId, proxy: &u64) -> Option<u64> {
+		Proxies::get().into_iter().find(|(p, _)| p == proxy).map(|(_, voter)| voter)
+	}
+	fn constituency_of(_: &ElectionId, _: &u64) -> Option<ConstituencyId> {
+		None
+	}
+	fn region_of(_: &ElectionId, _: &u64) -> Option<RegionId> {
+		None
+	}
+	fn registrations(_: &ElectionId) -> Participation {
+		Participation::default()
+	}
+	fn resolve_delegate<F: Fn(&u64) -> bool>(_: &ElectionId, delegator: &u64, topic: DelegationTopic, has_voted: F) -> Option<u64> {
+		let mut current = Self::delegate_of(delegator, topic)?;
+		for _ in 0..Self::max_delegation_depth() {
+			if has_voted(&current) {
+				return Some(current)
+			}
+			current = Self::delegate_of(&current, topic).or_else(|| Self::delegate_of(&current, DelegationTopic::All))?;
+		}
+		None
+	}
 }
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	frame_system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+	let mut ext: sp_io::TestExternalities = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap().into();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
 }
@@ -1,32 +1,391 @@
 use crate::{mock::*, Error, Event};
+use codec::Encode;
 use frame_support::{assert_noop, assert_ok};
+use pallet_candidate::{CertificationInfo, ElectionResults};
+use pallet_election::ElectionInfo;
+use primitives::{
+	zkp, BallotChoice, BallotResolution, CountingMethod, DelegationTopic, ElectionConfig, RemovalReason, RemovedCandidate,
+	ScoredChoices, SealedWeight, TallyStatus, VoteCount, VoteRule,
+};
+use sp_runtime::DispatchError;
+
+fn config(counting_method: CountingMethod) -> ElectionConfig {
+	ElectionConfig { counting_method, seats: 1, ..Default::default() }
+}
+
+/// Election 1 open for voting
+fn open_election(config: ElectionConfig) {
+	assert_ok!(Election::register_election_for_voting(RuntimeOrigin::root(), config));
+	assert_ok!(Election::election_open_for_voting(RuntimeOrigin::root(), 1));
+}
+
+fn close_election() {
+	assert_ok!(Election::election_close_for_voting(RuntimeOrigin::root(), 1));
+}
+
+/// Commitment to the voter's weight with its proof over the ballot, as the voting
+/// client makes it
+fn sealed(voter: u64, weight: VoteCount, choice: &BallotChoice) -> SealedWeight {
+	zkp::prove_weight(weight, &voter.to_le_bytes(), &(1u64, choice).encode())
+}
+
+fn ranked(ranking: &[u64]) -> BallotChoice {
+	BallotChoice::Ranked(ranking.to_vec().try_into().unwrap())
+}
+
+fn vote(voter: u64, candidate_id: u64) {
+	let weight = Voters::get().into_iter().find(|(a, _, _)| *a == voter).unwrap().1;
+	let choice = BallotChoice::Single(candidate_id);
+	assert_ok!(Ballot::cast_vote(RuntimeOrigin::signed(voter), 1, candidate_id, sealed(voter, weight, &choice)));
+}
+
+/// Run the tally to completion
+fn tally() {
+	while Ballot::tally_status(1) != Some(TallyStatus::Complete) {
+		assert_ok!(Ballot::tally_ballots(RuntimeOrigin::signed(9), 1));
+	}
+}
+
+fn certify() {
+	let digest = Ballot::results_digest(1).unwrap();
+	assert_ok!(Ballot::certify_results(RuntimeOrigin::signed(10), 1, digest));
+	assert_ok!(Ballot::certify_results(RuntimeOrigin::signed(11), 1, digest));
+}
+
+#[test]
+fn cast_vote_requires_an_approved_registration() {
+	new_test_ext().execute_with(|| {
+		Voters::set(vec![(1, 1, true), (2, 1, false)]);
+		open_election(config(CountingMethod::Plurality));
+		let choice = BallotChoice::Single(1);
+		assert_noop!(
+			Ballot::cast_vote(RuntimeOrigin::signed(3), 1, 1, sealed(3, 1, &choice)),
+			Error::<Test>::NotRegisteredVoter
+		);
+		assert_noop!(
+			Ballot::cast_vote(RuntimeOrigin::signed(2), 1, 1, sealed(2, 1, &choice)),
+			Error::<Test>::NotApprovedVoter
+		);
+		assert_noop!(
+			Ballot::cast_vote(RuntimeOrigin::signed(1), 1, 1, sealed(1, 5, &choice)),
+			Error::<Test>::CommitmentWeightMismatch
+		);
+		assert_noop!(
+			Ballot::cast_vote(RuntimeOrigin::signed(1), 1, 4, sealed(1, 1, &BallotChoice::Single(4))),
+			Error::<Test>::InvalidCandidate
+		);
+
+		let seal = sealed(1, 1, &choice);
+		assert_ok!(Ballot::cast_vote(RuntimeOrigin::signed(1), 1, 1, seal));
+		let tracking_hash = Ballot::tracking_hash(1, &choice, &seal.commitment);
+		System::assert_last_event(Event::VoteCasted { election_id: 1, tracking_hash }.into());
+		assert_eq!(Ballot::ballot_receipt(1, tracking_hash), Some(1));
+		assert_eq!(Ballot::voted_count(1), 1);
+		assert_noop!(
+			Ballot::cast_vote(RuntimeOrigin::signed(1), 1, 2, sealed(1, 1, &BallotChoice::Single(2))),
+			Error::<Test>::AlreadyVoted
+		);
+
+		close_election();
+		assert_noop!(
+			Ballot::cast_vote(RuntimeOrigin::signed(1), 1, 1, sealed(1, 1, &choice)),
+			Error::<Test>::InvalidElectionIdOrNotOpenForVoting
+		);
+	});
+}
+
+#[test]
+fn spoiled_ballots_cannot_be_cast() {
+	new_test_ext().execute_with(|| {
+		Voters::set(vec![(1, 1, true), (2, 1, false)]);
+		open_election(config(CountingMethod::Plurality));
+		let randomness = |voter: u64| voter.to_le_bytes().to_vec().try_into().unwrap();
+		assert_noop!(
+			Ballot::spoil_ballot(RuntimeOrigin::signed(2), 1, 1, 1, randomness(2)),
+			Error::<Test>::NotApprovedVoter
+		);
+
+		assert_ok!(Ballot::spoil_ballot(RuntimeOrigin::signed(1), 1, 1, 1, randomness(1)));
+		let choice = BallotChoice::Single(1);
+		let seal = sealed(1, 1, &choice);
+		let tracking_hash = Ballot::tracking_hash(1, &choice, &seal.commitment);
+		System::assert_last_event(Event::BallotSpoiled { election_id: 1, tracking_hash }.into());
+		assert_eq!(Ballot::spoiled_ballot(1, tracking_hash).unwrap().zkp_randomness, randomness(1));
+		assert_noop!(
+			Ballot::cast_ballot(RuntimeOrigin::signed(1), 1, choice.clone(), seal),
+			Error::<Test>::BallotAlreadySpoiled
+		);
+		assert_noop!(
+			Ballot::cast_vote(RuntimeOrigin::signed(1), 1, 1, seal),
+			Error::<Test>::BallotAlreadySpoiled
+		);
+		assert_noop!(
+			Ballot::spoil_ballot(RuntimeOrigin::signed(1), 1, 1, 1, randomness(1)),
+			Error::<Test>::BallotAlreadySpoiled
+		);
+
+		// A freshly sealed ballot for the same choice is cast as usual
+		let fresh = zkp::prove_weight(1, b"fresh randomness", &(1u64, &choice).encode());
+		assert_ok!(Ballot::cast_ballot(RuntimeOrigin::signed(1), 1, choice, fresh));
+	});
+}
+
+#[test]
+fn quadratic_ballots_are_held_to_the_budget() {
+	new_test_ext().execute_with(|| {
+		Voters::set(vec![(1, 2, true), (2, 1, true)]);
+		open_election(config(CountingMethod::Quadratic { budget: 9 }));
+		let scored = |votes: &[(u64, u32)]| -> ScoredChoices { votes.to_vec().try_into().unwrap() };
+		let over = BallotChoice::Scored(scored(&[(1, 3), (2, 1)]));
+		assert_noop!(
+			Ballot::cast_quadratic_vote(RuntimeOrigin::signed(1), 1, scored(&[(1, 3), (2, 1)]), sealed(1, 2, &over)),
+			Error::<Test>::OverBudget
+		);
+		assert_noop!(
+			Ballot::cast_ballot(RuntimeOrigin::signed(1), 1, over.clone(), sealed(1, 2, &over)),
+			Error::<Test>::OverBudget
+		);
+		let within = BallotChoice::Scored(scored(&[(1, 2), (2, 2)]));
+		assert_noop!(
+			Ballot::cast_quadratic_vote(RuntimeOrigin::signed(1), 1, scored(&[(1, 2), (2, 2)]), sealed(1, 1, &within)),
+			Error::<Test>::CommitmentWeightMismatch
+		);
+		assert_ok!(Ballot::cast_quadratic_vote(RuntimeOrigin::signed(1), 1, scored(&[(1, 2), (2, 2)]), sealed(1, 2, &within)));
+		let single = BallotChoice::Scored(scored(&[(2, 3)]));
+		assert_ok!(Ballot::cast_ballot(RuntimeOrigin::signed(2), 1, single.clone(), sealed(2, 1, &single)));
+
+		close_election();
+		tally();
+		assert_eq!(Ballot::candidate_votes(&1, &1), Some(4));
+		assert_eq!(Ballot::candidate_votes(&1, &2), Some(7));
+	});
+}
+
+#[test]
+fn votes_of_removed_candidates_are_refused() {
+	new_test_ext().execute_with(|| {
+		Voters::set(vec![(1, 1, true)]);
+		Removed::set(vec![3]);
+		open_election(config(CountingMethod::Plurality));
+		assert_noop!(
+			Ballot::cast_vote(RuntimeOrigin::signed(1), 1, 3, sealed(1, 1, &BallotChoice::Single(3))),
+			Error::<Test>::CandidateNotStanding
+		);
+	});
+}
+
+#[test]
+fn results_summary_records_removals_and_voided_votes() {
+	new_test_ext().execute_with(|| {
+		Voters::set(vec![(1, 1, true), (2, 2, true)]);
+		open_election(config(CountingMethod::Plurality));
+		vote(1, 3);
+		vote(2, 1);
+		Removed::set(vec![3]);
+		close_election();
+		tally();
+
+		let summary = Ballot::results_summary(1);
+		assert_eq!(summary.totals, vec![(1, 2)]);
+		assert_eq!(
+			summary.removals,
+			vec![RemovedCandidate { candidate_id: 3, reason: RemovalReason::Withdrawn, rule: VoteRule::Void, voided: 1 }]
+		);
+	});
+}
+
+#[test]
+fn tally_adds_approved_delegations_and_certifies_with_signatures() {
+	new_test_ext().execute_with(|| {
+		Voters::set(vec![(1, 1, true), (2, 2, true), (3, 1, true), (4, 5, false), (5, 3, true)]);
+		Delegations::set(vec![(4, DelegationTopic::All, 1), (5, DelegationTopic::All, 2)]);
+		open_election(config(CountingMethod::Plurality));
+		vote(1, 1);
+		vote(2, 2);
+		vote(3, 1);
+		assert_noop!(Ballot::tally_ballots(RuntimeOrigin::signed(9), 1), Error::<Test>::ElectionNotClosedForVoting);
+		close_election();
+
+		assert_ok!(Ballot::tally_ballots(RuntimeOrigin::signed(9), 1));
+		System::assert_last_event(Event::DelegationsResolved { election_id: 1, resolved: 2 }.into());
+		assert_ok!(Ballot::tally_ballots(RuntimeOrigin::signed(9), 1));
+		System::assert_last_event(Event::TallyCompleted { election_id: 1 }.into());
+		// The unapproved voter 4 delegates no weight to 1
+		assert_eq!(Ballot::ballot(1, 1), Some(2));
+		assert_eq!(Ballot::ballot(1, 2), Some(5));
+		assert_eq!(Ballot::winners(1), Some(vec![2]));
+		assert_noop!(Ballot::tally_ballots(RuntimeOrigin::signed(9), 1), Error::<Test>::TallyAlreadyComplete);
+
+		let digest = Ballot::results_digest(1).unwrap();
+		assert_noop!(
+			Ballot::certify_results(RuntimeOrigin::signed(10), 1, Default::default()),
+			Error::<Test>::ResultsDigestMismatch
+		);
+		assert_ok!(Ballot::certify_results(RuntimeOrigin::signed(10), 1, digest));
+		System::assert_last_event(Event::ResultsSigned { election_id: 1, commissioner: 10, signatures: 1 }.into());
+		assert!(!<Ballot as ElectionResults>::is_certified(&1));
+		assert_noop!(Ballot::certify_results(RuntimeOrigin::signed(10), 1, digest), Error::<Test>::ResultsAlreadySigned);
+		assert_ok!(Ballot::certify_results(RuntimeOrigin::signed(11), 1, digest));
+		System::assert_last_event(Event::ResultsCertified { election_id: 1, digest }.into());
+		assert!(<Ballot as ElectionResults>::is_certified(&1));
+		assert_eq!(<Ballot as CertificationInfo<u64>>::certified_at(&1), Some(1));
+		assert!(Election::is_election_complete(&1));
+		assert_noop!(Ballot::certify_results(RuntimeOrigin::signed(12), 1, digest), Error::<Test>::ResultsAlreadyCertified);
+	});
+}
+
+#[test]
+fn recount_compares_with_the_certified_results() {
+	new_test_ext().execute_with(|| {
+		Voters::set(vec![(1, 1, true), (2, 2, true)]);
+		open_election(config(CountingMethod::Plurality));
+		vote(1, 1);
+		vote(2, 2);
+		close_election();
+		assert_noop!(Ballot::recount_ballots(RuntimeOrigin::root(), 1), Error::<Test>::ResultsNotCertified);
+		tally();
+		certify();
+
+		assert_noop!(Ballot::recount_ballots(RuntimeOrigin::signed(1), 1), DispatchError::BadOrigin);
+		assert_ok!(Ballot::recount_ballots(RuntimeOrigin::root(), 1));
+		System::assert_last_event(Event::RecountStarted { election_id: 1 }.into());
+		assert!(!<Ballot as ElectionResults>::is_certified(&1));
+		assert!(!Election::is_election_complete(&1));
+		assert_eq!(Ballot::tally_status(1), None);
+		tally();
+		System::assert_last_event(Event::RecountCompared { election_id: 1, matched: true }.into());
+		assert_eq!(Ballot::winners(1), Some(vec![2]));
+	});
+}
+
+#[test]
+fn results_embargo_withholds_certified_results() {
+	new_test_ext().execute_with(|| {
+		Voters::set(vec![(1, 1, true)]);
+		open_election(config(CountingMethod::Plurality));
+		vote(1, 1);
+		assert_noop!(Ballot::set_results_embargo(RuntimeOrigin::signed(1), 1, 10), DispatchError::BadOrigin);
+		assert_ok!(Ballot::set_results_embargo(RuntimeOrigin::root(), 1, 10));
+		System::assert_last_event(Event::ResultsEmbargoSet { election_id: 1, until: 10 }.into());
+		close_election();
+		tally();
+		certify();
+		assert!(Ballot::results_embargoed(1));
+		assert_eq!(Ballot::certified_results(1), None);
+		assert_noop!(Ballot::set_results_embargo(RuntimeOrigin::root(), 1, 20), Error::<Test>::ResultsAlreadyCertified);
+
+		System::set_block_number(10);
+		assert!(!Ballot::results_embargoed(1));
+		let results = Ballot::certified_results(1).unwrap();
+		assert_eq!(results.certified_at, 1);
+		assert_eq!(results.results.winners, vec![1]);
+	});
+}
 
 #[test]
-fn it_works_for_default_value() {
+fn embargoed_schulze_elections_keep_no_live_pairwise_counts() {
 	new_test_ext().execute_with(|| {
-		// Go past genesis block so events get deposited
-		System::set_block_number(1);
-		let election_id = 1;
-		let candidate = 1;
-        let zkp_value = 42;
-        let transcript = b"ZKPExample";
-        let zkp_randomness = b"hello world";
-		// Dispatch a signed extrinsic.
-		assert_ok!(TemplateModule::cast_vote(RuntimeOrigin::signed(1), election_id, candidate, zkp_value, transcript, zkp_randomness));
-		// Read pallet storage and assert an expected result.
-		//assert_eq!(TemplateModule::something(), Some(42));
-		// Assert that the correct event was deposited
-		//System::assert_last_event(Event::SomethingStored { something: 42, who: 1 }.into());
+		Voters::set(vec![(1, 1, true), (2, 1, true), (3, 1, true)]);
+		open_election(config(CountingMethod::Schulze));
+		let choice = ranked(&[1, 2]);
+		assert_ok!(Ballot::cast_ballot(RuntimeOrigin::signed(1), 1, choice.clone(), sealed(1, 1, &choice)));
+		assert_eq!(Ballot::ranked_by(1, 1), 1);
+		assert_eq!(Ballot::ranked_above(1, (1, 2)), 1);
+
+		assert_ok!(Ballot::set_results_embargo(RuntimeOrigin::root(), 1, 10));
+		assert_eq!(Ballot::ranked_by(1, 1), 0);
+		assert_eq!(Ballot::ranked_above(1, (1, 2)), 0);
+		assert!(Ballot::rebuild_preferences(1));
+		let choice = ranked(&[2, 1]);
+		assert_ok!(Ballot::cast_ballot(RuntimeOrigin::signed(2), 1, choice.clone(), sealed(2, 1, &choice)));
+		let choice = ranked(&[1, 3]);
+		assert_ok!(Ballot::cast_ballot(RuntimeOrigin::signed(3), 1, choice.clone(), sealed(3, 1, &choice)));
+		assert_eq!(Ballot::ranked_above(1, (2, 1)), 0);
+
+		// The tally rebuilds the counts from every ballot
+		close_election();
+		tally();
+		assert_eq!(Ballot::ranked_by(1, 1), 3);
+		assert_eq!(Ballot::ranked_above(1, (1, 2)), 1);
+		assert_eq!(Ballot::ranked_above(1, (2, 1)), 1);
+		assert_eq!(Ballot::ranked_above(1, (1, 3)), 1);
+		assert_eq!(Ballot::winners(1), Some(vec![1]));
+	});
+}
+
+#[test]
+fn proxies_cast_once_for_their_voter() {
+	new_test_ext().execute_with(|| {
+		Voters::set(vec![(1, 2, true)]);
+		Proxies::set(vec![(7, 1)]);
+		open_election(config(CountingMethod::Plurality));
+		let choice = BallotChoice::Single(2);
+		assert_noop!(
+			Ballot::cast_proxy_ballot(RuntimeOrigin::signed(8), 1, choice.clone(), sealed(1, 2, &choice)),
+			Error::<Test>::NotApprovedProxy
+		);
+		let seal = sealed(1, 2, &choice);
+		assert_ok!(Ballot::cast_proxy_ballot(RuntimeOrigin::signed(7), 1, choice.clone(), seal));
+		let tracking_hash = Ballot::tracking_hash(1, &choice, &seal.commitment);
+		System::assert_last_event(Event::ProxyVoteCasted { election_id: 1, voter: 1, proxy: 7, tracking_hash }.into());
+		assert_eq!(Ballot::proxy_ballot(1, 1), Some(tracking_hash));
+		let choice = BallotChoice::Single(3);
+		assert_noop!(
+			Ballot::cast_proxy_ballot(RuntimeOrigin::signed(7), 1, choice.clone(), sealed(7, 2, &choice)),
+			Error::<Test>::ProxyAlreadyVoted
+		);
 	});
 }
-/*
+
 #[test]
-fn correct_error_for_none_value() {
+fn anonymous_ballots_are_counted_once_resolved() {
 	new_test_ext().execute_with(|| {
-		// Ensure the expected error is thrown when no value is present.
+		Voters::set(vec![(1, 3, true), (2, 1, true)]);
+		let anonymous = ElectionConfig { allow_revoting: true, anonymous: true, ..config(CountingMethod::Plurality) };
+		open_election(anonymous);
+		let choice = BallotChoice::Single(1);
 		assert_noop!(
-			TemplateModule::cause_error(RuntimeOrigin::signed(1)),
-			Error::<Test>::NoneValue
+			Ballot::cast_vote(RuntimeOrigin::signed(1), 1, 1, sealed(1, 3, &choice)),
+			Error::<Test>::AnonymousBallotsOnly
 		);
+		let credential = |c: u8| vec![c; 8].try_into().unwrap();
+		assert_ok!(Ballot::cast_anonymous_ballot(RuntimeOrigin::signed(20), 1, None, choice.clone(), credential(1)));
+		assert_ok!(Ballot::cast_anonymous_ballot(RuntimeOrigin::signed(20), 1, None, BallotChoice::Single(2), credential(2)));
+		assert_ok!(Ballot::cast_anonymous_ballot(RuntimeOrigin::signed(21), 1, None, BallotChoice::Single(2), credential(3)));
+		assert_eq!(Ballot::pending_resolutions(1), 3);
+
+		assert_noop!(
+			Ballot::resolve_anonymous_ballots(RuntimeOrigin::root(), 1, vec![(0, BallotResolution::Superseded)]),
+			Error::<Test>::ElectionNotClosedForVoting
+		);
+		close_election();
+		assert_noop!(Ballot::tally_ballots(RuntimeOrigin::signed(9), 1), Error::<Test>::BallotsUnresolved);
+		assert_noop!(
+			Ballot::resolve_anonymous_ballots(RuntimeOrigin::signed(1), 1, vec![(0, BallotResolution::Superseded)]),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Ballot::resolve_anonymous_ballots(RuntimeOrigin::root(), 1, vec![(0, BallotResolution::Counted { weight: 0, region: None })]),
+			Error::<Test>::InvalidResolution
+		);
+		assert_ok!(Ballot::resolve_anonymous_ballots(
+			RuntimeOrigin::root(),
+			1,
+			vec![
+				(0, BallotResolution::Superseded),
+				(1, BallotResolution::Counted { weight: 3, region: None }),
+				(2, BallotResolution::Invalid),
+			]
+		));
+		System::assert_last_event(Event::AnonymousBallotsResolved { election_id: 1, resolved: 3, pending: 0 }.into());
+		assert_noop!(
+			Ballot::resolve_anonymous_ballots(RuntimeOrigin::root(), 1, vec![(1, BallotResolution::Invalid)]),
+			Error::<Test>::BallotNotPending
+		);
+
+		tally();
+		assert_eq!(Ballot::ballot(1, 1), None);
+		assert_eq!(Ballot::ballot(1, 2), Some(3));
+		assert_eq!(Ballot::winners(1), Some(vec![2]));
 	});
-}*/
+}
@@ -0,0 +1,39 @@
+//! RPC interface for the candidate pallet, so voters can fetch the disclosure
+//! documents archive nodes index off-chain. The node must run with
+//! `--enable-offchain-indexing true` for the documents to be stored.
+
+use codec::Codec;
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use primitives::disclosure_index_key;
+use sp_core::{
+	offchain::{OffchainStorage, STORAGE_PREFIX},
+	Bytes,
+};
+
+#[rpc(client, server)]
+pub trait CandidateApi<Hash> {
+	#[method(name = "candidate_disclosureDocument")]
+	fn disclosure_document(&self, document_hash: Hash) -> RpcResult<Option<Bytes>>;
+}
+
+/// Provides RPC methods to read the offchain index of candidate disclosures.
+pub struct Candidate<S> {
+	storage: S,
+}
+
+impl<S> Candidate<S> {
+	/// Creates a new instance of the Candidate RPC helper.
+	pub fn new(storage: S) -> Self {
+		Self { storage }
+	}
+}
+
+impl<S, Hash> CandidateApiServer<Hash> for Candidate<S>
+where
+	S: OffchainStorage + 'static,
+	Hash: Codec + Send + Sync + 'static,
+{
+	fn disclosure_document(&self, document_hash: Hash) -> RpcResult<Option<Bytes>> {
+		Ok(self.storage.get(STORAGE_PREFIX, &disclosure_index_key(&document_hash)).map(Into::into))
+	}
+}
@@ -6,9 +6,14 @@
 pub use pallet::*;
 use frame_support::pallet_prelude::*;
 use frame_system::pallet_prelude::*;
-use sp_runtime::ArithmeticError;
-use primitives::{ElectionId, CandidateId, CandidateInfo};
-use pallet_election::ElectionInfo;
+use frame_support::traits::{Currency, EnsureOriginWithArg, Randomness, ReservableCurrency, UnixTime};
+use sp_runtime::{traits::Hash, ArithmeticError, Perbill};
+use primitives::{
+	ElectionId, CandidateId, CandidateInfo, CandidateName, CandidateDetails, VoteCount, is_reserved_candidate,
+	disclosure_index_key, RoleScope, RemovedCandidate, ConstituencyId,
+};
+pub use primitives::{RemovalReason, VoteRule};
+use pallet_election::{ElectionControl, ElectionInfo};
 
 #[cfg(test)]
 mod mock;
@@ -20,6 +25,73 @@ mod tests;
 mod benchmarking;
 
 pub type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
+pub type BalanceOf<T> = <<T as Config>::Currency as Currency<AccountIdOf<T>>>::Balance;
+/// Maximum length of the returning officer's reason for rejecting a nomination
+pub const MAX_REASON_LEN: u32 = 256;
+pub type RejectionReason = BoundedVec<u8, ConstU32<MAX_REASON_LEN>>;
+/// Returning officer's note on a discrepancy in a disclosure, bounded like a reason
+pub type DiscrepancyNote = BoundedVec<u8, ConstU32<MAX_REASON_LEN>>;
+/// Maximum length of an IPFS CID
+pub const MAX_CID_LEN: u32 = 128;
+pub type DocumentCid = BoundedVec<u8, ConstU32<MAX_CID_LEN>>;
+/// Maximum number of disclosures of one candidate
+pub const MAX_DISCLOSURES: u32 = 16;
+/// Maximum size of a disclosure document stored through the offchain index
+pub const MAX_DOCUMENT_LEN: u32 = 1024 * 1024;
+
+/// Where a nomination stands in scrutiny
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum NominationStatus {
+	/// Waiting for the returning officer
+	Pending,
+	/// Accepted and standing as this candidate
+	Accepted(CandidateId),
+	/// Rejected by the returning officer; the deposit was returned
+	Rejected { reason: RejectionReason },
+}
+
+/// A candidate's self-nomination with the deposit reserved for it
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Nomination<Balance> {
+	pub name: CandidateName,
+	pub info: CandidateDetails,
+	pub deposit: Balance,
+	pub status: NominationStatus,
+}
+
+/// What a disclosure document declares
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum DocumentKind {
+	Assets,
+	CriminalRecord,
+	Education,
+	Other,
+}
+
+/// An affidavit a candidate published, by content hash, IPFS CID or both
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Disclosure<Hash> {
+	pub kind: DocumentKind,
+	/// Hash of the document bytes, which archive nodes index off-chain under
+	/// `disclosure_index_key`
+	pub hash: Option<Hash>,
+	pub cid: DocumentCid,
+	/// Unix time in seconds when the document was filed
+	pub filed_at: u64,
+	/// Discrepancy flagged by the returning officer
+	pub discrepancy: Option<DiscrepancyNote>,
+}
+
+/// How the candidates are ordered on the ballot
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum BallotOrdering {
+	/// By name, ties by candidate id
+	Alphabetical,
+	/// Drawn by lot from the runtime's randomness. The lot is only as fair as that
+	/// source: with a collective-flip randomness the block author can bias the draw,
+	/// so it is not tamper-proof and is meant for elections where that is acceptable.
+	ByLot,
+}
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -27,7 +99,6 @@ pub mod pallet {
 
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
-	#[pallet::without_storage_info]
 	pub struct Pallet<T>(_);
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
@@ -36,7 +107,30 @@ pub mod pallet {
 		/// Because this pallet emits events, it depends on the runtime's definition of an event.
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 		type ElectionInfo: ElectionInfo;/// Allowed origins for only election commission
-		type ElectionCommissionApproveOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		type ElectionCommissionApproveOrigin: EnsureOriginWithArg<Self::RuntimeOrigin, RoleScope>;
+		/// Allowed origins for the returning officer who scrutinises nominations
+		type ReturningOfficerOrigin: EnsureOriginWithArg<Self::RuntimeOrigin, RoleScope>;
+		/// Currency the nomination deposit is reserved in
+		type Currency: ReservableCurrency<Self::AccountId>;
+		/// Security deposit reserved with every nomination
+		#[pallet::constant]
+		type NominationDeposit: Get<BalanceOf<Self>>;
+		/// Share of the votes under which a candidate forfeits their deposit
+		#[pallet::constant]
+		type DepositForfeitThreshold: Get<Perbill>;
+		type ElectionResults: ElectionResults;
+		/// Most candidates that can stand in one election at once. Candidates who withdrew
+		/// do not count against it, though their ids are not reissued.
+		#[pallet::constant]
+		type MaxCandidatesPerElection: Get<u32>;
+		/// Source of the lot drawn for the ballot order. The draw is no harder to bias
+		/// than this source, which the block author can influence unless it is a VRF
+		type Randomness: Randomness<Self::Hash, Self::BlockNumber>;
+		/// Pallets keeping state per candidate id, told when a candidate withdraws
+		type CandidateWithdrawal: CandidateWithdrawal;
+		/// Clock for the time disclosures are filed
+		type UnixTime: UnixTime;
+		type ElectionControl: ElectionControl;
 	}
 
 	// The pallet's runtime storage items.
@@ -47,9 +141,50 @@ pub mod pallet {
 	pub type Candidates<T: Config> = StorageDoubleMap<_, Blake2_128Concat, ElectionId, Blake2_128Concat, CandidateId, CandidateInfo>;
 
 	#[pallet::storage]
-	#[pallet::getter(fn election_id_counter)]
-	/// CandidateId counter
-	pub type CandidateIdCounter<T: Config> = StorageValue<_, CandidateId>;
+	#[pallet::getter(fn candidate_id_counter)]
+	/// Last CandidateId issued in each election. Ids are never reissued, so a ballot
+	/// order or party affiliation always refers to the candidate it was made for.
+	pub type CandidateIdCounter<T: Config> = StorageMap<_, Blake2_128Concat, ElectionId, CandidateId, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn candidate_count)]
+	// Number of candidates standing in each election, those who withdrew left out
+	pub type CandidateCount<T: Config> = StorageMap<_, Blake2_128Concat, ElectionId, u32, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn withdrawn_candidate)]
+	// Candidates who withdrew before voting opened, kept under their retired ids
+	pub type WithdrawnCandidates<T: Config> = StorageDoubleMap<_, Blake2_128Concat, ElectionId, Blake2_128Concat, CandidateId, CandidateInfo>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn ballot_order)]
+	// Order of the candidates on the ballot of each election; reset when the candidates change
+	pub type BallotOrder<T: Config> = StorageMap<_, Blake2_128Concat, ElectionId, BoundedVec<CandidateId, T::MaxCandidatesPerElection>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn nominations)]
+	// Self-nominations of candidate accounts with their deposit and scrutiny status
+	pub type Nominations<T: Config> = StorageDoubleMap<_, Blake2_128Concat, ElectionId, Blake2_128Concat, AccountIdOf<T>, Nomination<BalanceOf<T>>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn nominated_account)]
+	// Account standing as each candidate whose nomination was accepted
+	pub type NominatedAccounts<T: Config> = StorageDoubleMap<_, Blake2_128Concat, ElectionId, Blake2_128Concat, CandidateId, AccountIdOf<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn withdrawal_deadline)]
+	// Block from which nominations can no longer be withdrawn
+	pub type WithdrawalDeadlines<T: Config> = StorageMap<_, Blake2_128Concat, ElectionId, T::BlockNumber>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn removal)]
+	// Candidates who stopped standing after the ballots were set, and the rule for their votes
+	pub type Removals<T: Config> = StorageDoubleMap<_, Blake2_128Concat, ElectionId, Blake2_128Concat, CandidateId, (RemovalReason, VoteRule)>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn disclosures)]
+	// Affidavits each candidate published, in filing order
+	pub type Disclosures<T: Config> = StorageDoubleMap<_, Blake2_128Concat, ElectionId, Blake2_128Concat, CandidateId, BoundedVec<Disclosure<T::Hash>, ConstU32<MAX_DISCLOSURES>>, ValueQuery>;
 
 	// Pallets use events to inform users when important changes are made.
 	// https://docs.substrate.io/main-docs/build/events-errors/
@@ -59,6 +194,39 @@ pub mod pallet {
 		/// Voter is successfully registered
 		/// parameters. []
 		VoterRegistered,
+		/// Candidate account nominated themselves and reserved the deposit
+		/// parameters. [election_id, account, deposit]
+		CandidateNominated{election_id: ElectionId, account: AccountIdOf<T>, deposit: BalanceOf<T>},
+		/// Returning officer accepted a nomination
+		/// parameters. [election_id, account, candidate_id]
+		NominationAccepted{election_id: ElectionId, account: AccountIdOf<T>, candidate_id: CandidateId},
+		/// Returning officer rejected a nomination
+		/// parameters. [election_id, account, reason]
+		NominationRejected{election_id: ElectionId, account: AccountIdOf<T>, reason: RejectionReason},
+		/// Candidate withdrew their nomination
+		/// parameters. [election_id, account]
+		NominationWithdrawn{election_id: ElectionId, account: AccountIdOf<T>},
+		/// Commission set the deadline for withdrawing nominations
+		/// parameters. [election_id, deadline]
+		WithdrawalDeadlineSet{election_id: ElectionId, deadline: T::BlockNumber},
+		/// Deposit returned to a candidate after the results
+		/// parameters. [election_id, account, deposit]
+		DepositReturned{election_id: ElectionId, account: AccountIdOf<T>, deposit: BalanceOf<T>},
+		/// Deposit forfeited by a candidate under the vote-share threshold
+		/// parameters. [election_id, account, deposit]
+		DepositForfeited{election_id: ElectionId, account: AccountIdOf<T>, deposit: BalanceOf<T>},
+		/// Commission set the order of the candidates on the ballot
+		/// parameters. [election_id, ordering]
+		BallotOrderSet{election_id: ElectionId, ordering: BallotOrdering},
+		/// Candidate filed a disclosure document
+		/// parameters. [election_id, candidate_id, index, hash]
+		DisclosureFiled{election_id: ElectionId, candidate_id: CandidateId, index: u32, hash: Option<T::Hash>},
+		/// Returning officer flagged a discrepancy in a disclosure
+		/// parameters. [election_id, candidate_id, index]
+		DisclosureFlagged{election_id: ElectionId, candidate_id: CandidateId, index: u32},
+		/// Candidate stopped standing after the ballots were set
+		/// parameters. [election_id, candidate_id, reason, rule]
+		CandidateRemoved{election_id: ElectionId, candidate_id: CandidateId, reason: RemovalReason, rule: VoteRule},
 	}
 
 	// Errors inform users that something went wrong.
@@ -66,6 +234,38 @@ pub mod pallet {
 	pub enum Error<T> {
 		/// Error: Invalid Election Id Or Not Open For Registration.
 		InvalidElectionIdOrNotOpenForRegistration,
+		/// Error: Nominations Closed Once Voting Opens.
+		NominationsClosed,
+		/// Error: Account Already Nominated For This Election.
+		AlreadyNominated,
+		/// Error: No Nomination For This Account.
+		NominationNotFound,
+		/// Error: Nomination Already Scrutinised.
+		NominationNotPending,
+		/// Error: Nomination Not Accepted.
+		NominationNotAccepted,
+		/// Error: Withdrawal Deadline Has Passed.
+		WithdrawalDeadlinePassed,
+		/// Error: Election Results Not Certified.
+		ResultsNotCertified,
+		/// Error: Election Has The Most Candidates Allowed.
+		TooManyCandidates,
+		/// Error: Account Is Not An Accepted Candidate In This Election.
+		NotACandidate,
+		/// Error: Disclosure Has Neither A Document Nor A CID.
+		EmptyDisclosure,
+		/// Error: Disclosure Document Too Large.
+		DocumentTooLarge,
+		/// Error: Candidate Has The Most Disclosures Allowed.
+		TooManyDisclosures,
+		/// Error: Disclosure Not Found.
+		DisclosureNotFound,
+		/// Error: Candidate Not Found.
+		CandidateNotFound,
+		/// Error: Candidate Already Withdrawn Or Disqualified.
+		CandidateAlreadyRemoved,
+		/// Error: Voting Already Closed For This Election.
+		VotingAlreadyClosed,
 	}
 
 	// Dispatchable functions allows users to interact with the pallet and invoke state changes.
@@ -76,10 +276,10 @@ pub mod pallet {
 		/// Self register by voter
 		#[pallet::call_index(0)]
 		#[pallet::weight(10_000 + T::DbWeight::get().writes(1).ref_time())]
-		pub fn register_candidate(origin: OriginFor<T>, election_id: ElectionId, name: Vec<u8>, info: Vec<u8>) -> DispatchResult {
+		pub fn register_candidate(origin: OriginFor<T>, election_id: ElectionId, name: CandidateName, info: CandidateDetails) -> DispatchResult {
 			// Check if the sender is an approved origin or not
-			T::ElectionCommissionApproveOrigin::ensure_origin(origin)?;
-			let id = Self::candidate_id_inc()?;
+			T::ElectionCommissionApproveOrigin::ensure_origin(origin, &RoleScope::Election(election_id))?;
+			let id = Self::candidate_id_inc(election_id)?;
 			ensure!(
 				!&T::ElectionInfo::is_election_open_for_voter_registration(&election_id),
 				Error::<T>::InvalidElectionIdOrNotOpenForRegistration
@@ -96,21 +296,384 @@ pub mod pallet {
 			// Return a successful DispatchResultWithPostInfo
 			Ok(())
 		}
+		/// Self-nomination by a candidate account, reserving the security deposit until
+		/// the nomination is rejected, withdrawn or settled after the results
+		#[pallet::call_index(1)]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4, 2).ref_time())]
+		pub fn nominate_candidate(origin: OriginFor<T>, election_id: ElectionId, name: CandidateName, info: CandidateDetails) -> DispatchResult {
+			let account = ensure_signed(origin)?;
+			Self::ensure_voting_not_opened(election_id)?;
+			ensure!(
+				!<Nominations<T>>::contains_key(election_id, &account),
+				Error::<T>::AlreadyNominated
+			);
+			let deposit = T::NominationDeposit::get();
+			T::Currency::reserve(&account, deposit)?;
+			// Update storage for nomination list for particular election id
+			<Nominations<T>>::insert(election_id, &account, Nomination{
+				name,
+				info,
+				deposit,
+				status: NominationStatus::Pending,
+			});
+
+			// Emit an event.
+			Self::deposit_event(Event::CandidateNominated{election_id, account, deposit});
+			// Return a successful DispatchResultWithPostInfo
+			Ok(())
+		}
+		/// Accept a nomination after scrutiny; the account then stands as a candidate
+		#[pallet::call_index(2)]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4, 4).ref_time())]
+		pub fn accept_nomination(origin: OriginFor<T>, election_id: ElectionId, account: AccountIdOf<T>) -> DispatchResult {
+			// Check if the sender is an approved origin or not
+			T::ReturningOfficerOrigin::ensure_origin(origin, &RoleScope::Election(election_id))?;
+			Self::ensure_voting_not_opened(election_id)?;
+			let mut nomination = <Nominations<T>>::get(election_id, &account).ok_or(Error::<T>::NominationNotFound)?;
+			ensure!(nomination.status == NominationStatus::Pending, Error::<T>::NominationNotPending);
+			let candidate_id = Self::candidate_id_inc(election_id)?;
+			// Update storage for candidate list for particular election id
+			<Candidates<T>>::insert(election_id, candidate_id, CandidateInfo{
+				id: candidate_id,
+				name: nomination.name.clone(),
+				info: nomination.info.clone(),
+			});
+			nomination.status = NominationStatus::Accepted(candidate_id);
+			<Nominations<T>>::insert(election_id, &account, nomination);
+			<NominatedAccounts<T>>::insert(election_id, candidate_id, &account);
+
+			// Emit an event.
+			Self::deposit_event(Event::NominationAccepted{election_id, account, candidate_id});
+			// Return a successful DispatchResultWithPostInfo
+			Ok(())
+		}
+		/// Reject a nomination after scrutiny, giving the reason, and return the deposit
+		#[pallet::call_index(3)]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 2).ref_time())]
+		pub fn reject_nomination(origin: OriginFor<T>, election_id: ElectionId, account: AccountIdOf<T>, reason: RejectionReason) -> DispatchResult {
+			// Check if the sender is an approved origin or not
+			T::ReturningOfficerOrigin::ensure_origin(origin, &RoleScope::Election(election_id))?;
+			let mut nomination = <Nominations<T>>::get(election_id, &account).ok_or(Error::<T>::NominationNotFound)?;
+			ensure!(nomination.status == NominationStatus::Pending, Error::<T>::NominationNotPending);
+			T::Currency::unreserve(&account, nomination.deposit);
+			nomination.status = NominationStatus::Rejected { reason: reason.clone() };
+			<Nominations<T>>::insert(election_id, &account, nomination);
+
+			// Emit an event.
+			Self::deposit_event(Event::NominationRejected{election_id, account, reason});
+			// Return a successful DispatchResultWithPostInfo
+			Ok(())
+		}
+		/// Withdraw a pending or accepted nomination and get the deposit back. Allowed
+		/// until voting opens, and before the withdrawal deadline when one is set; a
+		/// candidate leaving once voting is open has to be removed by the commission.
+		/// The withdrawn candidate's id is retired rather than reissued.
+		#[pallet::call_index(4)]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(6, 8).ref_time())]
+		pub fn withdraw_nomination(origin: OriginFor<T>, election_id: ElectionId) -> DispatchResult {
+			let account = ensure_signed(origin)?;
+			Self::ensure_voting_not_opened(election_id)?;
+			if let Some(deadline) = <WithdrawalDeadlines<T>>::get(election_id) {
+				ensure!(
+					<frame_system::Pallet<T>>::block_number() < deadline,
+					Error::<T>::WithdrawalDeadlinePassed
+				);
+			}
+			let nomination = <Nominations<T>>::get(election_id, &account).ok_or(Error::<T>::NominationNotFound)?;
+			match nomination.status {
+				NominationStatus::Pending => {},
+				NominationStatus::Accepted(candidate_id) => Self::retire_candidate(election_id, candidate_id),
+				NominationStatus::Rejected { .. } => return Err(Error::<T>::NominationNotPending.into()),
+			}
+			T::Currency::unreserve(&account, nomination.deposit);
+			<Nominations<T>>::remove(election_id, &account);
+
+			// Emit an event.
+			Self::deposit_event(Event::NominationWithdrawn{election_id, account});
+			// Return a successful DispatchResultWithPostInfo
+			Ok(())
+		}
+		/// Set the block from which nominations can no longer be withdrawn, until voting
+		/// opens. Withdrawals close when voting opens whatever the deadline.
+		#[pallet::call_index(5)]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 1).ref_time())]
+		pub fn set_withdrawal_deadline(origin: OriginFor<T>, election_id: ElectionId, deadline: T::BlockNumber) -> DispatchResult {
+			// Check if the sender is an approved origin or not
+			T::ElectionCommissionApproveOrigin::ensure_origin(origin, &RoleScope::Election(election_id))?;
+			Self::ensure_voting_not_opened(election_id)?;
+			<WithdrawalDeadlines<T>>::insert(election_id, deadline);
+
+			// Emit an event.
+			Self::deposit_event(Event::WithdrawalDeadlineSet{election_id, deadline});
+			// Return a successful DispatchResultWithPostInfo
+			Ok(())
+		}
+		/// Settle a candidate's deposit once the results are certified: it is forfeited
+		/// when their share of the votes is under `DepositForfeitThreshold` and returned
+		/// otherwise. A candidate removed once the ballots were set is settled by the
+		/// reason instead, as their share no longer measures their support: a withdrawn
+		/// candidate gets the deposit back and a disqualified one forfeits it. Anyone may
+		/// call this.
+		#[pallet::call_index(6)]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(5, 2).ref_time())]
+		pub fn settle_deposit(origin: OriginFor<T>, election_id: ElectionId, account: AccountIdOf<T>) -> DispatchResult {
+			let _ = ensure_signed(origin)?;
+			ensure!(T::ElectionResults::is_certified(&election_id), Error::<T>::ResultsNotCertified);
+			let nomination = <Nominations<T>>::get(election_id, &account).ok_or(Error::<T>::NominationNotFound)?;
+			let candidate_id = match nomination.status {
+				NominationStatus::Accepted(candidate_id) => candidate_id,
+				_ => return Err(Error::<T>::NominationNotAccepted.into()),
+			};
+			<Nominations<T>>::remove(election_id, &account);
+			let deposit = nomination.deposit;
+			let forfeit = match <Removals<T>>::get(election_id, candidate_id) {
+				Some((reason, _)) => reason == RemovalReason::Disqualified,
+				// Methods without a per-candidate share of the votes never forfeit
+				None => T::ElectionResults::vote_share(&election_id, &candidate_id)
+					.map_or(false, |share| share < T::DepositForfeitThreshold::get()),
+			};
+			if forfeit {
+				// The slashed deposit is burned
+				let _ = T::Currency::slash_reserved(&account, deposit);
+				Self::deposit_event(Event::DepositForfeited{election_id, account, deposit});
+			} else {
+				T::Currency::unreserve(&account, deposit);
+				Self::deposit_event(Event::DepositReturned{election_id, account, deposit});
+			}
+			// Return a successful DispatchResultWithPostInfo
+			Ok(())
+		}
+		/// Set the order of the candidates on the ballot, alphabetical or drawn by lot,
+		/// once the candidates are final and before voting opens
+		#[pallet::call_index(7)]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3 + T::MaxCandidatesPerElection::get() as u64, 1).ref_time())]
+		pub fn set_ballot_order(origin: OriginFor<T>, election_id: ElectionId, ordering: BallotOrdering) -> DispatchResult {
+			// Check if the sender is an approved origin or not
+			T::ElectionCommissionApproveOrigin::ensure_origin(origin, &RoleScope::Election(election_id))?;
+			Self::ensure_voting_not_opened(election_id)?;
+			let mut candidates: Vec<CandidateInfo> = <Candidates<T>>::iter_prefix_values(election_id).collect();
+			candidates.sort_by_key(|c| c.id);
+			match ordering {
+				BallotOrdering::Alphabetical => candidates.sort_by(|a, b| a.name.cmp(&b.name).then(a.id.cmp(&b.id))),
+				BallotOrdering::ByLot => {
+					let (seed, _) = T::Randomness::random(&(b"ballot_order", election_id).encode());
+					candidates.sort_by_key(|c| T::Hashing::hash_of(&(seed, c.id)));
+				},
+			}
+			let order: Vec<CandidateId> = candidates.into_iter().map(|c| c.id).collect();
+			let order: BoundedVec<CandidateId, T::MaxCandidatesPerElection> =
+				order.try_into().map_err(|_| Error::<T>::TooManyCandidates)?;
+			<BallotOrder<T>>::insert(election_id, order);
+
+			// Emit an event.
+			Self::deposit_event(Event::BallotOrderSet{election_id, ordering});
+			// Return a successful DispatchResultWithPostInfo
+			Ok(())
+		}
+		/// File a disclosure document of the caller's candidacy by IPFS CID, by its bytes
+		/// or both. The bytes are not kept in state: they are hashed, and archive nodes
+		/// running with offchain indexing store them so voters can fetch them over RPC.
+		#[pallet::call_index(8)]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 1).ref_time() + document.len() as u64)]
+		pub fn file_disclosure(origin: OriginFor<T>, election_id: ElectionId, kind: DocumentKind, cid: DocumentCid, document: Vec<u8>) -> DispatchResult {
+			let account = ensure_signed(origin)?;
+			let candidate_id = Self::candidate_of(&election_id, &account).ok_or(Error::<T>::NotACandidate)?;
+			ensure!(!cid.is_empty() || !document.is_empty(), Error::<T>::EmptyDisclosure);
+			ensure!(document.len() <= MAX_DOCUMENT_LEN as usize, Error::<T>::DocumentTooLarge);
+			let hash = (!document.is_empty()).then(|| T::Hashing::hash(&document));
+			let index = <Disclosures<T>>::try_mutate(election_id, candidate_id, |disclosures| -> Result<u32, DispatchError> {
+				disclosures
+					.try_push(Disclosure{
+						kind,
+						hash,
+						cid,
+						filed_at: T::UnixTime::now().as_secs(),
+						discrepancy: None,
+					})
+					.map_err(|_| Error::<T>::TooManyDisclosures)?;
+				Ok(disclosures.len() as u32 - 1)
+			})?;
+			if let Some(hash) = hash {
+				sp_io::offchain_index::set(&disclosure_index_key(&hash), &document);
+			}
+
+			// Emit an event.
+			Self::deposit_event(Event::DisclosureFiled{election_id, candidate_id, index, hash});
+			// Return a successful DispatchResultWithPostInfo
+			Ok(())
+		}
+		/// Flag a discrepancy in a candidate's disclosure, replacing an earlier note
+		#[pallet::call_index(9)]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1).ref_time())]
+		pub fn flag_disclosure(origin: OriginFor<T>, election_id: ElectionId, candidate_id: CandidateId, index: u32, note: DiscrepancyNote) -> DispatchResult {
+			// Check if the sender is an approved origin or not
+			T::ReturningOfficerOrigin::ensure_origin(origin, &RoleScope::Election(election_id))?;
+			<Disclosures<T>>::try_mutate(election_id, candidate_id, |disclosures| -> DispatchResult {
+				let disclosure = disclosures.get_mut(index as usize).ok_or(Error::<T>::DisclosureNotFound)?;
+				disclosure.discrepancy = Some(note);
+				Ok(())
+			})?;
+
+			// Emit an event.
+			Self::deposit_event(Event::DisclosureFlagged{election_id, candidate_id, index});
+			// Return a successful DispatchResultWithPostInfo
+			Ok(())
+		}
+		/// Mark a candidate as withdrawn or disqualified once the ballots are set, e.g. on
+		/// their death, until voting closes. The candidate stays on the ballot but no new
+		/// votes are taken for them, and `rule` decides what happens to the poll.
+		#[pallet::call_index(10)]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4, 4).ref_time())]
+		pub fn remove_candidate(origin: OriginFor<T>, election_id: ElectionId, candidate_id: CandidateId, reason: RemovalReason, rule: VoteRule) -> DispatchResult {
+			// Check if the sender is an approved origin or not
+			T::ElectionCommissionApproveOrigin::ensure_origin(origin, &RoleScope::Election(election_id))?;
+			ensure!(
+				!T::ElectionInfo::is_election_closed_for_voting(&election_id),
+				Error::<T>::VotingAlreadyClosed
+			);
+			ensure!(
+				<Candidates<T>>::contains_key(election_id, candidate_id),
+				Error::<T>::CandidateNotFound
+			);
+			ensure!(
+				!<Removals<T>>::contains_key(election_id, candidate_id),
+				Error::<T>::CandidateAlreadyRemoved
+			);
+			<Removals<T>>::insert(election_id, candidate_id, (reason, rule));
+			match rule {
+				VoteRule::Void => {},
+				VoteRule::Countermand => T::ElectionControl::countermand(&election_id),
+				VoteRule::Repoll => T::ElectionControl::order_repoll(&election_id),
+			}
+
+			// Emit an event.
+			Self::deposit_event(Event::CandidateRemoved{election_id, candidate_id, reason, rule});
+			// Return a successful DispatchResultWithPostInfo
+			Ok(())
+		}
 	}
 }
 
 impl<T: Config> Pallet<T> {
-	pub fn candidate_id_inc() -> Result<CandidateId, DispatchError> {
-		if CandidateIdCounter::<T>::get().is_some() {
-			let candidate_id = CandidateIdCounter::<T>::get()
-				.unwrap()
-				.checked_add(1)
-				.ok_or(ArithmeticError::Underflow)?;
-			CandidateIdCounter::<T>::set(Option::from(candidate_id));
-			Ok(candidate_id)
-		} else {
-			CandidateIdCounter::<T>::set(Some(1));
-			Ok(1)
+	fn ensure_voting_not_opened(election_id: ElectionId) -> DispatchResult {
+		ensure!(
+			!T::ElectionInfo::is_election_open_for_voting(&election_id) &&
+				!T::ElectionInfo::is_election_closed_for_voting(&election_id),
+			Error::<T>::NominationsClosed
+		);
+		Ok(())
+	}
+
+	/// Issue the next candidate id of an election. Ids run from 1 within each
+	/// election, and a new candidate clears any ballot order already set.
+	pub fn candidate_id_inc(election_id: ElectionId) -> Result<CandidateId, DispatchError> {
+		let count = <CandidateCount<T>>::get(election_id);
+		ensure!(count < T::MaxCandidatesPerElection::get(), Error::<T>::TooManyCandidates);
+		let candidate_id = CandidateIdCounter::<T>::get(election_id)
+			.checked_add(1)
+			.filter(|id| !is_reserved_candidate(*id))
+			.ok_or(ArithmeticError::Overflow)?;
+		CandidateIdCounter::<T>::insert(election_id, candidate_id);
+		<CandidateCount<T>>::insert(election_id, count + 1);
+		<BallotOrder<T>>::remove(election_id);
+		Ok(candidate_id)
+	}
+
+	/// Take a withdrawn candidate off the list under their id, which is not reissued,
+	/// and free their place under `MaxCandidatesPerElection`. Their disclosures stay
+	/// filed under the id.
+	fn retire_candidate(election_id: ElectionId, candidate_id: CandidateId) {
+		if let Some(info) = <Candidates<T>>::take(election_id, candidate_id) {
+			<WithdrawnCandidates<T>>::insert(election_id, candidate_id, info);
+			<CandidateCount<T>>::mutate(election_id, |count| *count = count.saturating_sub(1));
 		}
+		<NominatedAccounts<T>>::remove(election_id, candidate_id);
+		T::CandidateWithdrawal::withdrawn(&election_id, &candidate_id);
+		<BallotOrder<T>>::remove(election_id);
+	}
+}
+
+impl<T: Config> CandidateAccounts<AccountIdOf<T>> for Pallet<T> {
+	fn candidate_of(election_id: &ElectionId, account: &AccountIdOf<T>) -> Option<CandidateId> {
+		match <Nominations<T>>::get(election_id, account)?.status {
+			NominationStatus::Accepted(candidate_id) => Some(candidate_id),
+			_ => None,
+		}
+	}
+}
+
+impl<T: Config> CandidateLookup for Pallet<T> {
+	fn is_candidate(election_id: &ElectionId, candidate_id: &CandidateId) -> bool {
+		<Candidates<T>>::contains_key(election_id, candidate_id)
+	}
+	fn is_standing(election_id: &ElectionId, candidate_id: &CandidateId) -> bool {
+		<Candidates<T>>::contains_key(election_id, candidate_id) && !<Removals<T>>::contains_key(election_id, candidate_id)
+	}
+	fn candidates(election_id: &ElectionId) -> Vec<CandidateId> {
+		<Candidates<T>>::iter_key_prefix(election_id)
+			.filter(|candidate_id| !<Removals<T>>::contains_key(election_id, candidate_id))
+			.collect()
 	}
+	fn removals(election_id: &ElectionId) -> Vec<RemovedCandidate> {
+		let mut removals: Vec<RemovedCandidate> = <Removals<T>>::iter_prefix(election_id)
+			.map(|(candidate_id, (reason, rule))| RemovedCandidate{candidate_id, reason, rule, voided: 0})
+			.collect();
+		removals.sort_by_key(|removal| removal.candidate_id);
+		removals
+	}
+	fn max_candidates() -> u32 {
+		T::MaxCandidatesPerElection::get()
+	}
+}
+
+pub trait CandidateLookup {
+	fn is_candidate(election_id: &ElectionId, candidate_id: &CandidateId) -> bool;
+	/// Candidate on the ballot who has not been withdrawn or disqualified
+	fn is_standing(election_id: &ElectionId, candidate_id: &CandidateId) -> bool;
+	/// Candidates still standing
+	fn candidates(election_id: &ElectionId) -> Vec<CandidateId>;
+	/// Candidates who stopped standing after the ballots were set, in candidate id
+	/// order, with no votes voided yet
+	fn removals(election_id: &ElectionId) -> Vec<RemovedCandidate>;
+	fn max_candidates() -> u32;
+}
+
+/// Pallets keeping state per candidate id, which has to be dropped when a candidate
+/// withdraws
+pub trait CandidateWithdrawal {
+	/// Candidate withdrew before voting opened; their id is retired
+	fn withdrawn(election_id: &ElectionId, candidate_id: &CandidateId);
+}
+
+impl CandidateWithdrawal for () {
+	fn withdrawn(_: &ElectionId, _: &CandidateId) {}
+}
+
+pub trait CandidateAccounts<AccountId> {
+	/// Candidate an account stands as after its nomination was accepted
+	fn candidate_of(election_id: &ElectionId, account: &AccountId) -> Option<CandidateId>;
+}
+
+pub trait ElectionResults {
+	fn is_certified(election_id: &ElectionId) -> bool;
+	/// Votes counted for a candidate, or `None` when the counting method has no
+	/// per-candidate total
+	fn candidate_votes(election_id: &ElectionId, candidate_id: &CandidateId) -> Option<VoteCount>;
+	fn winners(election_id: &ElectionId) -> Vec<CandidateId>;
+	/// Votes counted for every candidate, or `None` when the counting method has no
+	/// per-candidate totals
+	fn vote_totals(election_id: &ElectionId) -> Option<Vec<(CandidateId, VoteCount)>>;
+	/// Number of times the results of an election have been certified, recounts and
+	/// re-polls included
+	fn certifications(election_id: &ElectionId) -> u32;
+	/// Candidate's share of the votes counted for candidates, or `None` when the
+	/// counting method gives no such share
+	fn vote_share(election_id: &ElectionId, candidate_id: &CandidateId) -> Option<Perbill>;
+	/// Whether a candidate was on the ballot of a constituency counted in the results
+	fn stood_in(election_id: &ElectionId, constituency: &ConstituencyId, candidate_id: &CandidateId) -> bool;
+}
+
+pub trait CertificationInfo<BlockNumber> {
+	/// Block in which the results of an election were last certified
+	fn certified_at(election_id: &ElectionId) -> Option<BlockNumber>;
 }
@@ -1,9 +1,17 @@
-use crate as pallet_template;
-use frame_support::traits::{ConstU16, ConstU64};
+use crate as pallet_candidate;
+use crate::{CandidateWithdrawal, ElectionResults};
+use core::time::Duration;
+use frame_support::{
+	parameter_types,
+	traits::{AsEnsureOriginWithArg, ConstU16, ConstU32, ConstU64, Randomness, UnixTime},
+};
+use frame_system::EnsureRoot;
+use primitives::{CandidateId, ConstituencyId, ElectionId, VoteCount};
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
-	traits::{BlakeTwo256, IdentityLookup},
+	traits::{BlakeTwo256, Hash, IdentityLookup},
+	Perbill,
 };
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
@@ -17,7 +25,9 @@ frame_support::construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system,
-		TemplateModule: pallet_template,
+		Balances: pallet_balances,
+		Election: pallet_election,
+		Candidate: pallet_candidate,
 	}
 );
 
@@ -39,20 +49,123 @@ impl frame_system::Config for Test {
 	type BlockHashCount = ConstU64<250>;
 	type Version = ();
 	type PalletInfo = PalletInfo;
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u64>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
 	type SS58Prefix = ConstU16<42>;
 	type OnSetCode = ();
-	type MaxConsumers = frame_support::traits::ConstU32<16>;
+	type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type RuntimeEvent = RuntimeEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+impl pallet_election::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type ElectionCommissionApproveOrigin = AsEnsureOriginWithArg<EnsureRoot<u64>>;
+	type CreateElectionOrigin = EnsureRoot<u64>;
+	type VotingWindowOrigin = EnsureRoot<u64>;
+}
+
+pub const DEPOSIT: u64 = 50;
+pub const MAX_CANDIDATES: u32 = 3;
+
+parameter_types! {
+	pub const ForfeitThreshold: Perbill = Perbill::from_percent(5);
+	/// Whether the results of every election are certified
+	pub static Certified: bool = false;
+	/// Share of the votes of each candidate
+	pub static Shares: Vec<(CandidateId, Perbill)> = vec![];
+	/// Unix time in seconds
+	pub static Now: u64 = 0;
+	/// Candidate ids reported withdrawn to `CandidateWithdrawal`
+	pub static Withdrawn: Vec<CandidateId> = vec![];
 }
 
-impl pallet_template::Config for Test {
+impl pallet_candidate::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
+	type ElectionInfo = Election;
+	type ElectionCommissionApproveOrigin = AsEnsureOriginWithArg<EnsureRoot<u64>>;
+	type ReturningOfficerOrigin = AsEnsureOriginWithArg<EnsureRoot<u64>>;
+	type Currency = Balances;
+	type NominationDeposit = ConstU64<DEPOSIT>;
+	type DepositForfeitThreshold = ForfeitThreshold;
+	type ElectionResults = MockResults;
+	type MaxCandidatesPerElection = ConstU32<MAX_CANDIDATES>;
+	type Randomness = MockRandomness;
+	type CandidateWithdrawal = MockWithdrawal;
+	type UnixTime = MockTime;
+	type ElectionControl = Election;
+}
+
+/// Results as the ballot pallet would report them once certified
+pub struct MockResults;
+
+impl ElectionResults for MockResults {
+	fn is_certified(_: &ElectionId) -> bool {
+		Certified::get()
+	}
+	fn candidate_votes(_: &ElectionId, _: &CandidateId) -> Option<VoteCount> {
+		None
+	}
+	fn winners(_: &ElectionId) -> Vec<CandidateId> {
+		vec![]
+	}
+	fn vote_totals(_: &ElectionId) -> Option<Vec<(CandidateId, VoteCount)>> {
+		None
+	}
+	fn certifications(_: &ElectionId) -> u32 {
+		Certified::get() as u32
+	}
+	fn vote_share(_: &ElectionId, candidate_id: &CandidateId) -> Option<Perbill> {
+		Shares::get().into_iter().find(|(c, _)| c == candidate_id).map(|(_, share)| share)
+	}
+	fn stood_in(_: &ElectionId, _: &ConstituencyId, _: &CandidateId) -> bool {
+		true
+	}
+}
+
+pub struct MockRandomness;
+
+impl Randomness<H256, u64> for MockRandomness {
+	fn random(subject: &[u8]) -> (H256, u64) {
+		(BlakeTwo256::hash(subject), 0)
+	}
+}
+
+pub struct MockWithdrawal;
+
+impl CandidateWithdrawal for MockWithdrawal {
+	fn withdrawn(_: &ElectionId, candidate_id: &CandidateId) {
+		Withdrawn::mutate(|w| w.push(*candidate_id));
+	}
+}
+
+pub struct MockTime;
+
+impl UnixTime for MockTime {
+	fn now() -> Duration {
+		Duration::from_secs(Now::get())
+	}
 }
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	frame_system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> { balances: (1..=4).map(|a| (a, 1_000)).collect() }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	let mut ext: sp_io::TestExternalities = t.into();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
 }
@@ -1,27 +1,293 @@
-use crate::{mock::*, Error, Event};
+use crate::{mock::*, BallotOrdering, CandidateLookup, DocumentKind, Error, Event, NominationStatus, RemovalReason, VoteRule};
 use frame_support::{assert_noop, assert_ok};
+use pallet_election::ElectionInfo;
+use primitives::ElectionConfig;
+use sp_core::H256;
+use sp_runtime::{traits::{BlakeTwo256, Hash}, DispatchError, Perbill};
+
+fn bounded<S: TryFrom<Vec<u8>>>(s: &str) -> S
+where
+	<S as TryFrom<Vec<u8>>>::Error: core::fmt::Debug,
+{
+	s.as_bytes().to_vec().try_into().unwrap()
+}
+
+/// Election 1, registered and not yet open for voting
+fn election() {
+	assert_ok!(Election::register_election_for_voting(RuntimeOrigin::root(), ElectionConfig { seats: 1, ..Default::default() }));
+}
+
+fn nominate(account: u64, name: &str) {
+	assert_ok!(Candidate::nominate_candidate(RuntimeOrigin::signed(account), 1, bounded(name), bounded("")));
+}
+
+/// Nominate and accept each account in turn, so account `n` of the list stands as
+/// candidate `n + 1`
+fn stand(accounts: &[(u64, &str)]) {
+	for (account, name) in accounts {
+		nominate(*account, name);
+		assert_ok!(Candidate::accept_nomination(RuntimeOrigin::root(), 1, *account));
+	}
+}
 
 #[test]
-fn it_works_for_default_value() {
+fn nominate_candidate_reserves_the_deposit() {
 	new_test_ext().execute_with(|| {
-		// Go past genesis block so events get deposited
-		System::set_block_number(1);
-		// Dispatch a signed extrinsic.
-		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(1), 42));
-		// Read pallet storage and assert an expected result.
-		assert_eq!(TemplateModule::something(), Some(42));
-		// Assert that the correct event was deposited
-		System::assert_last_event(Event::SomethingStored { something: 42, who: 1 }.into());
+		election();
+		nominate(1, "Alice");
+		System::assert_last_event(Event::CandidateNominated { election_id: 1, account: 1, deposit: DEPOSIT }.into());
+		assert_eq!(Balances::reserved_balance(1), DEPOSIT);
+		assert_eq!(Candidate::nominations(1, 1).unwrap().status, NominationStatus::Pending);
+		assert_noop!(
+			Candidate::nominate_candidate(RuntimeOrigin::signed(1), 1, bounded("Alice"), bounded("")),
+			Error::<Test>::AlreadyNominated
+		);
 	});
 }
 
 #[test]
-fn correct_error_for_none_value() {
+fn nominations_close_once_voting_opens() {
 	new_test_ext().execute_with(|| {
-		// Ensure the expected error is thrown when no value is present.
+		election();
+		nominate(1, "Alice");
+		assert_ok!(Election::election_open_for_voting(RuntimeOrigin::root(), 1));
 		assert_noop!(
-			TemplateModule::cause_error(RuntimeOrigin::signed(1)),
-			Error::<Test>::NoneValue
+			Candidate::nominate_candidate(RuntimeOrigin::signed(2), 1, bounded("Bob"), bounded("")),
+			Error::<Test>::NominationsClosed
 		);
+		assert_noop!(Candidate::accept_nomination(RuntimeOrigin::root(), 1, 1), Error::<Test>::NominationsClosed);
+		assert_noop!(Candidate::withdraw_nomination(RuntimeOrigin::signed(1), 1), Error::<Test>::NominationsClosed);
+	});
+}
+
+#[test]
+fn accept_and_reject_nominations() {
+	new_test_ext().execute_with(|| {
+		election();
+		nominate(1, "Alice");
+		nominate(2, "Bob");
+		assert_noop!(Candidate::accept_nomination(RuntimeOrigin::signed(1), 1, 1), DispatchError::BadOrigin);
+		assert_noop!(Candidate::accept_nomination(RuntimeOrigin::root(), 1, 3), Error::<Test>::NominationNotFound);
+
+		assert_ok!(Candidate::accept_nomination(RuntimeOrigin::root(), 1, 1));
+		System::assert_last_event(Event::NominationAccepted { election_id: 1, account: 1, candidate_id: 1 }.into());
+		assert_eq!(Candidate::candidates(1, 1).unwrap().name, bounded::<primitives::CandidateName>("Alice"));
+		assert_eq!(Candidate::nominated_account(1, 1), Some(1));
+		assert_eq!(Candidate::candidate_id_counter(1), 1);
+		assert_noop!(Candidate::accept_nomination(RuntimeOrigin::root(), 1, 1), Error::<Test>::NominationNotPending);
+
+		assert_ok!(Candidate::reject_nomination(RuntimeOrigin::root(), 1, 2, bounded("Not eligible")));
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_eq!(Candidate::nominations(1, 2).unwrap().status, NominationStatus::Rejected { reason: bounded("Not eligible") });
+		assert_noop!(Candidate::accept_nomination(RuntimeOrigin::root(), 1, 2), Error::<Test>::NominationNotPending);
+		assert_noop!(Candidate::withdraw_nomination(RuntimeOrigin::signed(2), 1), Error::<Test>::NominationNotPending);
+	});
+}
+
+#[test]
+fn withdrawing_retires_the_candidate_id() {
+	new_test_ext().execute_with(|| {
+		election();
+		stand(&[(1, "Alice"), (2, "Bob"), (3, "Carol")]);
+		assert_ok!(Candidate::file_disclosure(RuntimeOrigin::signed(1), 1, DocumentKind::Assets, bounded("cid"), vec![]));
+		assert_ok!(Candidate::set_ballot_order(RuntimeOrigin::root(), 1, BallotOrdering::Alphabetical));
+
+		assert_ok!(Candidate::withdraw_nomination(RuntimeOrigin::signed(1), 1));
+		System::assert_last_event(Event::NominationWithdrawn { election_id: 1, account: 1 }.into());
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Candidate::nominations(1, 1), None);
+
+		// Nobody moves into the freed id
+		assert_eq!(Candidate::candidates(1, 1), None);
+		assert_eq!(Candidate::withdrawn_candidate(1, 1).unwrap().name, bounded::<primitives::CandidateName>("Alice"));
+		assert_eq!(Candidate::candidates(1, 3).unwrap().name, bounded::<primitives::CandidateName>("Carol"));
+		assert_eq!(Candidate::nominations(1, 3).unwrap().status, NominationStatus::Accepted(3));
+		assert_eq!(Candidate::nominated_account(1, 1), None);
+		assert_eq!(Candidate::nominated_account(1, 3), Some(3));
+		assert_eq!(Candidate::disclosures(1, 1).len(), 1);
+		assert_eq!(Candidate::candidate_id_counter(1), 3);
+		assert_eq!(Candidate::candidate_count(1), 2);
+		assert!(Candidate::ballot_order(1).is_empty());
+		assert_eq!(Withdrawn::get(), vec![1]);
+		assert_eq!(<Candidate as CandidateLookup>::candidates(&1), vec![2, 3]);
+	});
+}
+
+#[test]
+fn candidate_cap_counts_standing_candidates() {
+	new_test_ext().execute_with(|| {
+		election();
+		stand(&[(1, "Alice"), (2, "Bob"), (3, "Carol")]);
+		nominate(4, "Dave");
+		assert_noop!(Candidate::accept_nomination(RuntimeOrigin::root(), 1, 4), Error::<Test>::TooManyCandidates);
+		assert_ok!(Candidate::withdraw_nomination(RuntimeOrigin::signed(2), 1));
+		assert_ok!(Candidate::accept_nomination(RuntimeOrigin::root(), 1, 4));
+		assert_eq!(Candidate::nominations(1, 4).unwrap().status, NominationStatus::Accepted(MAX_CANDIDATES as u64 + 1));
+		assert_eq!(Candidate::candidate_count(1), MAX_CANDIDATES);
+		assert_eq!(Candidate::withdrawn_candidate(1, 2).unwrap().id, 2);
+	});
+}
+
+#[test]
+fn withdrawal_deadline_is_enforced() {
+	new_test_ext().execute_with(|| {
+		election();
+		nominate(1, "Alice");
+		assert_noop!(Candidate::set_withdrawal_deadline(RuntimeOrigin::signed(1), 1, 5), DispatchError::BadOrigin);
+		assert_ok!(Candidate::set_withdrawal_deadline(RuntimeOrigin::root(), 1, 5));
+		System::assert_last_event(Event::WithdrawalDeadlineSet { election_id: 1, deadline: 5 }.into());
+		System::set_block_number(5);
+		assert_noop!(Candidate::withdraw_nomination(RuntimeOrigin::signed(1), 1), Error::<Test>::WithdrawalDeadlinePassed);
+		System::set_block_number(4);
+		assert_ok!(Candidate::withdraw_nomination(RuntimeOrigin::signed(1), 1));
+		assert_eq!(Balances::reserved_balance(1), 0);
+	});
+}
+
+#[test]
+fn settle_deposit_by_vote_share() {
+	new_test_ext().execute_with(|| {
+		election();
+		stand(&[(1, "Alice"), (2, "Bob")]);
+		nominate(3, "Carol");
+		assert_noop!(Candidate::settle_deposit(RuntimeOrigin::signed(4), 1, 1), Error::<Test>::ResultsNotCertified);
+		Certified::set(true);
+		Shares::set(vec![(1, Perbill::from_percent(90)), (2, Perbill::from_percent(4))]);
+		assert_noop!(Candidate::settle_deposit(RuntimeOrigin::signed(4), 1, 3), Error::<Test>::NominationNotAccepted);
+
+		assert_ok!(Candidate::settle_deposit(RuntimeOrigin::signed(4), 1, 1));
+		System::assert_last_event(Event::DepositReturned { election_id: 1, account: 1, deposit: DEPOSIT }.into());
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 1_000);
+
+		assert_ok!(Candidate::settle_deposit(RuntimeOrigin::signed(4), 1, 2));
+		System::assert_last_event(Event::DepositForfeited { election_id: 1, account: 2, deposit: DEPOSIT }.into());
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_eq!(Balances::free_balance(2), 1_000 - DEPOSIT);
+		assert_noop!(Candidate::settle_deposit(RuntimeOrigin::signed(4), 1, 2), Error::<Test>::NominationNotFound);
+	});
+}
+
+#[test]
+fn settle_deposit_of_removed_candidates_by_the_reason() {
+	new_test_ext().execute_with(|| {
+		election();
+		stand(&[(1, "Alice"), (2, "Bob"), (3, "Carol")]);
+		assert_ok!(Election::election_open_for_voting(RuntimeOrigin::root(), 1));
+		assert_ok!(Candidate::remove_candidate(RuntimeOrigin::root(), 1, 1, RemovalReason::Withdrawn, VoteRule::Void));
+		assert_ok!(Candidate::remove_candidate(RuntimeOrigin::root(), 1, 2, RemovalReason::Disqualified, VoteRule::Void));
+		Certified::set(true);
+		// Their votes were voided, so both are under the threshold
+		Shares::set(vec![(1, Perbill::zero()), (2, Perbill::zero()), (3, Perbill::one())]);
+
+		assert_ok!(Candidate::settle_deposit(RuntimeOrigin::signed(4), 1, 1));
+		System::assert_last_event(Event::DepositReturned { election_id: 1, account: 1, deposit: DEPOSIT }.into());
+		assert_eq!(Balances::free_balance(1), 1_000);
+
+		assert_ok!(Candidate::settle_deposit(RuntimeOrigin::signed(4), 1, 2));
+		System::assert_last_event(Event::DepositForfeited { election_id: 1, account: 2, deposit: DEPOSIT }.into());
+		assert_eq!(Balances::free_balance(2), 1_000 - DEPOSIT);
+	});
+}
+
+#[test]
+fn set_ballot_order_alphabetical_and_by_lot() {
+	new_test_ext().execute_with(|| {
+		election();
+		stand(&[(1, "Carol"), (2, "Alice"), (3, "Bob")]);
+		assert_ok!(Candidate::set_ballot_order(RuntimeOrigin::root(), 1, BallotOrdering::Alphabetical));
+		System::assert_last_event(Event::BallotOrderSet { election_id: 1, ordering: BallotOrdering::Alphabetical }.into());
+		assert_eq!(Candidate::ballot_order(1).into_inner(), vec![2, 3, 1]);
+
+		assert_ok!(Candidate::set_ballot_order(RuntimeOrigin::root(), 1, BallotOrdering::ByLot));
+		let mut order = Candidate::ballot_order(1).into_inner();
+		order.sort();
+		assert_eq!(order, vec![1, 2, 3]);
+
+		assert_ok!(Election::election_open_for_voting(RuntimeOrigin::root(), 1));
+		assert_noop!(
+			Candidate::set_ballot_order(RuntimeOrigin::root(), 1, BallotOrdering::Alphabetical),
+			Error::<Test>::NominationsClosed
+		);
+	});
+}
+
+#[test]
+fn file_and_flag_disclosures() {
+	new_test_ext().execute_with(|| {
+		election();
+		stand(&[(1, "Alice")]);
+		Now::set(1_700_000_000);
+		assert_noop!(
+			Candidate::file_disclosure(RuntimeOrigin::signed(2), 1, DocumentKind::Assets, bounded("cid"), vec![]),
+			Error::<Test>::NotACandidate
+		);
+		assert_noop!(
+			Candidate::file_disclosure(RuntimeOrigin::signed(1), 1, DocumentKind::Assets, bounded(""), vec![]),
+			Error::<Test>::EmptyDisclosure
+		);
+
+		let document = b"assets".to_vec();
+		let hash: H256 = BlakeTwo256::hash(&document);
+		assert_ok!(Candidate::file_disclosure(RuntimeOrigin::signed(1), 1, DocumentKind::Assets, bounded(""), document));
+		System::assert_last_event(Event::DisclosureFiled { election_id: 1, candidate_id: 1, index: 0, hash: Some(hash) }.into());
+		let disclosure = &Candidate::disclosures(1, 1)[0];
+		assert_eq!((disclosure.hash, disclosure.filed_at), (Some(hash), 1_700_000_000));
+
+		assert_noop!(
+			Candidate::flag_disclosure(RuntimeOrigin::root(), 1, 1, 1, bounded("Understated")),
+			Error::<Test>::DisclosureNotFound
+		);
+		assert_ok!(Candidate::flag_disclosure(RuntimeOrigin::root(), 1, 1, 0, bounded("Understated")));
+		System::assert_last_event(Event::DisclosureFlagged { election_id: 1, candidate_id: 1, index: 0 }.into());
+		assert_eq!(Candidate::disclosures(1, 1)[0].discrepancy, Some(bounded("Understated")));
+	});
+}
+
+#[test]
+fn remove_candidate_void_keeps_the_poll() {
+	new_test_ext().execute_with(|| {
+		election();
+		stand(&[(1, "Alice"), (2, "Bob")]);
+		assert_ok!(Election::election_open_for_voting(RuntimeOrigin::root(), 1));
+		assert_noop!(
+			Candidate::remove_candidate(RuntimeOrigin::root(), 1, 3, RemovalReason::Withdrawn, VoteRule::Void),
+			Error::<Test>::CandidateNotFound
+		);
+		assert_ok!(Candidate::remove_candidate(RuntimeOrigin::root(), 1, 1, RemovalReason::Withdrawn, VoteRule::Void));
+		System::assert_last_event(
+			Event::CandidateRemoved { election_id: 1, candidate_id: 1, reason: RemovalReason::Withdrawn, rule: VoteRule::Void }.into(),
+		);
+		assert!(Election::is_election_open_for_voting(&1));
+		assert!(<Candidate as CandidateLookup>::is_candidate(&1, &1));
+		assert!(!<Candidate as CandidateLookup>::is_standing(&1, &1));
+		assert_eq!(<Candidate as CandidateLookup>::candidates(&1), vec![2]);
+		assert_noop!(
+			Candidate::remove_candidate(RuntimeOrigin::root(), 1, 1, RemovalReason::Disqualified, VoteRule::Void),
+			Error::<Test>::CandidateAlreadyRemoved
+		);
+
+		assert_ok!(Election::election_close_for_voting(RuntimeOrigin::root(), 1));
+		assert_noop!(
+			Candidate::remove_candidate(RuntimeOrigin::root(), 1, 2, RemovalReason::Disqualified, VoteRule::Void),
+			Error::<Test>::VotingAlreadyClosed
+		);
+	});
+}
+
+#[test]
+fn remove_candidate_countermands_or_repolls() {
+	new_test_ext().execute_with(|| {
+		election();
+		stand(&[(1, "Alice"), (2, "Bob")]);
+		assert_ok!(Election::election_open_for_voting(RuntimeOrigin::root(), 1));
+		assert_ok!(Candidate::remove_candidate(RuntimeOrigin::root(), 1, 1, RemovalReason::Disqualified, VoteRule::Repoll));
+		assert_eq!(Election::repoll_generation(&1), 1);
+		assert!(Election::is_election_open_for_voting(&1));
+
+		assert_ok!(Candidate::remove_candidate(RuntimeOrigin::root(), 1, 2, RemovalReason::Withdrawn, VoteRule::Countermand));
+		assert_eq!(Election::repoll_generation(&1), 2);
+		assert!(Election::is_countermanded(&1));
+		assert!(!Election::is_election_open_for_voting(&1));
 	});
 }
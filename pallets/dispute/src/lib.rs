@@ -0,0 +1,271 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// Edit this file to define custom logic or remove it if it is not needed.
+/// Learn more about FRAME and the core library of Substrate FRAME pallets:
+/// <https://docs.substrate.io/reference/frame-pallets/>
+pub use pallet::*;
+use frame_support::pallet_prelude::*;
+use frame_system::pallet_prelude::*;
+use frame_support::traits::{Currency, ReservableCurrency};
+use sp_runtime::ArithmeticError;
+use primitives::{ElectionId, CandidateId, ConstituencyId};
+use pallet_candidate::{CandidateAccounts, CertificationInfo, ElectionResults};
+use pallet_election::{ElectionControl, ElectionInfo, ResultStanding};
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+pub type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
+pub type BalanceOf<T> = <<T as Config>::Currency as Currency<AccountIdOf<T>>>::Balance;
+/// The type for identifying the election petitions
+pub type PetitionId = u32;
+
+/// The tribunal's decision on an election petition
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum Ruling {
+	/// The petition is dismissed and the result stands
+	Upheld,
+	/// The ballots are to be counted again. The count decides the winners over the
+	/// whole election, so every constituency is recounted, not only the petitioned one.
+	Recount,
+	/// The result of the petitioned constituency is void
+	Voided,
+	/// The poll of the petitioned constituency is to be held again
+	Repoll,
+}
+
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum PetitionStatus {
+	Pending,
+	Decided(Ruling),
+}
+
+/// A losing candidate's challenge to the certified result of an election in one
+/// constituency
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Petition<AccountId, Balance, Hash> {
+	pub election_id: ElectionId,
+	/// Constituency whose result is challenged, and which a ruling applies to
+	pub constituency: ConstituencyId,
+	pub candidate_id: CandidateId,
+	pub petitioner: AccountId,
+	/// Bond reserved from the petitioner, forfeited if the result is upheld
+	pub bond: Balance,
+	/// Hash of the evidence lodged with the tribunal off-chain
+	pub evidence: Hash,
+	pub status: PetitionStatus,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	/// Configure the pallet by specifying the parameters and types on which it depends.
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// Allowed origins for the election tribunal
+		type TribunalOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		type Currency: ReservableCurrency<Self::AccountId>;
+		/// Bond reserved from a petitioner until the petition is decided
+		#[pallet::constant]
+		type PetitionBond: Get<BalanceOf<Self>>;
+		/// Blocks after the results are certified during which petitions can be filed
+		#[pallet::constant]
+		type PetitionPeriod: Get<Self::BlockNumber>;
+		type CandidateAccounts: CandidateAccounts<Self::AccountId>;
+		type ElectionInfo: ElectionInfo;
+		type ElectionResults: ElectionResults + CertificationInfo<Self::BlockNumber>;
+		type ElectionControl: ElectionControl;
+	}
+
+	// The pallet's runtime storage items.
+	// https://docs.substrate.io/main-docs/build/runtime-storage/
+	#[pallet::storage]
+	#[pallet::getter(fn petitions)]
+	// Election petitions, pending and decided
+	pub type Petitions<T: Config> = StorageMap<_, Blake2_128Concat, PetitionId, Petition<AccountIdOf<T>, BalanceOf<T>, T::Hash>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn petition_id_counter)]
+	/// PetitionId counter
+	pub type PetitionIdCounter<T: Config> = StorageValue<_, PetitionId>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn pending_petitions)]
+	// Number of petitions still pending against each election
+	pub type PendingPetitions<T: Config> = StorageMap<_, Blake2_128Concat, ElectionId, u32, ValueQuery>;
+
+	// Pallets use events to inform users when important changes are made.
+	// https://docs.substrate.io/main-docs/build/events-errors/
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Candidate filed a petition against an election result
+		/// parameters. [petition_id, election_id, constituency, candidate_id, petitioner]
+		PetitionFiled{petition_id: PetitionId, election_id: ElectionId, constituency: ConstituencyId, candidate_id: CandidateId, petitioner: AccountIdOf<T>},
+		/// Tribunal decided a petition
+		/// parameters. [petition_id, election_id, constituency, ruling]
+		PetitionDecided{petition_id: PetitionId, election_id: ElectionId, constituency: ConstituencyId, ruling: Ruling},
+	}
+
+	// Errors inform users that something went wrong.
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Error: Account Is Not A Candidate In This Election.
+		NotACandidate,
+		/// Error: Candidate Did Not Stand In This Constituency.
+		NotACandidateInConstituency,
+		/// Error: Election Results Not Certified.
+		ResultsNotCertified,
+		/// Error: Elected Candidates Cannot Petition.
+		CandidateWasElected,
+		/// Error: Petition Not Found.
+		PetitionNotFound,
+		/// Error: Petition Already Decided.
+		PetitionAlreadyDecided,
+		/// Error: Period For Filing Petitions Is Over.
+		PetitionPeriodOver,
+		/// Error: Result Is Voided Or Under Recount.
+		ResultNotPetitionable,
+	}
+
+	// Dispatchable functions allows users to interact with the pallet and invoke state changes.
+	// These functions materialize as "extrinsics", which are often compared to transactions.
+	// Dispatchable functions must be annotated with a weight and must return a DispatchResult.
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// File a petition against the certified result of an election in a constituency,
+		/// by a candidate who stood there and was not elected, within `PetitionPeriod` of
+		/// the certification. The bond is reserved until the tribunal decides.
+		#[pallet::call_index(0)]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(11, 5).ref_time())]
+		pub fn file_petition(origin: OriginFor<T>, election_id: ElectionId, constituency: ConstituencyId, evidence: T::Hash) -> DispatchResult {
+			let petitioner = ensure_signed(origin)?;
+			let candidate_id = T::CandidateAccounts::candidate_of(&election_id, &petitioner).ok_or(Error::<T>::NotACandidate)?;
+			ensure!(T::ElectionResults::is_certified(&election_id), Error::<T>::ResultsNotCertified);
+			let certified_at = T::ElectionResults::certified_at(&election_id).ok_or(Error::<T>::ResultsNotCertified)?;
+			ensure!(
+				<frame_system::Pallet<T>>::block_number() <= certified_at.saturating_add(T::PetitionPeriod::get()),
+				Error::<T>::PetitionPeriodOver
+			);
+			// A voided result or one under recount is not reinstated by a later petition
+			ensure!(
+				matches!(
+					T::ElectionInfo::result_standing(&election_id),
+					None | Some(ResultStanding::Petitioned) | Some(ResultStanding::Upheld)
+				) && matches!(
+					T::ElectionInfo::constituency_standing(&election_id, &constituency),
+					None | Some(ResultStanding::Petitioned) | Some(ResultStanding::Upheld)
+				),
+				Error::<T>::ResultNotPetitionable
+			);
+			ensure!(
+				T::ElectionResults::stood_in(&election_id, &constituency, &candidate_id),
+				Error::<T>::NotACandidateInConstituency
+			);
+			ensure!(
+				!T::ElectionResults::winners(&election_id).contains(&candidate_id),
+				Error::<T>::CandidateWasElected
+			);
+			let bond = T::PetitionBond::get();
+			T::Currency::reserve(&petitioner, bond)?;
+			let petition_id = Self::petition_id_inc()?;
+			<Petitions<T>>::insert(petition_id, Petition{
+				election_id,
+				constituency,
+				candidate_id,
+				petitioner: petitioner.clone(),
+				bond,
+				evidence,
+				status: PetitionStatus::Pending,
+			});
+			<PendingPetitions<T>>::try_mutate(election_id, |count| -> DispatchResult {
+				*count = count.checked_add(1).ok_or(ArithmeticError::Overflow)?;
+				Ok(())
+			})?;
+			T::ElectionControl::set_result_standing(&election_id, ResultStanding::Petitioned);
+
+			// Emit an event.
+			Self::deposit_event(Event::PetitionFiled{petition_id, election_id, constituency, candidate_id, petitioner});
+			// Return a successful DispatchResultWithPostInfo
+			Ok(())
+		}
+		/// Decide a pending petition. Upholding the result forfeits the petitioner's bond;
+		/// any other ruling returns it and is passed on to the election: a recount changes
+		/// the standing of the whole result, a void result voids the petitioned
+		/// constituency, and a re-poll opens the constituency re-poll there. The result stands again
+		/// once every petition against it is dismissed.
+		#[pallet::call_index(1)]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(5, 9).ref_time())]
+		pub fn adjudicate_petition(origin: OriginFor<T>, petition_id: PetitionId, ruling: Ruling) -> DispatchResult {
+			// Check if the sender is an approved origin or not
+			T::TribunalOrigin::ensure_origin(origin)?;
+			let mut petition = <Petitions<T>>::get(petition_id).ok_or(Error::<T>::PetitionNotFound)?;
+			ensure!(petition.status == PetitionStatus::Pending, Error::<T>::PetitionAlreadyDecided);
+			let election_id = petition.election_id;
+			let constituency = petition.constituency;
+			if ruling == Ruling::Repoll {
+				// Fails before anything changes when the re-poll cannot be opened
+				T::ElectionControl::repoll_constituency(&election_id, &constituency)?;
+			}
+			let pending = <PendingPetitions<T>>::mutate(election_id, |count| {
+				*count = count.saturating_sub(1);
+				*count
+			});
+			match ruling {
+				Ruling::Upheld => {
+					// The slashed bond is burned
+					let _ = T::Currency::slash_reserved(&petition.petitioner, petition.bond);
+					// A recount or void ordered on another petition still applies
+					let petitioned = T::ElectionInfo::result_standing(&election_id) == Some(ResultStanding::Petitioned);
+					if pending == 0 && petitioned {
+						T::ElectionControl::set_result_standing(&election_id, ResultStanding::Upheld);
+					}
+				},
+				Ruling::Recount => {
+					T::Currency::unreserve(&petition.petitioner, petition.bond);
+					T::ElectionControl::set_result_standing(&election_id, ResultStanding::RecountOrdered);
+				},
+				Ruling::Voided => {
+					T::Currency::unreserve(&petition.petitioner, petition.bond);
+					T::ElectionControl::set_constituency_standing(&election_id, &constituency, ResultStanding::Voided);
+				},
+				Ruling::Repoll => {
+					T::Currency::unreserve(&petition.petitioner, petition.bond);
+				},
+			}
+			petition.status = PetitionStatus::Decided(ruling);
+			<Petitions<T>>::insert(petition_id, petition);
+
+			// Emit an event.
+			Self::deposit_event(Event::PetitionDecided{petition_id, election_id, constituency, ruling});
+			// Return a successful DispatchResultWithPostInfo
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	pub fn petition_id_inc() -> Result<PetitionId, DispatchError> {
+		if PetitionIdCounter::<T>::get().is_some() {
+			let petition_id = PetitionIdCounter::<T>::get()
+				.unwrap()
+				.checked_add(1)
+				.ok_or(ArithmeticError::Overflow)?;
+			PetitionIdCounter::<T>::set(Option::from(petition_id));
+			Ok(petition_id)
+		} else {
+			PetitionIdCounter::<T>::set(Some(1));
+			Ok(1)
+		}
+	}
+}
@@ -0,0 +1,164 @@
+use crate as pallet_dispute;
+use frame_support::{
+	parameter_types,
+	traits::{AsEnsureOriginWithArg, ConstU16, ConstU32, ConstU64},
+};
+use frame_system::EnsureRoot;
+use pallet_candidate::{CandidateAccounts, CertificationInfo, ElectionResults};
+use pallet_election::{ElectionInfo, ResultStanding};
+use primitives::{CandidateId, ConstituencyId, ElectionId, VoteCount};
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+	Perbill,
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system,
+		Balances: pallet_balances,
+		Election: pallet_election,
+		Dispute: pallet_dispute,
+	}
+);
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ConstU16<42>;
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type RuntimeEvent = RuntimeEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+impl pallet_election::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type ElectionCommissionApproveOrigin = AsEnsureOriginWithArg<EnsureRoot<u64>>;
+	type CreateElectionOrigin = EnsureRoot<u64>;
+	type VotingWindowOrigin = EnsureRoot<u64>;
+}
+
+pub const BOND: u64 = 100;
+pub const PERIOD: u64 = 10;
+
+impl pallet_dispute::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type TribunalOrigin = EnsureRoot<u64>;
+	type Currency = Balances;
+	type PetitionBond = ConstU64<BOND>;
+	type PetitionPeriod = ConstU64<PERIOD>;
+	type CandidateAccounts = MockResults;
+	type ElectionInfo = Election;
+	type ElectionResults = MockResults;
+	type ElectionControl = Election;
+}
+
+parameter_types! {
+	/// Accounts standing as candidates 1 to 3 of election 1
+	pub static Candidates: Vec<(u64, CandidateId)> = vec![(1, 1), (2, 2), (3, 3)];
+	pub static Winners: Vec<CandidateId> = vec![1];
+	/// Constituencies counted in the results of election 1
+	pub static Constituencies: Vec<ConstituencyId> = vec![4, 5];
+	/// Block the results of election 1 were certified in
+	pub static CertifiedAt: Option<u64> = None;
+}
+
+/// Candidates and certified results of election 1, as the candidate and ballot
+/// pallets would report them
+pub struct MockResults;
+
+impl CandidateAccounts<u64> for MockResults {
+	fn candidate_of(election_id: &ElectionId, account: &u64) -> Option<CandidateId> {
+		(*election_id == 1)
+			.then(|| Candidates::get().into_iter().find(|(a, _)| a == account).map(|(_, c)| c))
+			.flatten()
+	}
+}
+
+impl ElectionResults for MockResults {
+	fn is_certified(election_id: &ElectionId) -> bool {
+		*election_id == 1 && CertifiedAt::get().is_some() && !matches!(
+			Election::result_standing(election_id),
+			Some(ResultStanding::RecountOrdered) | Some(ResultStanding::Voided)
+		)
+	}
+	fn candidate_votes(_: &ElectionId, _: &CandidateId) -> Option<VoteCount> {
+		None
+	}
+	fn winners(_: &ElectionId) -> Vec<CandidateId> {
+		Winners::get()
+	}
+	fn vote_totals(_: &ElectionId) -> Option<Vec<(CandidateId, VoteCount)>> {
+		None
+	}
+	fn certifications(_: &ElectionId) -> u32 {
+		CertifiedAt::get().is_some() as u32
+	}
+	fn vote_share(_: &ElectionId, _: &CandidateId) -> Option<Perbill> {
+		None
+	}
+	fn stood_in(election_id: &ElectionId, constituency: &ConstituencyId, candidate_id: &CandidateId) -> bool {
+		*election_id == 1 &&
+			Constituencies::get().contains(constituency) &&
+			Candidates::get().iter().any(|(_, c)| c == candidate_id)
+	}
+}
+
+impl CertificationInfo<u64> for MockResults {
+	fn certified_at(election_id: &ElectionId) -> Option<u64> {
+		(*election_id == 1).then(CertifiedAt::get).flatten()
+	}
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> { balances: vec![(1, 1_000), (2, 1_000), (3, 1_000)] }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	let mut ext: sp_io::TestExternalities = t.into();
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		CertifiedAt::set(None);
+	});
+	ext
+}
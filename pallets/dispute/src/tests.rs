@@ -0,0 +1,205 @@
+use crate::{mock::*, Error, Event, PetitionStatus, Ruling};
+use frame_support::{assert_noop, assert_ok, traits::ReservableCurrency};
+use pallet_election::{ElectionInfo, ResultStanding};
+use primitives::{CountingMethod, ElectionConfig};
+use sp_core::H256;
+use sp_runtime::DispatchError;
+
+fn evidence() -> H256 {
+	H256::repeat_byte(7)
+}
+
+/// Election 1 with voting closed and its results certified in the current block
+fn certified_election() {
+	let config = ElectionConfig { counting_method: CountingMethod::Plurality, seats: 1, ..Default::default() };
+	assert_ok!(Election::register_election_for_voting(RuntimeOrigin::root(), config));
+	assert_ok!(Election::election_open_for_voting(RuntimeOrigin::root(), 1));
+	assert_ok!(Election::election_close_for_voting(RuntimeOrigin::root(), 1));
+	CertifiedAt::set(Some(System::block_number()));
+}
+
+#[test]
+fn file_petition_reserves_the_bond() {
+	new_test_ext().execute_with(|| {
+		certified_election();
+		assert_ok!(Dispute::file_petition(RuntimeOrigin::signed(2), 1, 4, evidence()));
+		System::assert_last_event(Event::PetitionFiled { petition_id: 1, election_id: 1, constituency: 4, candidate_id: 2, petitioner: 2 }.into());
+		assert_eq!(Balances::reserved_balance(2), BOND);
+		let petition = Dispute::petitions(1).unwrap();
+		assert_eq!(petition.constituency, 4);
+		assert_eq!(petition.status, PetitionStatus::Pending);
+		assert_eq!(Dispute::pending_petitions(1), 1);
+		assert_eq!(Election::result_standing(1), Some(ResultStanding::Petitioned));
+	});
+}
+
+#[test]
+fn file_petition_checks_the_petitioner_and_the_result() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Election::register_election_for_voting(
+			RuntimeOrigin::root(),
+			ElectionConfig { seats: 1, ..Default::default() }
+		));
+		assert_noop!(
+			Dispute::file_petition(RuntimeOrigin::signed(2), 1, 4, evidence()),
+			Error::<Test>::ResultsNotCertified
+		);
+		CertifiedAt::set(Some(1));
+		assert_noop!(
+			Dispute::file_petition(RuntimeOrigin::signed(9), 1, 4, evidence()),
+			Error::<Test>::NotACandidate
+		);
+		assert_noop!(
+			Dispute::file_petition(RuntimeOrigin::signed(2), 1, 6, evidence()),
+			Error::<Test>::NotACandidateInConstituency
+		);
+		assert_noop!(
+			Dispute::file_petition(RuntimeOrigin::signed(1), 1, 4, evidence()),
+			Error::<Test>::CandidateWasElected
+		);
+	});
+}
+
+#[test]
+fn file_petition_within_the_period_only() {
+	new_test_ext().execute_with(|| {
+		certified_election();
+		System::set_block_number(1 + PERIOD);
+		assert_ok!(Dispute::file_petition(RuntimeOrigin::signed(2), 1, 4, evidence()));
+		System::set_block_number(2 + PERIOD);
+		assert_noop!(
+			Dispute::file_petition(RuntimeOrigin::signed(3), 1, 4, evidence()),
+			Error::<Test>::PetitionPeriodOver
+		);
+		// Certifying the results again, after a recount, opens a new period
+		CertifiedAt::set(Some(2 + PERIOD));
+		assert_ok!(Dispute::file_petition(RuntimeOrigin::signed(3), 1, 4, evidence()));
+	});
+}
+
+#[test]
+fn adjudicate_petition_by_the_tribunal_once() {
+	new_test_ext().execute_with(|| {
+		certified_election();
+		assert_ok!(Dispute::file_petition(RuntimeOrigin::signed(2), 1, 4, evidence()));
+		assert_noop!(
+			Dispute::adjudicate_petition(RuntimeOrigin::signed(1), 1, Ruling::Upheld),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Dispute::adjudicate_petition(RuntimeOrigin::root(), 2, Ruling::Upheld),
+			Error::<Test>::PetitionNotFound
+		);
+		assert_ok!(Dispute::adjudicate_petition(RuntimeOrigin::root(), 1, Ruling::Recount));
+		assert_noop!(
+			Dispute::adjudicate_petition(RuntimeOrigin::root(), 1, Ruling::Upheld),
+			Error::<Test>::PetitionAlreadyDecided
+		);
+	});
+}
+
+#[test]
+fn upheld_result_forfeits_the_bond() {
+	new_test_ext().execute_with(|| {
+		certified_election();
+		let issuance = Balances::total_issuance();
+		assert_ok!(Dispute::file_petition(RuntimeOrigin::signed(2), 1, 4, evidence()));
+		assert_ok!(Dispute::file_petition(RuntimeOrigin::signed(3), 1, 5, evidence()));
+
+		assert_ok!(Dispute::adjudicate_petition(RuntimeOrigin::root(), 1, Ruling::Upheld));
+		System::assert_last_event(Event::PetitionDecided { petition_id: 1, election_id: 1, constituency: 4, ruling: Ruling::Upheld }.into());
+		// The bond is burned
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_eq!(Balances::free_balance(2), 1_000 - BOND);
+		assert_eq!(Balances::total_issuance(), issuance - BOND);
+		assert_eq!(Dispute::petitions(1).unwrap().status, PetitionStatus::Decided(Ruling::Upheld));
+		// Another petition is still pending
+		assert_eq!(Election::result_standing(1), Some(ResultStanding::Petitioned));
+
+		assert_ok!(Dispute::adjudicate_petition(RuntimeOrigin::root(), 2, Ruling::Upheld));
+		assert_eq!(Dispute::pending_petitions(1), 0);
+		assert_eq!(Election::result_standing(1), Some(ResultStanding::Upheld));
+		// An upheld result can still be petitioned within the period
+		assert_ok!(Dispute::file_petition(RuntimeOrigin::signed(2), 1, 4, evidence()));
+	});
+}
+
+#[test]
+fn recount_returns_the_bond_and_closes_petitions() {
+	new_test_ext().execute_with(|| {
+		certified_election();
+		assert_ok!(Dispute::file_petition(RuntimeOrigin::signed(2), 1, 4, evidence()));
+		assert_ok!(Dispute::adjudicate_petition(RuntimeOrigin::root(), 1, Ruling::Recount));
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_eq!(Balances::free_balance(2), 1_000);
+		assert_eq!(Election::result_standing(1), Some(ResultStanding::RecountOrdered));
+		// The result under recount no longer stands, so cannot be petitioned
+		assert_noop!(
+			Dispute::file_petition(RuntimeOrigin::signed(3), 1, 5, evidence()),
+			Error::<Test>::ResultsNotCertified
+		);
+	});
+}
+
+#[test]
+fn voided_constituency_is_not_reinstated_by_a_later_petition() {
+	new_test_ext().execute_with(|| {
+		certified_election();
+		assert_ok!(Dispute::file_petition(RuntimeOrigin::signed(2), 1, 4, evidence()));
+		assert_ok!(Dispute::file_petition(RuntimeOrigin::signed(3), 1, 5, evidence()));
+		assert_ok!(Dispute::adjudicate_petition(RuntimeOrigin::root(), 1, Ruling::Voided));
+		assert_eq!(Balances::reserved_balance(2), 0);
+		// Only the petitioned constituency is void
+		assert_eq!(Election::constituency_standing(1, 4), Some(ResultStanding::Voided));
+		assert_eq!(Election::constituency_standing(1, 5), None);
+		assert_noop!(
+			Dispute::file_petition(RuntimeOrigin::signed(2), 1, 4, evidence()),
+			Error::<Test>::ResultNotPetitionable
+		);
+		// Dismissing the other petition upholds the election but not the void constituency
+		assert_ok!(Dispute::adjudicate_petition(RuntimeOrigin::root(), 2, Ruling::Upheld));
+		assert_eq!(Election::result_standing(1), Some(ResultStanding::Upheld));
+		assert_eq!(Election::constituency_standing(1, 4), Some(ResultStanding::Voided));
+	});
+}
+
+#[test]
+fn repoll_opens_the_constituency_repoll() {
+	new_test_ext().execute_with(|| {
+		certified_election();
+		assert_ok!(Dispute::file_petition(RuntimeOrigin::signed(2), 1, 4, evidence()));
+		assert_ok!(Dispute::adjudicate_petition(RuntimeOrigin::root(), 1, Ruling::Repoll));
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert!(Election::is_open_for_repoll(&1, &4));
+		assert!(!Election::is_open_for_repoll(&1, &5));
+		assert_eq!(Election::constituency_generation(&1, &4), 1);
+		// The rest of the election is not polled again
+		assert_eq!(Election::repoll_generation(&1), 0);
+		assert!(Election::is_election_closed_for_voting(&1));
+	});
+}
+
+#[test]
+fn repoll_that_cannot_open_changes_nothing() {
+	new_test_ext().execute_with(|| {
+		certified_election();
+		assert_ok!(Dispute::file_petition(RuntimeOrigin::signed(2), 1, 4, evidence()));
+		assert_ok!(Election::open_constituency_repoll(RuntimeOrigin::root(), 1, 4));
+		assert_noop!(
+			Dispute::adjudicate_petition(RuntimeOrigin::root(), 1, Ruling::Repoll),
+			pallet_election::Error::<Test>::RepollAlreadyOpen
+		);
+		assert_eq!(Balances::reserved_balance(2), BOND);
+		assert_eq!(Dispute::pending_petitions(1), 1);
+	});
+}
+
+#[test]
+fn bond_must_be_affordable() {
+	new_test_ext().execute_with(|| {
+		certified_election();
+		assert_ok!(Balances::reserve(&3, 1_000 - BOND / 2));
+		assert!(Dispute::file_petition(RuntimeOrigin::signed(3), 1, 4, evidence()).is_err());
+		assert!(Dispute::petitions(1).is_none());
+	});
+}
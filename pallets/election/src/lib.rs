@@ -6,8 +6,27 @@
 pub use pallet::*;
 use frame_support::pallet_prelude::*;
 use frame_system::pallet_prelude::*;
+use frame_support::traits::EnsureOriginWithArg;
 use sp_runtime::ArithmeticError;
-use primitives::ElectionId;
+use primitives::{
+	referendum::Questions,
+	ConstituencyId, ElectionId, ElectionConfig, RoleScope,
+};
+
+/// Where the result of an election stands once it has been challenged
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum ResultStanding {
+	/// A petition against the result is pending
+	Petitioned,
+	/// Every petition was dismissed and the result stands
+	Upheld,
+	/// The ballots must be counted again before the result stands
+	RecountOrdered,
+	/// The ballots were counted again and the recounted result is certified
+	Recounted,
+	/// The result is void and no candidate is returned
+	Voided,
+}
 
 #[cfg(test)]
 mod mock;
@@ -32,7 +51,11 @@ pub mod pallet {
 		/// Because this pallet emits events, it depends on the runtime's definition of an event.
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 		/// Allowed origins for only election commission
-		type ElectionCommissionApproveOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		type ElectionCommissionApproveOrigin: EnsureOriginWithArg<Self::RuntimeOrigin, RoleScope>;
+		/// Allowed origins for creating an election
+		type CreateElectionOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// Allowed origins for opening and closing voter registration and voting
+		type VotingWindowOrigin: EnsureOrigin<Self::RuntimeOrigin>;
 	}
 
 	// The pallet's runtime storage items.
@@ -48,9 +71,65 @@ pub mod pallet {
 	// Active elections that are open for registration
 	pub type ElectionOpenForVoting<T> = StorageMap<_, Blake2_128Concat, ElectionId, bool>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn closed_for_election)]
+	// Elections whose voting window has been closed
+	pub type ElectionClosedForVoting<T> = StorageMap<_, Blake2_128Concat, ElectionId, bool>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn election_config)]
+	// Options each election was registered for voting with
+	pub type ElectionConfigs<T> = StorageMap<_, Blake2_128Concat, ElectionId, ElectionConfig, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn election_questions)]
+	// Questions on the ballot of each election, in ballot order
+	pub type ElectionQuestions<T> = StorageMap<_, Blake2_128Concat, ElectionId, Questions, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn countermanded)]
+	// Elections whose poll was countermanded; voting can be opened again for a fresh poll
+	pub type ElectionCountermanded<T> = StorageMap<_, Blake2_128Concat, ElectionId, bool, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn repoll_generation)]
+	// Number of times the poll of each election was started afresh; ballots of an
+	// earlier generation are not counted
+	pub type RepollGenerations<T> = StorageMap<_, Blake2_128Concat, ElectionId, u32, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn constituency_generation)]
+	// Number of times the poll of a constituency was held again on its own
+	pub type ConstituencyGenerations<T> = StorageDoubleMap<_, Blake2_128Concat, ElectionId, Blake2_128Concat, ConstituencyId, u32, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn open_for_repoll)]
+	// Constituencies whose re-poll is open for voting
+	pub type ConstituencyRepollOpen<T> = StorageDoubleMap<_, Blake2_128Concat, ElectionId, Blake2_128Concat, ConstituencyId, bool, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn open_repolls)]
+	// Number of constituency re-polls open in each election
+	pub type OpenRepolls<T> = StorageMap<_, Blake2_128Concat, ElectionId, u32, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn constituency_repolls)]
+	// Number of constituency re-polls ever opened in each election
+	pub type ConstituencyRepolls<T> = StorageMap<_, Blake2_128Concat, ElectionId, u32, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn result_standing)]
+	// Standing of each election result that was challenged
+	pub type ResultStandings<T> = StorageMap<_, Blake2_128Concat, ElectionId, ResultStanding>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn constituency_standing)]
+	// Standing of the result of a constituency an election tribunal ruled on
+	pub type ConstituencyStandings<T> = StorageDoubleMap<_, Blake2_128Concat, ElectionId, Blake2_128Concat, ConstituencyId, ResultStanding>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn election_complete)]
-	// Active elections that are open for registration
+	// Elections whose results the commissioners have certified
 	pub type ElectionComplete<T> = StorageMap<_, Blake2_128Concat, ElectionId, bool>;
 
 	#[pallet::storage]
@@ -86,7 +165,32 @@ pub mod pallet {
 		ElectionIdOpenForVoting{election_id: ElectionId},
 		/// Election id is Close For Voting
 		/// parameters. []
-		ElectionIdCloseForVoting
+		ElectionIdCloseForVoting,
+		/// Questions are set on the ballot of an election
+		/// parameters. [election_id, questions]
+		ElectionQuestionsSet{election_id: ElectionId, questions: u32},
+		/// Poll is countermanded and voting stopped
+		/// parameters. [election_id, generation]
+		ElectionCountermanded{election_id: ElectionId, generation: u32},
+		/// Poll is started afresh while voting stays open
+		/// parameters. [election_id, generation]
+		RepollOrdered{election_id: ElectionId, generation: u32},
+		/// Standing of a challenged result changed
+		/// parameters. [election_id, standing]
+		ResultStandingChanged{election_id: ElectionId, standing: ResultStanding},
+		/// Standing of the result of one constituency changed
+		/// parameters. [election_id, constituency, standing]
+		ConstituencyStandingChanged{election_id: ElectionId, constituency: ConstituencyId, standing: ResultStanding},
+		/// Election was completed by the certification of its results, or reopened when
+		/// they were set aside
+		/// parameters. [election_id, complete]
+		ElectionCompleteChanged{election_id: ElectionId, complete: bool},
+		/// Poll of a constituency is held again for its voters only
+		/// parameters. [election_id, constituency, generation]
+		ConstituencyRepollOpened{election_id: ElectionId, constituency: ConstituencyId, generation: u32},
+		/// Re-poll of a constituency is closed
+		/// parameters. [election_id, constituency]
+		ConstituencyRepollClosed{election_id: ElectionId, constituency: ConstituencyId},
 	}
 
 	// Errors inform users that something went wrong.
@@ -102,6 +206,18 @@ pub mod pallet {
 		ElectionIdAlreadyOpenForVoting,
 		/// Error: Election Id Already not Open For Voting.
 		ElectionIdNotOpenForVoting,
+		/// Error: Election Id Already Closed For Voting.
+		ElectionIdAlreadyClosedForVoting,
+		/// Error: Counting Method Cannot Run With This Election Config.
+		InvalidElectionConfig,
+		/// Error: Question Has Fewer Than Two Options.
+		InvalidQuestion,
+		/// Error: Election Id Not Closed For Voting.
+		ElectionIdNotClosedForVoting,
+		/// Error: Constituency Re-Poll Already Open.
+		RepollAlreadyOpen,
+		/// Error: Constituency Re-Poll Not Open.
+		RepollNotOpen,
 	}
 
 	// Dispatchable functions allows users to interact with the pallet and invoke state changes.
@@ -114,7 +230,7 @@ pub mod pallet {
 		#[pallet::weight(10_000 + T::DbWeight::get().writes(1).ref_time())]
 		pub fn register_new_election_id(origin: OriginFor<T>) -> DispatchResult {
 			// Check if the sender is an approved origin or not
-			T::ElectionCommissionApproveOrigin::ensure_origin(origin)?;
+			T::CreateElectionOrigin::ensure_origin(origin)?;
 			let election_id = Self::election_id_inc()?;
 			// Update storage for election id
 			<ElectionOpenForVoterRegistration<T>>::insert(election_id, false);
@@ -129,7 +245,7 @@ pub mod pallet {
 		#[pallet::weight(10_000 + T::DbWeight::get().writes(1).ref_time())]
 		pub fn de_register_election_id(origin: OriginFor<T>, election_id: ElectionId) -> DispatchResult {
 			// Check if the sender is an approved origin or not
-			T::ElectionCommissionApproveOrigin::ensure_origin(origin)?;
+			T::ElectionCommissionApproveOrigin::ensure_origin(origin, &RoleScope::Election(election_id))?;
 			// Update storage for election id
 			<ElectionOpenForVoterRegistration<T>>::remove(election_id);
 
@@ -143,13 +259,13 @@ pub mod pallet {
 		#[pallet::weight(10_000 + T::DbWeight::get().writes(1).ref_time())]
 		pub fn election_open_for_voter_registration(origin: OriginFor<T>, election_id: ElectionId) -> DispatchResult {
 			// Check if the sender is an approved origin or not
-			T::ElectionCommissionApproveOrigin::ensure_origin(origin)?;
+			T::VotingWindowOrigin::ensure_origin(origin)?;
 			ensure!(
-				!<ElectionOpenForVoterRegistration<T>>::contains_key(&election_id),
+				<ElectionOpenForVoterRegistration<T>>::contains_key(&election_id),
 				Error::<T>::InvalidElectionId
 			);
 			ensure!(
-				<ElectionOpenForVoterRegistration<T>>::get(&election_id).unwrap_or(false),
+				!<ElectionOpenForVoterRegistration<T>>::get(&election_id).unwrap_or(false),
 				Error::<T>::ElectionIdAlreadyOpenForVoterRegistration
 			);
 			// Update storage for election id
@@ -165,13 +281,13 @@ pub mod pallet {
 		#[pallet::weight(10_000 + T::DbWeight::get().writes(1).ref_time())]
 		pub fn election_close_for_voter_registration(origin: OriginFor<T>, election_id: ElectionId) -> DispatchResult {
 			// Check if the sender is an approved origin or not
-			T::ElectionCommissionApproveOrigin::ensure_origin(origin)?;
+			T::VotingWindowOrigin::ensure_origin(origin)?;
 			ensure!(
-				!<ElectionOpenForVoterRegistration<T>>::contains_key(&election_id),
+				<ElectionOpenForVoterRegistration<T>>::contains_key(&election_id),
 				Error::<T>::InvalidElectionId
 			);
 			ensure!(
-				!<ElectionOpenForVoterRegistration<T>>::get(&election_id).unwrap_or(false),
+				<ElectionOpenForVoterRegistration<T>>::get(&election_id).unwrap_or(false),
 				Error::<T>::ElectionIdNotOpenForVoterRegistration
 			);
 			// Update storage for election id
@@ -185,13 +301,15 @@ pub mod pallet {
 		/// Self register by voter
 		#[pallet::call_index(4)]
 		#[pallet::weight(10_000 + T::DbWeight::get().writes(1).ref_time())]
-		pub fn register_election_for_voting(origin: OriginFor<T>) -> DispatchResult {
+		pub fn register_election_for_voting(origin: OriginFor<T>, config: ElectionConfig) -> DispatchResult {
 			// Check if the sender is an approved origin or not
-			T::ElectionCommissionApproveOrigin::ensure_origin(origin)?;
+			T::CreateElectionOrigin::ensure_origin(origin)?;
+			ensure!(config.is_valid(), Error::<T>::InvalidElectionConfig);
 			Self::election_id_inc()?;
 			let election_id = ElectionIdCounter::<T>::get().expect("Pool Id not found");
 			// Update storage for election id
 			<ElectionOpenForVoting<T>>::insert(election_id, false);
+			<ElectionConfigs<T>>::insert(election_id, config);
 
 			// Emit an event.
 			Self::deposit_event(Event::ElectionIdRegisteredForVoting{election_id});
@@ -203,9 +321,11 @@ pub mod pallet {
 		#[pallet::weight(10_000 + T::DbWeight::get().writes(1).ref_time())]
 		pub fn de_register_election_for_voting(origin: OriginFor<T>, election_id: ElectionId) -> DispatchResult {
 			// Check if the sender is an approved origin or not
-			T::ElectionCommissionApproveOrigin::ensure_origin(origin)?;
+			T::ElectionCommissionApproveOrigin::ensure_origin(origin, &RoleScope::Election(election_id))?;
 			// Update storage for election id
 			<ElectionOpenForVoting<T>>::remove(election_id);
+			<ElectionConfigs<T>>::remove(election_id);
+			<ElectionQuestions<T>>::remove(election_id);
 
 			// Emit an event.
 			Self::deposit_event(Event::ElectionIdDeRegisteredForVoting);
@@ -217,17 +337,22 @@ pub mod pallet {
 		#[pallet::weight(10_000 + T::DbWeight::get().writes(1).ref_time())]
 		pub fn election_open_for_voting(origin: OriginFor<T>, election_id: ElectionId) -> DispatchResult {
 			// Check if the sender is an approved origin or not
-			T::ElectionCommissionApproveOrigin::ensure_origin(origin)?;
+			T::VotingWindowOrigin::ensure_origin(origin)?;
 			ensure!(
-				!<ElectionOpenForVoting<T>>::contains_key(&election_id),
+				<ElectionOpenForVoting<T>>::contains_key(&election_id),
 				Error::<T>::InvalidElectionId
 			);
 			ensure!(
-				<ElectionOpenForVoting<T>>::get(&election_id).unwrap_or(false),
+				!<ElectionOpenForVoting<T>>::get(&election_id).unwrap_or(false),
 				Error::<T>::ElectionIdAlreadyOpenForVoting
 			);
+			ensure!(
+				!<ElectionClosedForVoting<T>>::get(&election_id).unwrap_or(false),
+				Error::<T>::ElectionIdAlreadyClosedForVoting
+			);
 			// Update storage for election id
 			<ElectionOpenForVoting<T>>::insert(election_id, true);
+			<ElectionCountermanded<T>>::remove(election_id);
 
 			// Emit an event.
 			Self::deposit_event(Event::ElectionIdOpenForVoting{election_id});
@@ -239,23 +364,83 @@ pub mod pallet {
 		#[pallet::weight(10_000 + T::DbWeight::get().writes(1).ref_time())]
 		pub fn election_close_for_voting(origin: OriginFor<T>, election_id: ElectionId) -> DispatchResult {
 			// Check if the sender is an approved origin or not
-			T::ElectionCommissionApproveOrigin::ensure_origin(origin)?;
+			T::VotingWindowOrigin::ensure_origin(origin)?;
 			ensure!(
-				!<ElectionOpenForVoting<T>>::contains_key(&election_id),
+				<ElectionOpenForVoting<T>>::contains_key(&election_id),
 				Error::<T>::InvalidElectionId
 			);
 			ensure!(
-				!<ElectionOpenForVoting<T>>::get(&election_id).unwrap_or(false),
+				<ElectionOpenForVoting<T>>::get(&election_id).unwrap_or(false),
 				Error::<T>::ElectionIdNotOpenForVoting
 			);
 			// Update storage for election id
 			<ElectionOpenForVoting<T>>::insert(election_id, false);
+			<ElectionClosedForVoting<T>>::insert(election_id, true);
 
 			// Emit an event.
 			Self::deposit_event(Event::ElectionIdCloseForVoting);
 			// Return a successful DispatchResultWithPostInfo
 			Ok(())
 		}
+		/// Set the questions on the ballot of an election registered for voting, before
+		/// voting opens. Replaces any questions set earlier.
+		#[pallet::call_index(8)]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 1).ref_time())]
+		pub fn set_election_questions(origin: OriginFor<T>, election_id: ElectionId, questions: Questions) -> DispatchResult {
+			// Check if the sender is an approved origin or not
+			T::ElectionCommissionApproveOrigin::ensure_origin(origin, &RoleScope::Election(election_id))?;
+			ensure!(
+				<ElectionOpenForVoting<T>>::contains_key(&election_id),
+				Error::<T>::InvalidElectionId
+			);
+			ensure!(
+				!<ElectionOpenForVoting<T>>::get(&election_id).unwrap_or(false),
+				Error::<T>::ElectionIdAlreadyOpenForVoting
+			);
+			ensure!(
+				!<ElectionClosedForVoting<T>>::get(&election_id).unwrap_or(false),
+				Error::<T>::ElectionIdAlreadyClosedForVoting
+			);
+			ensure!(
+				questions.iter().all(|q| q.options.len() >= 2),
+				Error::<T>::InvalidQuestion
+			);
+			let count = questions.len() as u32;
+			// Update storage for election id
+			<ElectionQuestions<T>>::insert(election_id, questions);
+
+			// Emit an event.
+			Self::deposit_event(Event::ElectionQuestionsSet{election_id, questions: count});
+			// Return a successful DispatchResultWithPostInfo
+			Ok(())
+		}
+		/// Hold the poll of one constituency again once voting has closed. Ballots cast
+		/// there so far no longer count, and its voters, and only they, may vote afresh
+		/// until the re-poll is closed. The election is tallied again afterwards.
+		#[pallet::call_index(9)]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 6).ref_time())]
+		pub fn open_constituency_repoll(origin: OriginFor<T>, election_id: ElectionId, constituency: ConstituencyId) -> DispatchResult {
+			// Check if the sender is an approved origin or not
+			T::ElectionCommissionApproveOrigin::ensure_origin(origin, &RoleScope::Constituency(election_id, constituency))?;
+			Self::start_constituency_repoll(election_id, constituency)
+		}
+		/// Close the re-poll of a constituency
+		#[pallet::call_index(10)]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 2).ref_time())]
+		pub fn close_constituency_repoll(origin: OriginFor<T>, election_id: ElectionId, constituency: ConstituencyId) -> DispatchResult {
+			// Check if the sender is an approved origin or not
+			T::ElectionCommissionApproveOrigin::ensure_origin(origin, &RoleScope::Constituency(election_id, constituency))?;
+			ensure!(
+				<ConstituencyRepollOpen<T>>::take(election_id, constituency),
+				Error::<T>::RepollNotOpen
+			);
+			<OpenRepolls<T>>::mutate(election_id, |count| *count = count.saturating_sub(1));
+
+			// Emit an event.
+			Self::deposit_event(Event::ConstituencyRepollClosed{election_id, constituency});
+			// Return a successful DispatchResultWithPostInfo
+			Ok(())
+		}
 	}
 }
 
@@ -273,18 +458,159 @@ impl<T: Config> Pallet<T> {
 			Ok(1)
 		}
 	}
+
+	fn start_constituency_repoll(election_id: ElectionId, constituency: ConstituencyId) -> DispatchResult {
+		ensure!(
+			<ElectionClosedForVoting<T>>::get(&election_id).unwrap_or(false),
+			Error::<T>::ElectionIdNotClosedForVoting
+		);
+		ensure!(
+			!<ConstituencyRepollOpen<T>>::get(election_id, constituency),
+			Error::<T>::RepollAlreadyOpen
+		);
+		let generation = <ConstituencyGenerations<T>>::mutate(election_id, constituency, |generation| {
+			*generation = generation.saturating_add(1);
+			*generation
+		});
+		<ConstituencyRepollOpen<T>>::insert(election_id, constituency, true);
+		<OpenRepolls<T>>::mutate(election_id, |count| *count = count.saturating_add(1));
+		<ConstituencyRepolls<T>>::mutate(election_id, |count| *count = count.saturating_add(1));
+		// The result of the whole election is decided afresh
+		<ResultStandings<T>>::remove(election_id);
+		<ConstituencyStandings<T>>::remove(election_id, constituency);
+
+		// Emit an event.
+		Self::deposit_event(Event::ConstituencyRepollOpened{election_id, constituency, generation});
+		// Return a successful DispatchResultWithPostInfo
+		Ok(())
+	}
+
+	fn next_generation(election_id: ElectionId) -> u32 {
+		<RepollGenerations<T>>::mutate(election_id, |generation| {
+			*generation = generation.saturating_add(1);
+			*generation
+		})
+	}
+}
+
+impl<T: Config> ElectionControl for Pallet<T> {
+	fn countermand(election_id: &ElectionId) {
+		let generation = Self::next_generation(*election_id);
+		<ElectionOpenForVoting<T>>::insert(election_id, false);
+		<ElectionCountermanded<T>>::insert(election_id, true);
+		Self::deposit_event(Event::ElectionCountermanded{election_id: *election_id, generation});
+	}
+	fn order_repoll(election_id: &ElectionId) {
+		let generation = Self::next_generation(*election_id);
+		// A closed poll waits for voting to be opened again, like a countermanded one
+		if <ElectionClosedForVoting<T>>::take(election_id).unwrap_or(false) {
+			<ElectionCountermanded<T>>::insert(election_id, true);
+			<ResultStandings<T>>::remove(election_id);
+		}
+		Self::deposit_event(Event::RepollOrdered{election_id: *election_id, generation});
+	}
+	fn set_result_standing(election_id: &ElectionId, standing: ResultStanding) {
+		<ResultStandings<T>>::insert(election_id, standing);
+		Self::deposit_event(Event::ResultStandingChanged{election_id: *election_id, standing});
+	}
+	fn set_constituency_standing(election_id: &ElectionId, constituency: &ConstituencyId, standing: ResultStanding) {
+		<ConstituencyStandings<T>>::insert(election_id, constituency, standing);
+		Self::deposit_event(Event::ConstituencyStandingChanged{election_id: *election_id, constituency: *constituency, standing});
+	}
+	fn repoll_constituency(election_id: &ElectionId, constituency: &ConstituencyId) -> DispatchResult {
+		Self::start_constituency_repoll(*election_id, *constituency)
+	}
+	fn set_election_complete(election_id: &ElectionId, complete: bool) {
+		if complete {
+			<ElectionComplete<T>>::insert(election_id, true);
+		} else {
+			<ElectionComplete<T>>::remove(election_id);
+		}
+		Self::deposit_event(Event::ElectionCompleteChanged{election_id: *election_id, complete});
+	}
 }
 
 impl<T: Config> ElectionInfo for Pallet<T> {
+	fn election_exists(election_id: &ElectionId) -> bool {
+		<ElectionOpenForVoting<T>>::contains_key(election_id) ||
+			<ElectionOpenForVoterRegistration<T>>::contains_key(election_id)
+	}
 	fn is_election_open_for_voter_registration(election_id: &ElectionId) -> bool {
 		<ElectionOpenForVoterRegistration<T>>::get(election_id).unwrap_or(false)
 	}
 	fn is_election_open_for_voting(election_id: &ElectionId) -> bool {
 		<ElectionOpenForVoting<T>>::get(election_id).unwrap_or(false)
 	}
+	fn is_election_closed_for_voting(election_id: &ElectionId) -> bool {
+		<ElectionClosedForVoting<T>>::get(election_id).unwrap_or(false)
+	}
+	fn election_config(election_id: &ElectionId) -> ElectionConfig {
+		<ElectionConfigs<T>>::get(election_id)
+	}
+	fn election_questions(election_id: &ElectionId) -> Questions {
+		<ElectionQuestions<T>>::get(election_id)
+	}
+	fn is_countermanded(election_id: &ElectionId) -> bool {
+		<ElectionCountermanded<T>>::get(election_id)
+	}
+	fn repoll_generation(election_id: &ElectionId) -> u32 {
+		<RepollGenerations<T>>::get(election_id)
+	}
+	fn result_standing(election_id: &ElectionId) -> Option<ResultStanding> {
+		<ResultStandings<T>>::get(election_id)
+	}
+	fn constituency_standing(election_id: &ElectionId, constituency: &ConstituencyId) -> Option<ResultStanding> {
+		<ConstituencyStandings<T>>::get(election_id, constituency)
+	}
+	fn is_election_complete(election_id: &ElectionId) -> bool {
+		<ElectionComplete<T>>::get(election_id).unwrap_or(false)
+	}
+	fn constituency_generation(election_id: &ElectionId, constituency: &ConstituencyId) -> u32 {
+		<ConstituencyGenerations<T>>::get(election_id, constituency)
+	}
+	fn is_open_for_repoll(election_id: &ElectionId, constituency: &ConstituencyId) -> bool {
+		<ConstituencyRepollOpen<T>>::get(election_id, constituency)
+	}
+	fn has_open_repoll(election_id: &ElectionId) -> bool {
+		<OpenRepolls<T>>::get(election_id) > 0
+	}
+	fn constituency_repolls(election_id: &ElectionId) -> u32 {
+		<ConstituencyRepolls<T>>::get(election_id)
+	}
 }
 
 pub trait ElectionInfo {
+	/// Election id registered, for voter registration or for voting, and not deregistered
+	fn election_exists(election_id: &ElectionId) -> bool;
 	fn is_election_open_for_voter_registration(election_id: &ElectionId) -> bool;
 	fn is_election_open_for_voting(election_id: &ElectionId) -> bool;
+	fn is_election_closed_for_voting(election_id: &ElectionId) -> bool;
+	fn election_config(election_id: &ElectionId) -> ElectionConfig;
+	fn election_questions(election_id: &ElectionId) -> Questions;
+	fn is_countermanded(election_id: &ElectionId) -> bool;
+	fn repoll_generation(election_id: &ElectionId) -> u32;
+	fn result_standing(election_id: &ElectionId) -> Option<ResultStanding>;
+	fn constituency_standing(election_id: &ElectionId, constituency: &ConstituencyId) -> Option<ResultStanding>;
+	fn is_election_complete(election_id: &ElectionId) -> bool;
+	fn constituency_generation(election_id: &ElectionId, constituency: &ConstituencyId) -> u32;
+	fn is_open_for_repoll(election_id: &ElectionId, constituency: &ConstituencyId) -> bool;
+	fn has_open_repoll(election_id: &ElectionId) -> bool;
+	/// Number of constituency re-polls ever opened; the tally restarts when it changes
+	fn constituency_repolls(election_id: &ElectionId) -> u32;
+}
+
+pub trait ElectionControl {
+	/// Stop voting without a result; opening voting again starts a fresh poll
+	fn countermand(election_id: &ElectionId);
+	/// Start the poll afresh, voiding the ballots cast so far. A poll already closed
+	/// is reopened for voting.
+	fn order_repoll(election_id: &ElectionId);
+	/// Record the standing of a challenged result
+	fn set_result_standing(election_id: &ElectionId, standing: ResultStanding);
+	/// Record the standing of the result of one constituency
+	fn set_constituency_standing(election_id: &ElectionId, constituency: &ConstituencyId, standing: ResultStanding);
+	/// Hold the poll of one constituency again, as `open_constituency_repoll` does
+	fn repoll_constituency(election_id: &ElectionId, constituency: &ConstituencyId) -> DispatchResult;
+	/// Record that the results are certified, or no longer are
+	fn set_election_complete(election_id: &ElectionId, complete: bool);
 }
@@ -1,5 +1,6 @@
-use crate as pallet_template;
-use frame_support::traits::{ConstU16, ConstU64};
+use crate as pallet_election;
+use frame_support::traits::{AsEnsureOriginWithArg, ConstU16, ConstU32, ConstU64};
+use frame_system::EnsureRoot;
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
@@ -17,7 +18,7 @@ frame_support::construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system,
-		TemplateModule: pallet_template,
+		Election: pallet_election,
 	}
 );
 
@@ -45,11 +46,14 @@ impl frame_system::Config for Test {
 	type SystemWeightInfo = ();
 	type SS58Prefix = ConstU16<42>;
 	type OnSetCode = ();
-	type MaxConsumers = frame_support::traits::ConstU32<16>;
+	type MaxConsumers = ConstU32<16>;
 }
 
-impl pallet_template::Config for Test {
+impl pallet_election::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
+	type ElectionCommissionApproveOrigin = AsEnsureOriginWithArg<EnsureRoot<u64>>;
+	type CreateElectionOrigin = EnsureRoot<u64>;
+	type VotingWindowOrigin = EnsureRoot<u64>;
 }
 
 // Build genesis storage according to the mock runtime.
@@ -1,27 +1,182 @@
-use crate::{mock::*, Error, Event};
+use crate::{mock::*, ElectionControl, ElectionInfo, Error, Event, ResultStanding};
 use frame_support::{assert_noop, assert_ok};
+use primitives::{
+	referendum::{Question, Questions},
+	CountingMethod, ElectionConfig,
+};
+use sp_runtime::{DispatchError, Perbill};
+
+fn plurality() -> ElectionConfig {
+	ElectionConfig { counting_method: CountingMethod::Plurality, seats: 1, ..Default::default() }
+}
+
+fn question(options: &[&str]) -> Question {
+	Question {
+		text: b"Adopt the amendment?".to_vec().try_into().unwrap(),
+		options: options.iter().map(|o| o.as_bytes().to_vec().try_into().unwrap()).collect::<Vec<_>>().try_into().unwrap(),
+		min_turnout: Perbill::zero(),
+		approval: Perbill::from_percent(50),
+	}
+}
+
+/// Register election 1 and take it through voting
+fn closed_election() {
+	assert_ok!(Election::register_election_for_voting(RuntimeOrigin::root(), plurality()));
+	assert_ok!(Election::election_open_for_voting(RuntimeOrigin::root(), 1));
+	assert_ok!(Election::election_close_for_voting(RuntimeOrigin::root(), 1));
+}
 
 #[test]
-fn it_works_for_default_value() {
+fn register_election_for_voting_checks_the_config() {
 	new_test_ext().execute_with(|| {
 		// Go past genesis block so events get deposited
 		System::set_block_number(1);
-		// Dispatch a signed extrinsic.
-		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(1), 42));
-		// Read pallet storage and assert an expected result.
-		assert_eq!(TemplateModule::something(), Some(42));
-		// Assert that the correct event was deposited
-		System::assert_last_event(Event::SomethingStored { something: 42, who: 1 }.into());
+		assert_noop!(
+			Election::register_election_for_voting(RuntimeOrigin::signed(1), plurality()),
+			DispatchError::BadOrigin
+		);
+		let stv_without_seats = ElectionConfig { counting_method: CountingMethod::SingleTransferableVote, seats: 0, ..Default::default() };
+		assert_noop!(
+			Election::register_election_for_voting(RuntimeOrigin::root(), stv_without_seats),
+			Error::<Test>::InvalidElectionConfig
+		);
+		let anonymous_without_revoting = ElectionConfig { anonymous: true, ..plurality() };
+		assert_noop!(
+			Election::register_election_for_voting(RuntimeOrigin::root(), anonymous_without_revoting),
+			Error::<Test>::InvalidElectionConfig
+		);
+		assert_ok!(Election::register_election_for_voting(RuntimeOrigin::root(), plurality()));
+		System::assert_last_event(Event::ElectionIdRegisteredForVoting { election_id: 1 }.into());
+		assert_eq!(<Election as ElectionInfo>::election_config(&1), plurality());
+	});
+}
+
+#[test]
+fn voting_opens_and_closes_once() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Election::election_open_for_voting(RuntimeOrigin::root(), 1),
+			Error::<Test>::InvalidElectionId
+		);
+		assert_ok!(Election::register_election_for_voting(RuntimeOrigin::root(), plurality()));
+		assert_noop!(
+			Election::election_close_for_voting(RuntimeOrigin::root(), 1),
+			Error::<Test>::ElectionIdNotOpenForVoting
+		);
+		assert_ok!(Election::election_open_for_voting(RuntimeOrigin::root(), 1));
+		assert!(<Election as ElectionInfo>::is_election_open_for_voting(&1));
+		assert_ok!(Election::election_close_for_voting(RuntimeOrigin::root(), 1));
+		assert!(<Election as ElectionInfo>::is_election_closed_for_voting(&1));
+		assert_noop!(
+			Election::election_open_for_voting(RuntimeOrigin::root(), 1),
+			Error::<Test>::ElectionIdAlreadyClosedForVoting
+		);
+	});
+}
+
+#[test]
+fn questions_are_set_before_voting_opens() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let referendum = ElectionConfig { counting_method: CountingMethod::Referendum, ..Default::default() };
+		assert_ok!(Election::register_election_for_voting(RuntimeOrigin::root(), referendum));
+		let one_option: Questions = vec![question(&["Yes"])].try_into().unwrap();
+		assert_noop!(
+			Election::set_election_questions(RuntimeOrigin::root(), 1, one_option),
+			Error::<Test>::InvalidQuestion
+		);
+		let questions: Questions = vec![question(&["Yes", "No"])].try_into().unwrap();
+		assert_ok!(Election::set_election_questions(RuntimeOrigin::root(), 1, questions.clone()));
+		System::assert_last_event(Event::ElectionQuestionsSet { election_id: 1, questions: 1 }.into());
+		assert_eq!(<Election as ElectionInfo>::election_questions(&1), questions);
+		assert_ok!(Election::election_open_for_voting(RuntimeOrigin::root(), 1));
+		assert_noop!(
+			Election::set_election_questions(RuntimeOrigin::root(), 1, questions),
+			Error::<Test>::ElectionIdAlreadyOpenForVoting
+		);
 	});
 }
 
 #[test]
-fn correct_error_for_none_value() {
+fn constituency_repoll_opens_after_voting_closes() {
 	new_test_ext().execute_with(|| {
-		// Ensure the expected error is thrown when no value is present.
+		System::set_block_number(1);
+		assert_ok!(Election::register_election_for_voting(RuntimeOrigin::root(), plurality()));
 		assert_noop!(
-			TemplateModule::cause_error(RuntimeOrigin::signed(1)),
-			Error::<Test>::NoneValue
+			Election::open_constituency_repoll(RuntimeOrigin::root(), 1, 4),
+			Error::<Test>::ElectionIdNotClosedForVoting
 		);
+		assert_ok!(Election::election_open_for_voting(RuntimeOrigin::root(), 1));
+		assert_ok!(Election::election_close_for_voting(RuntimeOrigin::root(), 1));
+		<Election as ElectionControl>::set_result_standing(&1, ResultStanding::Petitioned);
+
+		assert_ok!(Election::open_constituency_repoll(RuntimeOrigin::root(), 1, 4));
+		System::assert_last_event(Event::ConstituencyRepollOpened { election_id: 1, constituency: 4, generation: 1 }.into());
+		assert!(<Election as ElectionInfo>::is_open_for_repoll(&1, &4));
+		assert!(!<Election as ElectionInfo>::is_open_for_repoll(&1, &5));
+		assert!(<Election as ElectionInfo>::has_open_repoll(&1));
+		assert_eq!(<Election as ElectionInfo>::constituency_generation(&1, &4), 1);
+		assert_eq!(<Election as ElectionInfo>::constituency_repolls(&1), 1);
+		// The result is decided afresh
+		assert_eq!(<Election as ElectionInfo>::result_standing(&1), None);
+		assert_noop!(
+			Election::open_constituency_repoll(RuntimeOrigin::root(), 1, 4),
+			Error::<Test>::RepollAlreadyOpen
+		);
+
+		assert_ok!(Election::close_constituency_repoll(RuntimeOrigin::root(), 1, 4));
+		assert!(!<Election as ElectionInfo>::has_open_repoll(&1));
+		assert_noop!(
+			Election::close_constituency_repoll(RuntimeOrigin::root(), 1, 4),
+			Error::<Test>::RepollNotOpen
+		);
+		// A second re-poll of the constituency is a new generation
+		assert_ok!(Election::open_constituency_repoll(RuntimeOrigin::root(), 1, 4));
+		assert_eq!(<Election as ElectionInfo>::constituency_generation(&1, &4), 2);
+		assert_eq!(<Election as ElectionInfo>::constituency_repolls(&1), 2);
+	});
+}
+
+#[test]
+fn constituency_standing_is_kept_per_constituency() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		closed_election();
+		<Election as ElectionControl>::set_constituency_standing(&1, &4, ResultStanding::Voided);
+		System::assert_last_event(Event::ConstituencyStandingChanged { election_id: 1, constituency: 4, standing: ResultStanding::Voided }.into());
+		assert_eq!(<Election as ElectionInfo>::constituency_standing(&1, &4), Some(ResultStanding::Voided));
+		assert_eq!(<Election as ElectionInfo>::constituency_standing(&1, &5), None);
+		assert_eq!(<Election as ElectionInfo>::result_standing(&1), None);
+		// A re-poll of the constituency starts its result afresh
+		assert_ok!(<Election as ElectionControl>::repoll_constituency(&1, &4));
+		assert_eq!(<Election as ElectionInfo>::constituency_standing(&1, &4), None);
+		assert!(<Election as ElectionInfo>::is_open_for_repoll(&1, &4));
+		assert_noop!(
+			<Election as ElectionControl>::repoll_constituency(&1, &4),
+			Error::<Test>::RepollAlreadyOpen
+		);
+	});
+}
+
+#[test]
+fn countermand_and_repoll_start_a_new_generation() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Election::register_election_for_voting(RuntimeOrigin::root(), plurality()));
+		assert_ok!(Election::election_open_for_voting(RuntimeOrigin::root(), 1));
+		<Election as ElectionControl>::countermand(&1);
+		assert!(<Election as ElectionInfo>::is_countermanded(&1));
+		assert!(!<Election as ElectionInfo>::is_election_open_for_voting(&1));
+		assert_eq!(<Election as ElectionInfo>::repoll_generation(&1), 1);
+		// Opening voting again holds the poll afresh
+		assert_ok!(Election::election_open_for_voting(RuntimeOrigin::root(), 1));
+		assert!(!<Election as ElectionInfo>::is_countermanded(&1));
+		assert_ok!(Election::election_close_for_voting(RuntimeOrigin::root(), 1));
+
+		// A closed poll ordered re-polled waits for voting to open again
+		<Election as ElectionControl>::order_repoll(&1);
+		assert_eq!(<Election as ElectionInfo>::repoll_generation(&1), 2);
+		assert!(!<Election as ElectionInfo>::is_election_closed_for_voting(&1));
+		assert!(<Election as ElectionInfo>::is_countermanded(&1));
+		assert_ok!(Election::election_open_for_voting(RuntimeOrigin::root(), 1));
 	});
 }
@@ -0,0 +1,79 @@
+//! RPC interface for the observer pallet, so a public dashboard can show the incidents
+//! still open in an election.
+
+use std::sync::Arc;
+
+use codec::Codec;
+use jsonrpsee::{
+	core::{async_trait, Error as JsonRpseeError, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use primitives::{ElectionId, Incident, IncidentId};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+
+pub use pallet_observer_runtime_api::ObserverApi as ObserverRuntimeApi;
+
+#[rpc(client, server)]
+pub trait ObserverApi<BlockHash, AccountId, Hash, BlockNumber> {
+	#[method(name = "observer_openIncidents")]
+	fn open_incidents(
+		&self,
+		election_id: ElectionId,
+		after: Option<IncidentId>,
+		limit: u32,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<(IncidentId, Incident<AccountId, Hash, BlockNumber>)>>;
+}
+
+/// Provides RPC methods to query incident reports.
+pub struct Observer<C, P> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<P>,
+}
+
+impl<C, P> Observer<C, P> {
+	/// Creates a new instance of the Observer RPC helper.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error code for runtime API failures.
+const RUNTIME_ERROR: i32 = 1;
+
+fn runtime_error_into_rpc_err(err: impl std::fmt::Debug) -> JsonRpseeError {
+	CallError::Custom(ErrorObject::owned(
+		RUNTIME_ERROR,
+		"Unable to query incidents.",
+		Some(format!("{:?}", err)),
+	))
+	.into()
+}
+
+#[async_trait]
+impl<C, Block, AccountId, Hash, BlockNumber> ObserverApiServer<<Block as BlockT>::Hash, AccountId, Hash, BlockNumber>
+	for Observer<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: ObserverRuntimeApi<Block, AccountId, Hash, BlockNumber>,
+	AccountId: Codec + Send + Sync + 'static,
+	Hash: Codec + Send + Sync + 'static,
+	BlockNumber: Codec + Send + Sync + 'static,
+{
+	fn open_incidents(
+		&self,
+		election_id: ElectionId,
+		after: Option<IncidentId>,
+		limit: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<(IncidentId, Incident<AccountId, Hash, BlockNumber>)>> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.open_incidents(at_hash, election_id, after, limit).map_err(runtime_error_into_rpc_err)
+	}
+}
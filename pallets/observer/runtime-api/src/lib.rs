@@ -0,0 +1,18 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// Runtime API for following observers' incident reports from outside the runtime.
+use codec::Codec;
+use primitives::{ElectionId, Incident, IncidentId};
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	pub trait ObserverApi<AccountId, Hash, BlockNumber> where
+		AccountId: Codec,
+		Hash: Codec,
+		BlockNumber: Codec,
+	{
+		/// A page of at most `limit` incidents of an election not yet resolved, starting
+		/// after the incident `after` that ended the page before
+		fn open_incidents(election_id: ElectionId, after: Option<IncidentId>, limit: u32) -> Vec<(IncidentId, Incident<AccountId, Hash, BlockNumber>)>;
+	}
+}
@@ -0,0 +1,314 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// Edit this file to define custom logic or remove it if it is not needed.
+/// Learn more about FRAME and the core library of Substrate FRAME pallets:
+/// <https://docs.substrate.io/reference/frame-pallets/>
+pub use pallet::*;
+use frame_support::pallet_prelude::*;
+use frame_system::pallet_prelude::*;
+use frame_support::traits::EnsureOriginWithArg;
+use sp_runtime::ArithmeticError;
+use primitives::{
+	ConstituencyId, ElectionId, Incident, IncidentCategory, IncidentId, IncidentSeverity, IncidentStatus,
+	RoleScope,
+};
+use pallet_roles::{Role, RoleControl};
+use pallet_election::ElectionInfo;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+pub type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
+pub type IncidentOf<T> = Incident<AccountIdOf<T>, <T as frame_system::Config>::Hash, <T as frame_system::Config>::BlockNumber>;
+
+/// Maximum length of the name of an observer's organisation
+pub const MAX_ORGANISATION_LEN: u32 = 128;
+pub type Organisation = BoundedVec<u8, ConstU32<MAX_ORGANISATION_LEN>>;
+/// Most incidents returned in one page of `open_incidents`
+pub const MAX_INCIDENTS_PER_PAGE: u32 = 100;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	/// Configure the pallet by specifying the parameters and types on which it depends.
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// Allowed origins for accrediting observers and handling their reports
+		type ElectionCommissionApproveOrigin: EnsureOriginWithArg<Self::RuntimeOrigin, RoleScope>;
+		type Roles: RoleControl<Self::AccountId>;
+		type ElectionInfo: ElectionInfo;
+		/// Most incidents one observer can have open in an election at a time
+		#[pallet::constant]
+		type MaxOpenIncidentsPerObserver: Get<u32>;
+	}
+
+	// The pallet's runtime storage items.
+	// https://docs.substrate.io/main-docs/build/runtime-storage/
+	#[pallet::storage]
+	#[pallet::getter(fn accreditation)]
+	// Observers accredited to each election, with the organisation they observe for
+	pub type Accreditations<T> = StorageDoubleMap<_, Blake2_128Concat, ElectionId, Blake2_128Concat, AccountIdOf<T>, Organisation>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn granted_role)]
+	// Scope of the observer role granted with each accreditation, when the observer did
+	// not already hold it; withdrawing the accreditation revokes only this grant
+	pub type GrantedRoles<T> = StorageDoubleMap<_, Blake2_128Concat, ElectionId, Blake2_128Concat, AccountIdOf<T>, RoleScope>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn incidents)]
+	// Incidents reported by observers, open and resolved
+	pub type Incidents<T: Config> = StorageMap<_, Blake2_128Concat, IncidentId, IncidentOf<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn incident_id_counter)]
+	/// IncidentId counter
+	pub type IncidentIdCounter<T: Config> = StorageValue<_, IncidentId>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn is_incident_open)]
+	// Incidents of each election not yet resolved
+	pub type OpenIncidents<T: Config> = StorageDoubleMap<_, Blake2_128Concat, ElectionId, Blake2_128Concat, IncidentId, bool, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn open_incidents_of)]
+	// Number of incidents each observer has open in an election
+	pub type ObserverOpenIncidents<T: Config> = StorageDoubleMap<_, Blake2_128Concat, ElectionId, Blake2_128Concat, AccountIdOf<T>, u32, ValueQuery>;
+
+	// Pallets use events to inform users when important changes are made.
+	// https://docs.substrate.io/main-docs/build/events-errors/
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Commission accredited an observer to an election
+		/// parameters. [election_id, observer]
+		ObserverAccredited{election_id: ElectionId, observer: AccountIdOf<T>},
+		/// Commission withdrew an observer's accreditation
+		/// parameters. [election_id, observer]
+		AccreditationWithdrawn{election_id: ElectionId, observer: AccountIdOf<T>},
+		/// Observer reported an incident
+		/// parameters. [incident_id, election_id, constituency, category, severity]
+		IncidentReported{incident_id: IncidentId, election_id: ElectionId, constituency: ConstituencyId, category: IncidentCategory, severity: IncidentSeverity},
+		/// Commission acknowledged an incident
+		/// parameters. [incident_id, election_id]
+		IncidentAcknowledged{incident_id: IncidentId, election_id: ElectionId},
+		/// Commission resolved an incident
+		/// parameters. [incident_id, election_id, resolution]
+		IncidentResolved{incident_id: IncidentId, election_id: ElectionId, resolution: T::Hash},
+	}
+
+	// Errors inform users that something went wrong.
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Error: Observer Already Accredited For This Election.
+		AlreadyAccredited,
+		/// Error: Observer Not Accredited For This Election.
+		NotAccredited,
+		/// Error: Account Is Not An Observer For This Constituency.
+		NotAnObserver,
+		/// Error: Incident Not Found.
+		IncidentNotFound,
+		/// Error: Incident Already Acknowledged.
+		IncidentAlreadyAcknowledged,
+		/// Error: Incident Already Resolved.
+		IncidentAlreadyResolved,
+		/// Error: Observer Has The Most Open Incidents Allowed.
+		TooManyOpenIncidents,
+		/// Error: Invalid Election Id.
+		InvalidElectionId,
+	}
+
+	// Dispatchable functions allows users to interact with the pallet and invoke state changes.
+	// These functions materialize as "extrinsics", which are often compared to transactions.
+	// Dispatchable functions must be annotated with a weight and must return a DispatchResult.
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Accredit an independent observer to an election, or to one of its constituencies.
+		/// The observer is granted the observer role over that scope, unless they already
+		/// hold it, and can report from the constituencies it covers.
+		#[pallet::call_index(0)]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(6, 3).ref_time())]
+		pub fn accredit_observer(
+			origin: OriginFor<T>,
+			election_id: ElectionId,
+			constituency: Option<ConstituencyId>,
+			observer: AccountIdOf<T>,
+			organisation: Organisation,
+		) -> DispatchResult {
+			// Check if the sender is an approved origin or not
+			T::ElectionCommissionApproveOrigin::ensure_origin(origin, &RoleScope::Election(election_id))?;
+			ensure!(T::ElectionInfo::election_exists(&election_id), Error::<T>::InvalidElectionId);
+			ensure!(
+				!<Accreditations<T>>::contains_key(election_id, &observer),
+				Error::<T>::AlreadyAccredited
+			);
+			<Accreditations<T>>::insert(election_id, &observer, organisation);
+			let scope = constituency.map_or(RoleScope::Election(election_id), |constituency| {
+				RoleScope::Constituency(election_id, constituency)
+			});
+			if !T::Roles::has_role(&observer, Role::Observer, &scope) {
+				T::Roles::grant_role(&observer, Role::Observer, scope);
+				<GrantedRoles<T>>::insert(election_id, &observer, scope);
+			}
+
+			// Emit an event.
+			Self::deposit_event(Event::ObserverAccredited{election_id, observer});
+			// Return a successful DispatchResultWithPostInfo
+			Ok(())
+		}
+		/// Withdraw an observer's accreditation, and the observer role if the accreditation
+		/// granted it. Incidents they reported are kept.
+		#[pallet::call_index(1)]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 3).ref_time())]
+		pub fn withdraw_accreditation(origin: OriginFor<T>, election_id: ElectionId, observer: AccountIdOf<T>) -> DispatchResult {
+			// Check if the sender is an approved origin or not
+			T::ElectionCommissionApproveOrigin::ensure_origin(origin, &RoleScope::Election(election_id))?;
+			ensure!(
+				<Accreditations<T>>::take(election_id, &observer).is_some(),
+				Error::<T>::NotAccredited
+			);
+			if let Some(scope) = <GrantedRoles<T>>::take(election_id, &observer) {
+				T::Roles::revoke_role(&observer, Role::Observer, scope);
+			}
+
+			// Emit an event.
+			Self::deposit_event(Event::AccreditationWithdrawn{election_id, observer});
+			// Return a successful DispatchResultWithPostInfo
+			Ok(())
+		}
+		/// Report an incident in a constituency, by an observer accredited to the election
+		/// who holds the observer role over the constituency. The evidence itself is kept
+		/// off-chain under its hash. An observer can have at most
+		/// `MaxOpenIncidentsPerObserver` incidents open at a time.
+		#[pallet::call_index(2)]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(8, 4).ref_time())]
+		pub fn report_incident(
+			origin: OriginFor<T>,
+			election_id: ElectionId,
+			constituency: ConstituencyId,
+			category: IncidentCategory,
+			severity: IncidentSeverity,
+			evidence: T::Hash,
+		) -> DispatchResult {
+			let reporter = ensure_signed(origin)?;
+			ensure!(T::ElectionInfo::election_exists(&election_id), Error::<T>::InvalidElectionId);
+			ensure!(
+				<Accreditations<T>>::contains_key(election_id, &reporter),
+				Error::<T>::NotAccredited
+			);
+			ensure!(
+				T::Roles::has_role(&reporter, Role::Observer, &RoleScope::Constituency(election_id, constituency)),
+				Error::<T>::NotAnObserver
+			);
+			<ObserverOpenIncidents<T>>::try_mutate(election_id, &reporter, |count| -> DispatchResult {
+				ensure!(*count < T::MaxOpenIncidentsPerObserver::get(), Error::<T>::TooManyOpenIncidents);
+				*count += 1;
+				Ok(())
+			})?;
+			let incident_id = Self::incident_id_inc()?;
+			<Incidents<T>>::insert(incident_id, Incident{
+				election_id,
+				constituency,
+				reporter,
+				category,
+				severity,
+				evidence,
+				reported_at: <frame_system::Pallet<T>>::block_number(),
+				status: IncidentStatus::Open,
+				resolution: None,
+			});
+			<OpenIncidents<T>>::insert(election_id, incident_id, true);
+
+			// Emit an event.
+			Self::deposit_event(Event::IncidentReported{incident_id, election_id, constituency, category, severity});
+			// Return a successful DispatchResultWithPostInfo
+			Ok(())
+		}
+		/// Acknowledge an open incident, to show the commission is looking into it
+		#[pallet::call_index(3)]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 1).ref_time())]
+		pub fn acknowledge_incident(origin: OriginFor<T>, incident_id: IncidentId) -> DispatchResult {
+			let mut incident = <Incidents<T>>::get(incident_id).ok_or(Error::<T>::IncidentNotFound)?;
+			// Check if the sender is an approved origin or not
+			T::ElectionCommissionApproveOrigin::ensure_origin(origin, &RoleScope::Constituency(incident.election_id, incident.constituency))?;
+			match incident.status {
+				IncidentStatus::Open => (),
+				IncidentStatus::Acknowledged => return Err(Error::<T>::IncidentAlreadyAcknowledged.into()),
+				IncidentStatus::Resolved => return Err(Error::<T>::IncidentAlreadyResolved.into()),
+			}
+			let election_id = incident.election_id;
+			incident.status = IncidentStatus::Acknowledged;
+			<Incidents<T>>::insert(incident_id, incident);
+
+			// Emit an event.
+			Self::deposit_event(Event::IncidentAcknowledged{incident_id, election_id});
+			// Return a successful DispatchResultWithPostInfo
+			Ok(())
+		}
+		/// Resolve an incident, acknowledged or not, recording the hash of the commission's
+		/// findings
+		#[pallet::call_index(4)]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 3).ref_time())]
+		pub fn resolve_incident(origin: OriginFor<T>, incident_id: IncidentId, resolution: T::Hash) -> DispatchResult {
+			let mut incident = <Incidents<T>>::get(incident_id).ok_or(Error::<T>::IncidentNotFound)?;
+			// Check if the sender is an approved origin or not
+			T::ElectionCommissionApproveOrigin::ensure_origin(origin, &RoleScope::Constituency(incident.election_id, incident.constituency))?;
+			ensure!(incident.status != IncidentStatus::Resolved, Error::<T>::IncidentAlreadyResolved);
+			let election_id = incident.election_id;
+			incident.status = IncidentStatus::Resolved;
+			incident.resolution = Some(resolution);
+			<ObserverOpenIncidents<T>>::mutate(election_id, &incident.reporter, |count| *count = count.saturating_sub(1));
+			<Incidents<T>>::insert(incident_id, incident);
+			<OpenIncidents<T>>::remove(election_id, incident_id);
+
+			// Emit an event.
+			Self::deposit_event(Event::IncidentResolved{incident_id, election_id, resolution});
+			// Return a successful DispatchResultWithPostInfo
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	pub fn incident_id_inc() -> Result<IncidentId, DispatchError> {
+		if IncidentIdCounter::<T>::get().is_some() {
+			let incident_id = IncidentIdCounter::<T>::get()
+				.unwrap()
+				.checked_add(1)
+				.ok_or(ArithmeticError::Overflow)?;
+			IncidentIdCounter::<T>::set(Option::from(incident_id));
+			Ok(incident_id)
+		} else {
+			IncidentIdCounter::<T>::set(Some(1));
+			Ok(1)
+		}
+	}
+
+	/// A page of at most `limit` incidents of an election not yet resolved, capped by
+	/// `MAX_INCIDENTS_PER_PAGE`. Pages follow storage order: the next page starts after
+	/// `after`, the last incident of the page before.
+	pub fn open_incidents(election_id: ElectionId, after: Option<IncidentId>, limit: u32) -> Vec<(IncidentId, IncidentOf<T>)> {
+		let limit = limit.min(MAX_INCIDENTS_PER_PAGE) as usize;
+		let incident_ids = match after {
+			Some(incident_id) => <OpenIncidents<T>>::iter_key_prefix_from(
+				election_id,
+				<OpenIncidents<T>>::hashed_key_for(election_id, incident_id),
+			),
+			None => <OpenIncidents<T>>::iter_key_prefix(election_id),
+		};
+		incident_ids
+			.take(limit)
+			.filter_map(|incident_id| <Incidents<T>>::get(incident_id).map(|incident| (incident_id, incident)))
+			.collect()
+	}
+}
@@ -0,0 +1,85 @@
+use crate as pallet_observer;
+use pallet_roles::{EnsureRole, Role};
+use frame_support::{
+	parameter_types,
+	traits::{ConstU16, ConstU32, ConstU64},
+};
+use frame_system::EnsureRoot;
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system,
+		Roles: pallet_roles,
+		Election: pallet_election,
+		Observer: pallet_observer,
+	}
+);
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ConstU16<42>;
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_roles::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type RoleAdminOrigin = EnsureRoot<u64>;
+}
+
+impl pallet_election::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type ElectionCommissionApproveOrigin = EnsureRole<Test, CommissionRole, EnsureRoot<u64>>;
+	type CreateElectionOrigin = EnsureRoot<u64>;
+	type VotingWindowOrigin = EnsureRoot<u64>;
+}
+
+parameter_types! {
+	pub const CommissionRole: Role = Role::Commission;
+}
+
+impl pallet_observer::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type ElectionCommissionApproveOrigin = EnsureRole<Test, CommissionRole, EnsureRoot<u64>>;
+	type Roles = Roles;
+	type ElectionInfo = Election;
+	type MaxOpenIncidentsPerObserver = ConstU32<2>;
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	frame_system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+}
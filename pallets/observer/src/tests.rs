@@ -0,0 +1,222 @@
+use crate::{mock::*, Error, Event, Organisation};
+use frame_support::{assert_noop, assert_ok};
+use pallet_roles::Role;
+use primitives::{ElectionConfig, IncidentCategory, IncidentSeverity, IncidentStatus, RoleScope};
+use sp_core::H256;
+use sp_runtime::DispatchError;
+
+fn organisation() -> Organisation {
+	b"Election Watch".to_vec().try_into().unwrap()
+}
+
+/// Election 1, registered for voting
+fn election() {
+	assert_ok!(Election::register_election_for_voting(RuntimeOrigin::root(), ElectionConfig { seats: 1, ..Default::default() }));
+}
+
+fn accredit(observer: u64) {
+	assert_ok!(Observer::accredit_observer(RuntimeOrigin::root(), 1, None, observer, organisation()));
+}
+
+fn report(observer: u64, constituency: u32) -> sp_runtime::DispatchResult {
+	Observer::report_incident(
+		RuntimeOrigin::signed(observer),
+		1,
+		constituency,
+		IncidentCategory::BallotTampering,
+		IncidentSeverity::High,
+		H256::repeat_byte(1),
+	)
+}
+
+#[test]
+fn accredit_observer_grants_the_role() {
+	new_test_ext().execute_with(|| {
+		// Go past genesis block so events get deposited
+		System::set_block_number(1);
+		assert_noop!(
+			Observer::accredit_observer(RuntimeOrigin::root(), 1, None, 10, organisation()),
+			Error::<Test>::InvalidElectionId
+		);
+		election();
+		assert_noop!(
+			Observer::accredit_observer(RuntimeOrigin::signed(2), 1, None, 10, organisation()),
+			DispatchError::BadOrigin
+		);
+		accredit(10);
+		System::assert_has_event(Event::ObserverAccredited { election_id: 1, observer: 10 }.into());
+		assert_eq!(Observer::accreditation(1, 10), Some(organisation()));
+		assert!(Roles::has_role(&10, Role::Observer, &RoleScope::Constituency(1, 3)));
+		assert_eq!(Observer::granted_role(1, 10), Some(RoleScope::Election(1)));
+		assert_noop!(
+			Observer::accredit_observer(RuntimeOrigin::root(), 1, None, 10, organisation()),
+			Error::<Test>::AlreadyAccredited
+		);
+	});
+}
+
+#[test]
+fn commissioner_of_the_election_can_accredit() {
+	new_test_ext().execute_with(|| {
+		election();
+		assert_ok!(Roles::grant_role(RuntimeOrigin::root(), 2, Role::Commission, RoleScope::Election(1)));
+		assert_ok!(Observer::accredit_observer(RuntimeOrigin::signed(2), 1, None, 10, organisation()));
+		assert_noop!(
+			Observer::accredit_observer(RuntimeOrigin::signed(2), 2, None, 10, organisation()),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn only_observers_report_incidents() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(report(10, 3), Error::<Test>::InvalidElectionId);
+		election();
+		assert_noop!(report(10, 3), Error::<Test>::NotAccredited);
+		// Holding the role is not enough without an accreditation
+		assert_ok!(Roles::grant_role(RuntimeOrigin::root(), 11, Role::Observer, RoleScope::Election(1)));
+		assert_noop!(report(11, 3), Error::<Test>::NotAccredited);
+		accredit(10);
+		assert_ok!(report(10, 3));
+		System::assert_last_event(Event::IncidentReported {
+			incident_id: 1,
+			election_id: 1,
+			constituency: 3,
+			category: IncidentCategory::BallotTampering,
+			severity: IncidentSeverity::High,
+		}.into());
+		let incident = Observer::incidents(1).unwrap();
+		assert_eq!(incident.reporter, 10);
+		assert_eq!(incident.reported_at, 1);
+		assert_eq!(incident.status, IncidentStatus::Open);
+		assert!(Observer::is_incident_open(1, 1));
+		assert_eq!(Observer::open_incidents_of(1, 10), 1);
+	});
+}
+
+#[test]
+fn constituency_observer_reports_from_that_constituency_only() {
+	new_test_ext().execute_with(|| {
+		election();
+		assert_ok!(Observer::accredit_observer(RuntimeOrigin::root(), 1, Some(3), 11, organisation()));
+		assert!(Roles::has_role(&11, Role::Observer, &RoleScope::Constituency(1, 3)));
+		assert_ok!(report(11, 3));
+		assert_noop!(report(11, 4), Error::<Test>::NotAnObserver);
+	});
+}
+
+#[test]
+fn incident_is_acknowledged_then_resolved() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		election();
+		accredit(10);
+		assert_ok!(report(10, 3));
+		// A commissioner of another constituency cannot handle it
+		assert_ok!(Roles::grant_role(RuntimeOrigin::root(), 2, Role::Commission, RoleScope::Constituency(1, 4)));
+		assert_noop!(Observer::acknowledge_incident(RuntimeOrigin::signed(2), 1), DispatchError::BadOrigin);
+		assert_ok!(Roles::grant_role(RuntimeOrigin::root(), 3, Role::Commission, RoleScope::Constituency(1, 3)));
+		assert_ok!(Observer::acknowledge_incident(RuntimeOrigin::signed(3), 1));
+		System::assert_last_event(Event::IncidentAcknowledged { incident_id: 1, election_id: 1 }.into());
+		assert_eq!(Observer::incidents(1).unwrap().status, IncidentStatus::Acknowledged);
+		assert_noop!(
+			Observer::acknowledge_incident(RuntimeOrigin::signed(3), 1),
+			Error::<Test>::IncidentAlreadyAcknowledged
+		);
+		assert!(Observer::is_incident_open(1, 1));
+
+		let resolution = H256::repeat_byte(2);
+		assert_ok!(Observer::resolve_incident(RuntimeOrigin::signed(3), 1, resolution));
+		System::assert_last_event(Event::IncidentResolved { incident_id: 1, election_id: 1, resolution }.into());
+		let incident = Observer::incidents(1).unwrap();
+		assert_eq!(incident.status, IncidentStatus::Resolved);
+		assert_eq!(incident.resolution, Some(resolution));
+		assert!(!Observer::is_incident_open(1, 1));
+		assert_eq!(Observer::open_incidents_of(1, 10), 0);
+		assert_noop!(
+			Observer::resolve_incident(RuntimeOrigin::signed(3), 1, resolution),
+			Error::<Test>::IncidentAlreadyResolved
+		);
+		assert_noop!(
+			Observer::acknowledge_incident(RuntimeOrigin::signed(3), 1),
+			Error::<Test>::IncidentAlreadyResolved
+		);
+		assert_noop!(
+			Observer::resolve_incident(RuntimeOrigin::root(), 2, resolution),
+			Error::<Test>::IncidentNotFound
+		);
+	});
+}
+
+#[test]
+fn open_incidents_are_capped_per_observer() {
+	new_test_ext().execute_with(|| {
+		election();
+		accredit(10);
+		accredit(11);
+		assert_ok!(report(10, 3));
+		assert_ok!(report(10, 3));
+		assert_noop!(report(10, 3), Error::<Test>::TooManyOpenIncidents);
+		// Other observers have their own allowance
+		assert_ok!(report(11, 3));
+		// Resolving an incident frees a place
+		assert_ok!(Observer::resolve_incident(RuntimeOrigin::root(), 1, H256::repeat_byte(2)));
+		assert_ok!(report(10, 3));
+	});
+}
+
+#[test]
+fn withdrawn_observer_stops_reporting_and_incidents_are_kept() {
+	new_test_ext().execute_with(|| {
+		election();
+		accredit(10);
+		assert_ok!(report(10, 3));
+		assert_ok!(Observer::withdraw_accreditation(RuntimeOrigin::root(), 1, 10));
+		assert!(!Roles::has_role(&10, Role::Observer, &RoleScope::Election(1)));
+		assert_eq!(Observer::granted_role(1, 10), None);
+		assert_noop!(report(10, 3), Error::<Test>::NotAccredited);
+		assert!(Observer::incidents(1).is_some());
+		assert_noop!(
+			Observer::withdraw_accreditation(RuntimeOrigin::root(), 1, 10),
+			Error::<Test>::NotAccredited
+		);
+	});
+}
+
+#[test]
+fn withdrawal_keeps_a_role_granted_elsewhere() {
+	new_test_ext().execute_with(|| {
+		election();
+		assert_ok!(Roles::grant_role(RuntimeOrigin::root(), 10, Role::Observer, RoleScope::Election(1)));
+		accredit(10);
+		assert_eq!(Observer::granted_role(1, 10), None);
+		assert_ok!(Observer::withdraw_accreditation(RuntimeOrigin::root(), 1, 10));
+		assert!(Roles::has_role(&10, Role::Observer, &RoleScope::Election(1)));
+	});
+}
+
+#[test]
+fn open_incidents_are_paged() {
+	new_test_ext().execute_with(|| {
+		election();
+		accredit(10);
+		accredit(11);
+		assert_ok!(report(10, 3));
+		assert_ok!(report(10, 4));
+		assert_ok!(report(11, 3));
+		assert_ok!(report(11, 4));
+		assert_ok!(Observer::resolve_incident(RuntimeOrigin::root(), 2, H256::repeat_byte(2)));
+
+		let first = Observer::open_incidents(1, None, 2);
+		assert_eq!(first.len(), 2);
+		let second = Observer::open_incidents(1, first.last().map(|(id, _)| *id), 2);
+		assert_eq!(second.len(), 1);
+		let mut ids: Vec<_> = first.iter().chain(second.iter()).map(|(id, _)| *id).collect();
+		ids.sort();
+		assert_eq!(ids, vec![1, 3, 4]);
+		assert!(Observer::open_incidents(1, second.last().map(|(id, _)| *id), 2).is_empty());
+		assert!(Observer::open_incidents(2, None, 10).is_empty());
+	});
+}
@@ -0,0 +1,362 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// Edit this file to define custom logic or remove it if it is not needed.
+/// Learn more about FRAME and the core library of Substrate FRAME pallets:
+/// <https://docs.substrate.io/reference/frame-pallets/>
+pub use pallet::*;
+use frame_support::pallet_prelude::*;
+use frame_system::pallet_prelude::*;
+use sp_runtime::ArithmeticError;
+use primitives::{ElectionId, CandidateId, VoteCount};
+use pallet_candidate::{CandidateAccounts, CandidateLookup, CandidateWithdrawal, ElectionResults};
+use pallet_election::ElectionInfo;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+pub type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
+/// The type for identifying the parties
+pub type PartyId = u32;
+/// Maximum length of a party's name
+pub const MAX_PARTY_NAME_LEN: u32 = 128;
+/// Maximum length of a party's abbreviation
+pub const MAX_ABBREVIATION_LEN: u32 = 16;
+
+/// A registered political party
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Party<AccountId, Hash> {
+	pub name: BoundedVec<u8, ConstU32<MAX_PARTY_NAME_LEN>>,
+	pub abbreviation: BoundedVec<u8, ConstU32<MAX_ABBREVIATION_LEN>>,
+	/// Hash of the party's symbol image as printed on the ballot
+	pub symbol: Hash,
+	/// Account authorised to confirm candidates on the party's behalf
+	pub signatory: AccountId,
+}
+
+/// A candidate's declared party, confirmed once the party's signatory endorses it
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum Affiliation {
+	Declared(PartyId),
+	Confirmed(PartyId),
+}
+
+/// A party's results added up over the constituencies of a general election
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct PartyResult {
+	pub votes: VoteCount,
+	pub seats: u32,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	/// Configure the pallet by specifying the parameters and types on which it depends.
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// Allowed origins for only election commission
+		type ElectionCommissionApproveOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		type ElectionInfo: ElectionInfo;
+		type CandidateLookup: CandidateLookup;
+		type CandidateAccounts: CandidateAccounts<Self::AccountId>;
+		type ElectionResults: ElectionResults;
+	}
+
+	// The pallet's runtime storage items.
+	// https://docs.substrate.io/main-docs/build/runtime-storage/
+	#[pallet::storage]
+	#[pallet::getter(fn parties)]
+	// Registered political parties
+	pub type Parties<T: Config> = StorageMap<_, Blake2_128Concat, PartyId, Party<AccountIdOf<T>, T::Hash>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn party_id_counter)]
+	/// PartyId counter
+	pub type PartyIdCounter<T: Config> = StorageValue<_, PartyId>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn affiliation)]
+	// Party each candidate declared, and whether the party confirmed it
+	pub type Affiliations<T: Config> = StorageDoubleMap<_, Blake2_128Concat, ElectionId, Blake2_128Concat, CandidateId, Affiliation>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn general_election)]
+	// General election each constituency's election belongs to
+	pub type Constituencies<T: Config> = StorageMap<_, Blake2_128Concat, ElectionId, ElectionId>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn constituency_recorded)]
+	// Certification of each constituency's results the party totals were last recorded
+	// from, and whether the results stood then
+	pub type ConstituencyRecorded<T: Config> = StorageMap<_, Blake2_128Concat, ElectionId, (u32, bool)>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn recorded_contribution)]
+	// Votes and seats each constituency added to the totals of each party
+	pub type RecordedContributions<T: Config> = StorageDoubleMap<_, Blake2_128Concat, ElectionId, Blake2_128Concat, PartyId, PartyResult>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn party_result)]
+	// Votes and seats of each party over the recorded constituencies of a general election
+	pub type PartyResults<T: Config> = StorageDoubleMap<_, Blake2_128Concat, ElectionId, Blake2_128Concat, PartyId, PartyResult, ValueQuery>;
+
+	// Pallets use events to inform users when important changes are made.
+	// https://docs.substrate.io/main-docs/build/events-errors/
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Party is successfully registered
+		/// parameters. [party_id]
+		PartyRegistered{party_id: PartyId},
+		/// Candidate declared their party
+		/// parameters. [election_id, candidate_id, party_id]
+		AffiliationDeclared{election_id: ElectionId, candidate_id: CandidateId, party_id: PartyId},
+		/// Party signatory confirmed a candidate
+		/// parameters. [election_id, candidate_id, party_id]
+		AffiliationConfirmed{election_id: ElectionId, candidate_id: CandidateId, party_id: PartyId},
+		/// Party withdrew its endorsement of a candidate
+		/// parameters. [election_id, candidate_id, party_id]
+		EndorsementWithdrawn{election_id: ElectionId, candidate_id: CandidateId, party_id: PartyId},
+		/// Election is counted as a constituency of a general election
+		/// parameters. [election_id, general_election]
+		ConstituencySet{election_id: ElectionId, general_election: ElectionId},
+		/// Constituency results are recorded in the party totals, or taken out of them
+		/// when they no longer stand
+		/// parameters. [election_id, general_election, counted]
+		ConstituencyRecorded{election_id: ElectionId, general_election: ElectionId, counted: bool},
+	}
+
+	// Errors inform users that something went wrong.
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Error: Party Not Found.
+		PartyNotFound,
+		/// Error: Account Is Not An Accepted Candidate In This Election.
+		NotACandidate,
+		/// Error: Candidate Has Not Declared This Party.
+		AffiliationNotFound,
+		/// Error: Affiliation Already Confirmed.
+		AffiliationAlreadyConfirmed,
+		/// Error: Caller Is Not The Party Signatory.
+		NotPartySignatory,
+		/// Error: Election Is Not A Constituency.
+		NotAConstituency,
+		/// Error: Election Results Not Certified.
+		ResultsNotCertified,
+		/// Error: Constituency Results Already Recorded As They Stand.
+		ConstituencyAlreadyRecorded,
+		/// Error: Affiliations Closed Once Voting Opens.
+		AffiliationsClosed,
+		/// Error: Invalid Election Id.
+		InvalidElectionId,
+	}
+
+	// Dispatchable functions allows users to interact with the pallet and invoke state changes.
+	// These functions materialize as "extrinsics", which are often compared to transactions.
+	// Dispatchable functions must be annotated with a weight and must return a DispatchResult.
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Register a party with its name, abbreviation, symbol and signatory
+		#[pallet::call_index(0)]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 2).ref_time())]
+		pub fn register_party(
+			origin: OriginFor<T>,
+			name: BoundedVec<u8, ConstU32<MAX_PARTY_NAME_LEN>>,
+			abbreviation: BoundedVec<u8, ConstU32<MAX_ABBREVIATION_LEN>>,
+			symbol: T::Hash,
+			signatory: AccountIdOf<T>,
+		) -> DispatchResult {
+			// Check if the sender is an approved origin or not
+			T::ElectionCommissionApproveOrigin::ensure_origin(origin)?;
+			let party_id = Self::party_id_inc()?;
+			// Update storage for party list
+			<Parties<T>>::insert(party_id, Party{
+				name,
+				abbreviation,
+				symbol,
+				signatory,
+			});
+
+			// Emit an event.
+			Self::deposit_event(Event::PartyRegistered{party_id});
+			// Return a successful DispatchResultWithPostInfo
+			Ok(())
+		}
+		/// Declare the party of the caller's candidacy, replacing an earlier declaration.
+		/// The party's signatory must confirm it. Affiliations are fixed once voting opens.
+		#[pallet::call_index(1)]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(5, 1).ref_time())]
+		pub fn declare_affiliation(origin: OriginFor<T>, election_id: ElectionId, party_id: PartyId) -> DispatchResult {
+			let account = ensure_signed(origin)?;
+			Self::ensure_affiliations_open(election_id)?;
+			let candidate_id = T::CandidateAccounts::candidate_of(&election_id, &account).ok_or(Error::<T>::NotACandidate)?;
+			ensure!(<Parties<T>>::contains_key(party_id), Error::<T>::PartyNotFound);
+			<Affiliations<T>>::insert(election_id, candidate_id, Affiliation::Declared(party_id));
+
+			// Emit an event.
+			Self::deposit_event(Event::AffiliationDeclared{election_id, candidate_id, party_id});
+			// Return a successful DispatchResultWithPostInfo
+			Ok(())
+		}
+		/// Confirm a candidate who declared the party, by the party's signatory
+		#[pallet::call_index(2)]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 1).ref_time())]
+		pub fn confirm_affiliation(origin: OriginFor<T>, election_id: ElectionId, candidate_id: CandidateId) -> DispatchResult {
+			let signatory = ensure_signed(origin)?;
+			let party_id = match <Affiliations<T>>::get(election_id, candidate_id) {
+				Some(Affiliation::Declared(party_id)) => party_id,
+				Some(Affiliation::Confirmed(_)) => return Err(Error::<T>::AffiliationAlreadyConfirmed.into()),
+				None => return Err(Error::<T>::AffiliationNotFound.into()),
+			};
+			Self::ensure_signatory(party_id, &signatory)?;
+			<Affiliations<T>>::insert(election_id, candidate_id, Affiliation::Confirmed(party_id));
+
+			// Emit an event.
+			Self::deposit_event(Event::AffiliationConfirmed{election_id, candidate_id, party_id});
+			// Return a successful DispatchResultWithPostInfo
+			Ok(())
+		}
+		/// Withdraw the party's endorsement of a candidate, declared or confirmed, by the
+		/// party's signatory. The candidate then stands as an independent. Only possible
+		/// before voting opens, as the party totals count the candidate's votes.
+		#[pallet::call_index(3)]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(5, 1).ref_time())]
+		pub fn withdraw_endorsement(origin: OriginFor<T>, election_id: ElectionId, candidate_id: CandidateId) -> DispatchResult {
+			let signatory = ensure_signed(origin)?;
+			Self::ensure_affiliations_open(election_id)?;
+			let party_id = match <Affiliations<T>>::get(election_id, candidate_id) {
+				Some(Affiliation::Declared(party_id)) | Some(Affiliation::Confirmed(party_id)) => party_id,
+				None => return Err(Error::<T>::AffiliationNotFound.into()),
+			};
+			Self::ensure_signatory(party_id, &signatory)?;
+			<Affiliations<T>>::remove(election_id, candidate_id);
+
+			// Emit an event.
+			Self::deposit_event(Event::EndorsementWithdrawn{election_id, candidate_id, party_id});
+			// Return a successful DispatchResultWithPostInfo
+			Ok(())
+		}
+		/// Count an election as a constituency of a general election, itself identified
+		/// by an election id
+		#[pallet::call_index(4)]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(5, 1).ref_time())]
+		pub fn set_constituency(origin: OriginFor<T>, election_id: ElectionId, general_election: ElectionId) -> DispatchResult {
+			// Check if the sender is an approved origin or not
+			T::ElectionCommissionApproveOrigin::ensure_origin(origin)?;
+			ensure!(
+				T::ElectionInfo::election_exists(&election_id) && T::ElectionInfo::election_exists(&general_election),
+				Error::<T>::InvalidElectionId
+			);
+			ensure!(
+				!<ConstituencyRecorded<T>>::contains_key(election_id),
+				Error::<T>::ConstituencyAlreadyRecorded
+			);
+			<Constituencies<T>>::insert(election_id, general_election);
+
+			// Emit an event.
+			Self::deposit_event(Event::ConstituencySet{election_id, general_election});
+			// Return a successful DispatchResultWithPostInfo
+			Ok(())
+		}
+		/// Add the certified results of a constituency to the totals of each party with a
+		/// confirmed candidate there. Anyone may call this again whenever the results
+		/// change standing: a voided or recounting result is taken out of the totals, and
+		/// a result certified again after a recount or re-poll replaces the earlier one.
+		#[pallet::call_index(5)]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(6 + 5 * T::CandidateLookup::max_candidates() as u64, 2 + 4 * T::CandidateLookup::max_candidates() as u64).ref_time())]
+		pub fn record_constituency(origin: OriginFor<T>, election_id: ElectionId) -> DispatchResult {
+			let _ = ensure_signed(origin)?;
+			let general_election = <Constituencies<T>>::get(election_id).ok_or(Error::<T>::NotAConstituency)?;
+			let counted = T::ElectionResults::is_certified(&election_id);
+			let revision = (T::ElectionResults::certifications(&election_id), counted);
+			match <ConstituencyRecorded<T>>::get(election_id) {
+				Some(recorded) => ensure!(recorded != revision, Error::<T>::ConstituencyAlreadyRecorded),
+				None => ensure!(counted, Error::<T>::ResultsNotCertified),
+			}
+			// Take out what the constituency added before
+			for (party_id, previous) in <RecordedContributions<T>>::drain_prefix(election_id) {
+				<PartyResults<T>>::mutate(general_election, party_id, |result| {
+					result.votes = result.votes.saturating_sub(previous.votes);
+					result.seats = result.seats.saturating_sub(previous.seats);
+				});
+			}
+			if counted {
+				// Totals are read once rather than per candidate
+				let totals = T::ElectionResults::vote_totals(&election_id).unwrap_or_default();
+				let winners = T::ElectionResults::winners(&election_id);
+				for candidate_id in T::CandidateLookup::candidates(&election_id) {
+					let party_id = match <Affiliations<T>>::get(election_id, candidate_id) {
+						Some(Affiliation::Confirmed(party_id)) => party_id,
+						_ => continue,
+					};
+					let votes = totals.iter().find(|(c, _)| *c == candidate_id).map_or(0, |(_, n)| *n);
+					let seats = winners.contains(&candidate_id) as u32;
+					let add = |result: &mut PartyResult| -> DispatchResult {
+						result.votes = result.votes.checked_add(votes).ok_or(ArithmeticError::Overflow)?;
+						result.seats = result.seats.checked_add(seats).ok_or(ArithmeticError::Overflow)?;
+						Ok(())
+					};
+					<PartyResults<T>>::try_mutate(general_election, party_id, add)?;
+					<RecordedContributions<T>>::try_mutate(election_id, party_id, |contribution| {
+						add(contribution.get_or_insert_with(Default::default))
+					})?;
+				}
+			}
+			<ConstituencyRecorded<T>>::insert(election_id, revision);
+
+			// Emit an event.
+			Self::deposit_event(Event::ConstituencyRecorded{election_id, general_election, counted});
+			// Return a successful DispatchResultWithPostInfo
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Affiliations can change until voting opens, and not after the poll closes or the
+	/// results are certified
+	fn ensure_affiliations_open(election_id: ElectionId) -> DispatchResult {
+		ensure!(
+			!T::ElectionInfo::is_election_open_for_voting(&election_id) &&
+				!T::ElectionInfo::is_election_closed_for_voting(&election_id) &&
+				!T::ElectionResults::is_certified(&election_id),
+			Error::<T>::AffiliationsClosed
+		);
+		Ok(())
+	}
+
+	fn ensure_signatory(party_id: PartyId, account: &AccountIdOf<T>) -> DispatchResult {
+		let party = <Parties<T>>::get(party_id).ok_or(Error::<T>::PartyNotFound)?;
+		ensure!(party.signatory == *account, Error::<T>::NotPartySignatory);
+		Ok(())
+	}
+
+	pub fn party_id_inc() -> Result<PartyId, DispatchError> {
+		if PartyIdCounter::<T>::get().is_some() {
+			let party_id = PartyIdCounter::<T>::get()
+				.unwrap()
+				.checked_add(1)
+				.ok_or(ArithmeticError::Overflow)?;
+			PartyIdCounter::<T>::set(Option::from(party_id));
+			Ok(party_id)
+		} else {
+			PartyIdCounter::<T>::set(Some(1));
+			Ok(1)
+		}
+	}
+}
+
+impl<T: Config> CandidateWithdrawal for Pallet<T> {
+	fn withdrawn(election_id: &ElectionId, candidate_id: &CandidateId) {
+		<Affiliations<T>>::remove(election_id, candidate_id);
+	}
+}
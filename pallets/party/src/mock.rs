@@ -0,0 +1,148 @@
+use crate as pallet_party;
+use frame_support::{
+	parameter_types,
+	traits::{AsEnsureOriginWithArg, ConstU16, ConstU32, ConstU64},
+};
+use frame_system::EnsureRoot;
+use pallet_candidate::{CandidateAccounts, CandidateLookup, ElectionResults};
+use primitives::{CandidateId, ConstituencyId, ElectionId, RemovedCandidate, VoteCount};
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+	Perbill,
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system,
+		Election: pallet_election,
+		Party: pallet_party,
+	}
+);
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ConstU16<42>;
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_election::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type ElectionCommissionApproveOrigin = AsEnsureOriginWithArg<EnsureRoot<u64>>;
+	type CreateElectionOrigin = EnsureRoot<u64>;
+	type VotingWindowOrigin = EnsureRoot<u64>;
+}
+
+parameter_types! {
+	/// Accounts standing in every election, with their candidate ids
+	pub static Standing: Vec<(u64, CandidateId)> = vec![];
+	/// Votes counted for each candidate
+	pub static Totals: Vec<(CandidateId, VoteCount)> = vec![];
+	/// Candidates elected
+	pub static Winners: Vec<CandidateId> = vec![];
+	/// Whether the results of every election are certified
+	pub static Certified: bool = false;
+	/// Times the results of every election were certified
+	pub static Certifications: u32 = 0;
+}
+
+impl pallet_party::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type ElectionCommissionApproveOrigin = EnsureRoot<u64>;
+	type ElectionInfo = Election;
+	type CandidateLookup = MockCandidates;
+	type CandidateAccounts = MockCandidates;
+	type ElectionResults = MockResults;
+}
+
+/// Candidates as the candidate pallet would report them
+pub struct MockCandidates;
+
+impl CandidateLookup for MockCandidates {
+	fn is_candidate(_: &ElectionId, candidate_id: &CandidateId) -> bool {
+		Standing::get().iter().any(|(_, c)| c == candidate_id)
+	}
+	fn is_standing(election_id: &ElectionId, candidate_id: &CandidateId) -> bool {
+		Self::is_candidate(election_id, candidate_id)
+	}
+	fn candidates(_: &ElectionId) -> Vec<CandidateId> {
+		Standing::get().into_iter().map(|(_, c)| c).collect()
+	}
+	fn removals(_: &ElectionId) -> Vec<RemovedCandidate> {
+		vec![]
+	}
+	fn max_candidates() -> u32 {
+		8
+	}
+}
+
+impl CandidateAccounts<u64> for MockCandidates {
+	fn candidate_of(_: &ElectionId, account: &u64) -> Option<CandidateId> {
+		Standing::get().into_iter().find(|(a, _)| a == account).map(|(_, c)| c)
+	}
+}
+
+/// Results as the ballot pallet would report them
+pub struct MockResults;
+
+impl ElectionResults for MockResults {
+	fn is_certified(_: &ElectionId) -> bool {
+		Certified::get()
+	}
+	fn candidate_votes(_: &ElectionId, candidate_id: &CandidateId) -> Option<VoteCount> {
+		Totals::get().into_iter().find(|(c, _)| c == candidate_id).map(|(_, n)| n)
+	}
+	fn winners(_: &ElectionId) -> Vec<CandidateId> {
+		Winners::get()
+	}
+	fn vote_totals(_: &ElectionId) -> Option<Vec<(CandidateId, VoteCount)>> {
+		Some(Totals::get())
+	}
+	fn certifications(_: &ElectionId) -> u32 {
+		Certifications::get()
+	}
+	fn vote_share(_: &ElectionId, _: &CandidateId) -> Option<Perbill> {
+		None
+	}
+	fn stood_in(_: &ElectionId, _: &ConstituencyId, _: &CandidateId) -> bool {
+		true
+	}
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	let mut ext: sp_io::TestExternalities = t.into();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
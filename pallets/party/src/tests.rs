@@ -0,0 +1,132 @@
+use crate::{mock::*, Affiliation, CandidateWithdrawal, Error, Event, PartyResult};
+use frame_support::{assert_noop, assert_ok};
+use primitives::ElectionConfig;
+use sp_core::H256;
+use sp_runtime::DispatchError;
+
+/// Party 1, with account 9 as its signatory
+fn party() {
+	assert_ok!(Party::register_party(
+		RuntimeOrigin::root(),
+		b"Green Party".to_vec().try_into().unwrap(),
+		b"GP".to_vec().try_into().unwrap(),
+		H256::repeat_byte(1),
+		9
+	));
+}
+
+/// Elections 1 and 2, registered and not yet open for voting
+fn elections() {
+	for _ in 0..2 {
+		assert_ok!(Election::register_election_for_voting(RuntimeOrigin::root(), ElectionConfig { seats: 1, ..Default::default() }));
+	}
+}
+
+#[test]
+fn candidates_declare_and_signatories_confirm() {
+	new_test_ext().execute_with(|| {
+		elections();
+		party();
+		System::assert_last_event(Event::PartyRegistered { party_id: 1 }.into());
+		Standing::set(vec![(1, 1)]);
+		assert_noop!(Party::declare_affiliation(RuntimeOrigin::signed(2), 1, 1), Error::<Test>::NotACandidate);
+		assert_noop!(Party::declare_affiliation(RuntimeOrigin::signed(1), 1, 2), Error::<Test>::PartyNotFound);
+
+		assert_ok!(Party::declare_affiliation(RuntimeOrigin::signed(1), 1, 1));
+		System::assert_last_event(Event::AffiliationDeclared { election_id: 1, candidate_id: 1, party_id: 1 }.into());
+		assert_noop!(Party::confirm_affiliation(RuntimeOrigin::signed(1), 1, 1), Error::<Test>::NotPartySignatory);
+		assert_ok!(Party::confirm_affiliation(RuntimeOrigin::signed(9), 1, 1));
+		assert_eq!(Party::affiliation(1, 1), Some(Affiliation::Confirmed(1)));
+		assert_noop!(Party::confirm_affiliation(RuntimeOrigin::signed(9), 1, 1), Error::<Test>::AffiliationAlreadyConfirmed);
+
+		assert_ok!(Party::withdraw_endorsement(RuntimeOrigin::signed(9), 1, 1));
+		System::assert_last_event(Event::EndorsementWithdrawn { election_id: 1, candidate_id: 1, party_id: 1 }.into());
+		assert_eq!(Party::affiliation(1, 1), None);
+	});
+}
+
+#[test]
+fn affiliations_close_once_voting_opens() {
+	new_test_ext().execute_with(|| {
+		elections();
+		party();
+		Standing::set(vec![(1, 1), (2, 2)]);
+		assert_ok!(Party::declare_affiliation(RuntimeOrigin::signed(1), 1, 1));
+		assert_ok!(Election::election_open_for_voting(RuntimeOrigin::root(), 1));
+		assert_noop!(Party::declare_affiliation(RuntimeOrigin::signed(2), 1, 1), Error::<Test>::AffiliationsClosed);
+		assert_noop!(Party::withdraw_endorsement(RuntimeOrigin::signed(9), 1, 1), Error::<Test>::AffiliationsClosed);
+		// Confirming a declaration made in time is still possible
+		assert_ok!(Party::confirm_affiliation(RuntimeOrigin::signed(9), 1, 1));
+
+		assert_ok!(Election::election_close_for_voting(RuntimeOrigin::root(), 1));
+		assert_noop!(Party::withdraw_endorsement(RuntimeOrigin::signed(9), 1, 1), Error::<Test>::AffiliationsClosed);
+
+		// Certified results are closed as well
+		Certified::set(true);
+		assert_noop!(Party::declare_affiliation(RuntimeOrigin::signed(2), 2, 1), Error::<Test>::AffiliationsClosed);
+	});
+}
+
+#[test]
+fn constituencies_must_be_registered_elections() {
+	new_test_ext().execute_with(|| {
+		elections();
+		assert_noop!(Party::set_constituency(RuntimeOrigin::signed(1), 1, 2), DispatchError::BadOrigin);
+		assert_noop!(Party::set_constituency(RuntimeOrigin::root(), 3, 2), Error::<Test>::InvalidElectionId);
+		assert_noop!(Party::set_constituency(RuntimeOrigin::root(), 1, 3), Error::<Test>::InvalidElectionId);
+		assert_ok!(Party::set_constituency(RuntimeOrigin::root(), 1, 2));
+		System::assert_last_event(Event::ConstituencySet { election_id: 1, general_election: 2 }.into());
+		assert_eq!(Party::general_election(1), Some(2));
+	});
+}
+
+#[test]
+fn constituency_results_are_recorded_as_they_stand() {
+	new_test_ext().execute_with(|| {
+		elections();
+		party();
+		Standing::set(vec![(1, 1), (2, 2)]);
+		assert_ok!(Party::declare_affiliation(RuntimeOrigin::signed(1), 1, 1));
+		assert_ok!(Party::confirm_affiliation(RuntimeOrigin::signed(9), 1, 1));
+		// Declared but never confirmed, so not counted for the party
+		assert_ok!(Party::declare_affiliation(RuntimeOrigin::signed(2), 1, 1));
+		assert_noop!(Party::record_constituency(RuntimeOrigin::signed(3), 1), Error::<Test>::NotAConstituency);
+		assert_ok!(Party::set_constituency(RuntimeOrigin::root(), 1, 2));
+		assert_noop!(Party::record_constituency(RuntimeOrigin::signed(3), 1), Error::<Test>::ResultsNotCertified);
+
+		Totals::set(vec![(1, 30), (2, 20)]);
+		Winners::set(vec![1]);
+		Certified::set(true);
+		Certifications::set(1);
+		assert_ok!(Party::record_constituency(RuntimeOrigin::signed(3), 1));
+		System::assert_last_event(Event::ConstituencyRecorded { election_id: 1, general_election: 2, counted: true }.into());
+		assert_eq!(Party::party_result(2, 1), PartyResult { votes: 30, seats: 1 });
+		assert_noop!(Party::record_constituency(RuntimeOrigin::signed(3), 1), Error::<Test>::ConstituencyAlreadyRecorded);
+		assert_noop!(Party::set_constituency(RuntimeOrigin::root(), 1, 1), Error::<Test>::ConstituencyAlreadyRecorded);
+
+		// A result no longer standing is taken out
+		Certified::set(false);
+		assert_ok!(Party::record_constituency(RuntimeOrigin::signed(3), 1));
+		assert_eq!(Party::party_result(2, 1), PartyResult::default());
+
+		// A recount certified again replaces the earlier result
+		Totals::set(vec![(1, 25), (2, 35)]);
+		Winners::set(vec![2]);
+		Certified::set(true);
+		Certifications::set(2);
+		assert_ok!(Party::record_constituency(RuntimeOrigin::signed(3), 1));
+		assert_eq!(Party::party_result(2, 1), PartyResult { votes: 25, seats: 0 });
+	});
+}
+
+#[test]
+fn withdrawn_candidates_lose_their_affiliation() {
+	new_test_ext().execute_with(|| {
+		elections();
+		party();
+		Standing::set(vec![(1, 1)]);
+		assert_ok!(Party::declare_affiliation(RuntimeOrigin::signed(1), 1, 1));
+		<Party as CandidateWithdrawal>::withdrawn(&1, &1);
+		assert_eq!(Party::affiliation(1, 1), None);
+	});
+}
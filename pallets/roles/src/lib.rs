@@ -0,0 +1,214 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// Edit this file to define custom logic or remove it if it is not needed.
+/// Learn more about FRAME and the core library of Substrate FRAME pallets:
+/// <https://docs.substrate.io/reference/frame-pallets/>
+pub use pallet::*;
+use frame_support::pallet_prelude::*;
+use frame_system::pallet_prelude::*;
+use frame_support::traits::EnsureOriginWithArg;
+use primitives::RoleScope;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+pub type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
+
+/// The offices an account can be appointed to
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum Role {
+	/// Member of the election commission
+	Commission,
+	/// Returning officer, in charge of the nominations of an election or constituency
+	ReturningOfficer,
+	/// Registrar of the voters of a region or embassy
+	Registrar,
+	/// Independent observer
+	Observer,
+	/// Trustee holding a share of the election key
+	Trustee,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	/// Configure the pallet by specifying the parameters and types on which it depends.
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// Allowed origins for granting and revoking roles
+		type RoleAdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+	}
+
+	// The pallet's runtime storage items.
+	// https://docs.substrate.io/main-docs/build/runtime-storage/
+	#[pallet::storage]
+	#[pallet::getter(fn grants)]
+	// Roles held by each account, with the scope each one is held over
+	pub type Grants<T> = StorageDoubleMap<_, Blake2_128Concat, AccountIdOf<T>, Blake2_128Concat, (Role, RoleScope), bool, ValueQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// Roles held from genesis, such as the first returning officers
+		pub grants: Vec<(AccountIdOf<T>, Role, RoleScope)>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { grants: Vec::new() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			for (account, role, scope) in &self.grants {
+				<Grants<T>>::insert(account, (*role, *scope), true);
+			}
+		}
+	}
+
+	// Pallets use events to inform users when important changes are made.
+	// https://docs.substrate.io/main-docs/build/events-errors/
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Role granted to an account
+		/// parameters. [account, role, scope]
+		RoleGranted{account: AccountIdOf<T>, role: Role, scope: RoleScope},
+		/// Role revoked from an account
+		/// parameters. [account, role, scope]
+		RoleRevoked{account: AccountIdOf<T>, role: Role, scope: RoleScope},
+	}
+
+	// Errors inform users that something went wrong.
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Error: Role Already Granted.
+		RoleAlreadyGranted,
+		/// Error: Role Not Granted.
+		RoleNotGranted,
+	}
+
+	// Dispatchable functions allows users to interact with the pallet and invoke state changes.
+	// These functions materialize as "extrinsics", which are often compared to transactions.
+	// Dispatchable functions must be annotated with a weight and must return a DispatchResult.
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Grant a role to an account over a scope. A role held over an election extends
+		/// to each of its constituencies, and a global role to every scope.
+		#[pallet::call_index(0)]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1).ref_time())]
+		pub fn grant_role(origin: OriginFor<T>, account: AccountIdOf<T>, role: Role, scope: RoleScope) -> DispatchResult {
+			// Check if the sender is an approved origin or not
+			T::RoleAdminOrigin::ensure_origin(origin)?;
+			ensure!(
+				!<Grants<T>>::get(&account, (role, scope)),
+				Error::<T>::RoleAlreadyGranted
+			);
+			<Grants<T>>::insert(&account, (role, scope), true);
+
+			// Emit an event.
+			Self::deposit_event(Event::RoleGranted{account, role, scope});
+			// Return a successful DispatchResultWithPostInfo
+			Ok(())
+		}
+		/// Revoke a role granted over a scope. Grants over wider scopes are kept.
+		#[pallet::call_index(1)]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1).ref_time())]
+		pub fn revoke_role(origin: OriginFor<T>, account: AccountIdOf<T>, role: Role, scope: RoleScope) -> DispatchResult {
+			// Check if the sender is an approved origin or not
+			T::RoleAdminOrigin::ensure_origin(origin)?;
+			ensure!(
+				<Grants<T>>::get(&account, (role, scope)),
+				Error::<T>::RoleNotGranted
+			);
+			<Grants<T>>::remove(&account, (role, scope));
+
+			// Emit an event.
+			Self::deposit_event(Event::RoleRevoked{account, role, scope});
+			// Return a successful DispatchResultWithPostInfo
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Whether the account holds the role over the scope or over a scope enclosing it
+	pub fn has_role(account: &AccountIdOf<T>, role: Role, scope: &RoleScope) -> bool {
+		let mut scope = Some(*scope);
+		while let Some(current) = scope {
+			if <Grants<T>>::get(account, (role, current)) {
+				return true
+			}
+			scope = current.parent();
+		}
+		false
+	}
+}
+
+impl<T: Config> RoleControl<AccountIdOf<T>> for Pallet<T> {
+	fn has_role(account: &AccountIdOf<T>, role: Role, scope: &RoleScope) -> bool {
+		Self::has_role(account, role, scope)
+	}
+	fn grant_role(account: &AccountIdOf<T>, role: Role, scope: RoleScope) {
+		<Grants<T>>::insert(account, (role, scope), true);
+		Self::deposit_event(Event::RoleGranted{account: account.clone(), role, scope});
+	}
+	fn revoke_role(account: &AccountIdOf<T>, role: Role, scope: RoleScope) {
+		if <Grants<T>>::take(account, (role, scope)) {
+			Self::deposit_event(Event::RoleRevoked{account: account.clone(), role, scope});
+		}
+	}
+}
+
+/// Origin check for an official's role over the scope of a call. `Fallback` origins,
+/// such as a commission motion, pass without holding a role and succeed with `None`;
+/// otherwise a signed account holding the role `R` succeeds with `Some(account)`.
+pub struct EnsureRole<T, R, Fallback>(PhantomData<(T, R, Fallback)>);
+
+impl<T, R, Fallback> EnsureOriginWithArg<T::RuntimeOrigin, RoleScope> for EnsureRole<T, R, Fallback>
+where
+	T: Config,
+	R: Get<Role>,
+	Fallback: EnsureOrigin<T::RuntimeOrigin>,
+{
+	type Success = Option<AccountIdOf<T>>;
+
+	fn try_origin(o: T::RuntimeOrigin, scope: &RoleScope) -> Result<Self::Success, T::RuntimeOrigin> {
+		let o = match Fallback::try_origin(o) {
+			Ok(_) => return Ok(None),
+			Err(o) => o,
+		};
+		o.into().and_then(|o| match o {
+			frame_system::RawOrigin::Signed(who) if Pallet::<T>::has_role(&who, R::get(), scope) => Ok(Some(who)),
+			r => Err(T::RuntimeOrigin::from(r)),
+		})
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn try_successful_origin(_scope: &RoleScope) -> Result<T::RuntimeOrigin, ()> {
+		Fallback::try_successful_origin()
+	}
+}
+
+pub trait RoleControl<AccountId> {
+	/// Whether the account holds the role over the scope or over a scope enclosing it
+	fn has_role(account: &AccountId, role: Role, scope: &RoleScope) -> bool;
+	fn grant_role(account: &AccountId, role: Role, scope: RoleScope);
+	/// Revoke a role granted over exactly this scope
+	fn revoke_role(account: &AccountId, role: Role, scope: RoleScope);
+}
@@ -0,0 +1,102 @@
+use crate::{mock::*, Error, Event, Role, RoleControl};
+use frame_support::{assert_noop, assert_ok, traits::EnsureOriginWithArg};
+use primitives::RoleScope;
+use sp_runtime::DispatchError;
+
+#[test]
+fn grant_role_by_admin_only() {
+	new_test_ext().execute_with(|| {
+		// Go past genesis block so events get deposited
+		System::set_block_number(1);
+		assert_noop!(
+			Roles::grant_role(RuntimeOrigin::signed(1), 2, Role::Observer, RoleScope::Election(1)),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(Roles::grant_role(RuntimeOrigin::root(), 2, Role::Observer, RoleScope::Election(1)));
+		assert!(Roles::grants(2, (Role::Observer, RoleScope::Election(1))));
+		System::assert_last_event(Event::RoleGranted { account: 2, role: Role::Observer, scope: RoleScope::Election(1) }.into());
+		assert_noop!(
+			Roles::grant_role(RuntimeOrigin::root(), 2, Role::Observer, RoleScope::Election(1)),
+			Error::<Test>::RoleAlreadyGranted
+		);
+	});
+}
+
+#[test]
+fn election_role_extends_to_its_constituencies_only() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Roles::grant_role(RuntimeOrigin::root(), 2, Role::ReturningOfficer, RoleScope::Election(1)));
+		assert!(Roles::has_role(&2, Role::ReturningOfficer, &RoleScope::Election(1)));
+		assert!(Roles::has_role(&2, Role::ReturningOfficer, &RoleScope::Constituency(1, 7)));
+		assert!(!Roles::has_role(&2, Role::ReturningOfficer, &RoleScope::Election(2)));
+		assert!(!Roles::has_role(&2, Role::ReturningOfficer, &RoleScope::Constituency(2, 7)));
+		assert!(!Roles::has_role(&2, Role::ReturningOfficer, &RoleScope::Global));
+		// Another role over the same scope is not implied
+		assert!(!Roles::has_role(&2, Role::Registrar, &RoleScope::Election(1)));
+	});
+}
+
+#[test]
+fn constituency_role_does_not_extend_upwards() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Roles::grant_role(RuntimeOrigin::root(), 2, Role::Observer, RoleScope::Constituency(1, 7)));
+		assert!(Roles::has_role(&2, Role::Observer, &RoleScope::Constituency(1, 7)));
+		assert!(!Roles::has_role(&2, Role::Observer, &RoleScope::Constituency(1, 8)));
+		assert!(!Roles::has_role(&2, Role::Observer, &RoleScope::Election(1)));
+	});
+}
+
+#[test]
+fn global_role_extends_to_every_scope() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Roles::grant_role(RuntimeOrigin::root(), 2, Role::Registrar, RoleScope::Global));
+		assert!(Roles::has_role(&2, Role::Registrar, &RoleScope::Election(3)));
+		assert!(Roles::has_role(&2, Role::Registrar, &RoleScope::Constituency(3, 1)));
+		assert!(Roles::has_role(&2, Role::Registrar, &RoleScope::Region(5)));
+	});
+}
+
+#[test]
+fn revoke_role_keeps_wider_grants() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Roles::grant_role(RuntimeOrigin::root(), 2, Role::Observer, RoleScope::Election(1)));
+		assert_ok!(Roles::grant_role(RuntimeOrigin::root(), 2, Role::Observer, RoleScope::Constituency(1, 7)));
+		assert_ok!(Roles::revoke_role(RuntimeOrigin::root(), 2, Role::Observer, RoleScope::Constituency(1, 7)));
+		System::assert_last_event(Event::RoleRevoked { account: 2, role: Role::Observer, scope: RoleScope::Constituency(1, 7) }.into());
+		// Still held through the election
+		assert!(Roles::has_role(&2, Role::Observer, &RoleScope::Constituency(1, 7)));
+		assert_noop!(
+			Roles::revoke_role(RuntimeOrigin::root(), 2, Role::Observer, RoleScope::Constituency(1, 7)),
+			Error::<Test>::RoleNotGranted
+		);
+		assert_ok!(Roles::revoke_role(RuntimeOrigin::root(), 2, Role::Observer, RoleScope::Election(1)));
+		assert!(!Roles::has_role(&2, Role::Observer, &RoleScope::Constituency(1, 7)));
+	});
+}
+
+#[test]
+fn role_control_grants_and_revokes() {
+	new_test_ext().execute_with(|| {
+		<Roles as RoleControl<u64>>::grant_role(&2, Role::Trustee, RoleScope::Election(1));
+		assert!(<Roles as RoleControl<u64>>::has_role(&2, Role::Trustee, &RoleScope::Constituency(1, 2)));
+		<Roles as RoleControl<u64>>::revoke_role(&2, Role::Trustee, RoleScope::Election(1));
+		assert!(!<Roles as RoleControl<u64>>::has_role(&2, Role::Trustee, &RoleScope::Election(1)));
+	});
+}
+
+#[test]
+fn ensure_role_checks_the_role_over_the_scope() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Roles::grant_role(RuntimeOrigin::root(), 2, Role::Observer, RoleScope::Election(1)));
+		// Held over the election, so over its constituencies too
+		assert_eq!(EnsureObserver::try_origin(RuntimeOrigin::signed(2), &RoleScope::Constituency(1, 7)).ok(), Some(Some(2)));
+		assert!(EnsureObserver::try_origin(RuntimeOrigin::signed(2), &RoleScope::Election(2)).is_err());
+		// The wrong role does not pass
+		assert_ok!(Roles::grant_role(RuntimeOrigin::root(), 3, Role::Registrar, RoleScope::Global));
+		assert!(EnsureObserver::try_origin(RuntimeOrigin::signed(3), &RoleScope::Election(1)).is_err());
+		// The fallback origin passes without a role
+		assert_eq!(EnsureObserver::try_origin(RuntimeOrigin::root(), &RoleScope::Election(2)).ok(), Some(None));
+		assert!(EnsureObserver::try_origin(RuntimeOrigin::none(), &RoleScope::Election(1)).is_err());
+	});
+}
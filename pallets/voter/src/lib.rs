@@ -6,7 +6,12 @@
 pub use pallet::*;
 use frame_support::pallet_prelude::*;
 use frame_system::pallet_prelude::*;
-use primitives::ElectionId;
+use frame_support::traits::EnsureOriginWithArg;
+use frame_support::sp_runtime::ArithmeticError;
+use primitives::{
+	referendum::{QuestionIndex, MAX_QUESTIONS},
+	ConstituencyId, DelegationTopic, ElectionId, Participation, ParticipationCount, RegionId, RoleScope, VoteCount,
+};
 use pallet_election::ElectionInfo;
 
 #[cfg(test)]
@@ -34,14 +39,90 @@ pub mod pallet {
 		/// Because this pallet emits events, it depends on the runtime's definition of an event.
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 		type ElectionInfo: ElectionInfo;
+		/// Allowed origins for the registrar of the region a voter registered in
+		type RegistrarOrigin: EnsureOriginWithArg<Self::RuntimeOrigin, RoleScope>;
+		/// Longest chain of delegations followed from a delegator to the voter who casts
+		#[pallet::constant]
+		type MaxDelegationDepth: Get<u32>;
 	}
 
 	// The pallet's runtime storage items.
 	// https://docs.substrate.io/main-docs/build/runtime-storage/
 	#[pallet::storage]
 	#[pallet::getter(fn voters)]
-	// List of voters
-	pub type Voters<T> = StorageMap<_, Blake2_128Concat, ElectionId, AccountIdOf<T>>;
+	// List of voters with their voting weight
+	pub type Voters<T> = StorageDoubleMap<_, Blake2_128Concat, ElectionId, Blake2_128Concat, AccountIdOf<T>, VoteCount>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn voter_count)]
+	// Number of voters registered for each election
+	pub type VoterCount<T> = StorageMap<_, Blake2_128Concat, ElectionId, u32, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn total_weight)]
+	// Sum of the voting weights registered for each election
+	pub type TotalWeight<T> = StorageMap<_, Blake2_128Concat, ElectionId, VoteCount, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn delegation)]
+	// Delegate chosen by each delegating voter on each topic, with the delegation's position in `Delegators`
+	pub type Delegations<T> = StorageDoubleMap<_, Blake2_128Concat, ElectionId, Blake2_128Concat, (AccountIdOf<T>, DelegationTopic), (AccountIdOf<T>, u32)>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn delegator)]
+	// Delegating voters of each election and the topic they delegated, by position, so the
+	// tally can walk them in batches
+	pub type Delegators<T> = StorageDoubleMap<_, Blake2_128Concat, ElectionId, Blake2_128Concat, u32, (AccountIdOf<T>, DelegationTopic)>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn delegator_count)]
+	// Number of delegations of each election
+	pub type DelegatorCount<T> = StorageMap<_, Blake2_128Concat, ElectionId, u32, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn proxy_nomination)]
+	// Proxy nominated by each voter, waiting for the registrar's approval
+	pub type ProxyNominations<T> = StorageDoubleMap<_, Blake2_128Concat, ElectionId, Blake2_128Concat, AccountIdOf<T>, AccountIdOf<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn proxy)]
+	// Approved proxy of each voter
+	pub type Proxies<T> = StorageDoubleMap<_, Blake2_128Concat, ElectionId, Blake2_128Concat, AccountIdOf<T>, AccountIdOf<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn proxy_principal)]
+	// Voter each approved proxy casts for
+	pub type ProxyPrincipals<T> = StorageDoubleMap<_, Blake2_128Concat, ElectionId, Blake2_128Concat, AccountIdOf<T>, AccountIdOf<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn constituency_of)]
+	// Constituency or polling unit each voter votes in
+	pub type VoterConstituencies<T> = StorageDoubleMap<_, Blake2_128Concat, ElectionId, Blake2_128Concat, AccountIdOf<T>, ConstituencyId>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn region_of)]
+	// Registration region or embassy each voter registered in
+	pub type VoterRegions<T> = StorageDoubleMap<_, Blake2_128Concat, ElectionId, Blake2_128Concat, AccountIdOf<T>, RegionId>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn is_approved)]
+	// Voters whose registration their registrar has approved
+	pub type ApprovedVoters<T> = StorageDoubleMap<_, Blake2_128Concat, ElectionId, Blake2_128Concat, AccountIdOf<T>, bool, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn approved_count)]
+	// Number of approved voters of each election
+	pub type ApprovedCount<T> = StorageMap<_, Blake2_128Concat, ElectionId, u32, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn region_registrations)]
+	// Voters registered and approved in each region of an election
+	pub type RegionRegistrations<T> = StorageDoubleMap<_, Blake2_128Concat, ElectionId, Blake2_128Concat, RegionId, (u32, u32), ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn constituency_registrations)]
+	// Voters registered and approved in each constituency of an election
+	pub type ConstituencyRegistrations<T> = StorageDoubleMap<_, Blake2_128Concat, ElectionId, Blake2_128Concat, ConstituencyId, (u32, u32), ValueQuery>;
 
 	// Pallets use events to inform users when important changes are made.
 	// https://docs.substrate.io/main-docs/build/events-errors/
@@ -51,6 +132,30 @@ pub mod pallet {
 		/// Voter is successfully registered
 		/// parameters. []
 		VoterRegistered,
+		/// Registrar set the voting weight of a voter
+		/// parameters. [election_id, voter, weight]
+		VoterWeightSet{election_id: ElectionId, voter: AccountIdOf<T>, weight: VoteCount},
+		/// Voter delegated their vote on a topic
+		/// parameters. [election_id, delegator, topic, delegate]
+		VoteDelegated{election_id: ElectionId, delegator: AccountIdOf<T>, topic: DelegationTopic, delegate: AccountIdOf<T>},
+		/// Voter took their delegation on a topic back
+		/// parameters. [election_id, delegator, topic]
+		DelegationRevoked{election_id: ElectionId, delegator: AccountIdOf<T>, topic: DelegationTopic},
+		/// Voter nominated a proxy to cast their ballot
+		/// parameters. [election_id, voter, proxy]
+		ProxyNominated{election_id: ElectionId, voter: AccountIdOf<T>, proxy: AccountIdOf<T>},
+		/// Registrar approved a voter's proxy
+		/// parameters. [election_id, voter, proxy]
+		ProxyApproved{election_id: ElectionId, voter: AccountIdOf<T>, proxy: AccountIdOf<T>},
+		/// Voter revoked their proxy, nominated or approved
+		/// parameters. [election_id, voter, proxy]
+		ProxyRevoked{election_id: ElectionId, voter: AccountIdOf<T>, proxy: AccountIdOf<T>},
+		/// Registrar assigned a voter to a constituency
+		/// parameters. [election_id, voter, constituency]
+		ConstituencyAssigned{election_id: ElectionId, voter: AccountIdOf<T>, constituency: ConstituencyId},
+		/// Registrar approved a voter's registration
+		/// parameters. [election_id, voter]
+		VoterApproved{election_id: ElectionId, voter: AccountIdOf<T>},
 	}
 
 	// Errors inform users that something went wrong.
@@ -58,6 +163,36 @@ pub mod pallet {
 	pub enum Error<T> {
 		/// Error: Invalid Election Id Or Not Open For Registration.
 		InvalidElectionIdOrNotOpenForRegistration,
+		/// Error: Voter Already Registered For This Election.
+		VoterAlreadyRegistered,
+		/// Error: Voter Not Registered For This Election.
+		VoterNotRegistered,
+		/// Error: Voting Weight Must Be Greater Than Zero.
+		InvalidWeight,
+		/// Error: Voting Already Opened For This Election.
+		VotingAlreadyOpened,
+		/// Error: Voting Already Closed For This Election.
+		VotingAlreadyClosed,
+		/// Error: Voter Cannot Delegate To Themselves.
+		SelfDelegation,
+		/// Error: Delegation Would Form A Cycle.
+		DelegationCycle,
+		/// Error: Delegation Chain Too Long.
+		DelegationTooDeep,
+		/// Error: Voter Has Not Delegated Their Vote.
+		NotDelegated,
+		/// Error: Voter Cannot Be Their Own Proxy.
+		SelfProxy,
+		/// Error: Voter Already Has An Approved Proxy.
+		ProxyAlreadyApproved,
+		/// Error: Account Is Already Proxy For Another Voter.
+		ProxyAlreadyActing,
+		/// Error: No Proxy Nominated For This Voter.
+		NoProxyNominated,
+		/// Error: Voter Already Approved.
+		VoterAlreadyApproved,
+		/// Error: Election Has No Such Question.
+		InvalidTopic,
 	}
 
 	// Dispatchable functions allows users to interact with the pallet and invoke state changes.
@@ -65,22 +200,391 @@ pub mod pallet {
 	// Dispatchable functions must be annotated with a weight and must return a DispatchResult.
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		/// Self register by voter
+		/// Self register by voter, in the registration region or embassy whose registrar
+		/// looks after their registration
 		#[pallet::call_index(0)]
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1).ref_time())]
-		pub fn register_voter(origin: OriginFor<T>, election_id: ElectionId, key: String, value: String) -> DispatchResult {
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(5, 5).ref_time())]
+		pub fn register_voter(origin: OriginFor<T>, election_id: ElectionId, region: RegionId, key: String, value: String) -> DispatchResult {
 			let voter_account = ensure_signed(origin)?;
 			ensure!(
-				!&T::ElectionInfo::is_election_open_for_voter_registration(&election_id),
+				T::ElectionInfo::is_election_open_for_voter_registration(&election_id),
 				Error::<T>::InvalidElectionIdOrNotOpenForRegistration
 			);
+			ensure!(
+				!<Voters<T>>::contains_key(election_id, &voter_account),
+				Error::<T>::VoterAlreadyRegistered
+			);
 			// Update storage for voter list for particular election id
-			<Voters<T>>::insert(election_id, voter_account);
+			<Voters<T>>::insert(election_id, &voter_account, 1);
+			<VoterRegions<T>>::insert(election_id, voter_account, region);
+			<VoterCount<T>>::mutate(election_id, |count| *count = count.saturating_add(1));
+			<RegionRegistrations<T>>::mutate(election_id, region, |(registered, _)| *registered = registered.saturating_add(1));
+			<TotalWeight<T>>::mutate(election_id, |total| *total = total.saturating_add(1));
 
 			// Emit an event.
 			Self::deposit_event(Event::VoterRegistered);
 			// Return a successful DispatchResultWithPostInfo
 			Ok(())
 		}
+		/// Set the voting weight of a registered voter, e.g. their shareholding.
+		/// Weights are fixed once voting opens.
+		#[pallet::call_index(1)]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(5, 2).ref_time())]
+		pub fn set_voter_weight(origin: OriginFor<T>, election_id: ElectionId, voter: AccountIdOf<T>, weight: VoteCount) -> DispatchResult {
+			// Check if the sender is an approved origin or not
+			Self::ensure_registrar(origin, election_id, &voter)?;
+			ensure!(weight > 0, Error::<T>::InvalidWeight);
+			Self::ensure_voting_not_opened(election_id)?;
+			let previous = <Voters<T>>::get(election_id, &voter).ok_or(Error::<T>::VoterNotRegistered)?;
+			let total = <TotalWeight<T>>::get(election_id)
+				.saturating_sub(previous)
+				.checked_add(weight)
+				.ok_or(ArithmeticError::Overflow)?;
+			// Update storage for voter list for particular election id
+			<Voters<T>>::insert(election_id, &voter, weight);
+			<TotalWeight<T>>::insert(election_id, total);
+
+			// Emit an event.
+			Self::deposit_event(Event::VoterWeightSet{election_id, voter, weight});
+			// Return a successful DispatchResultWithPostInfo
+			Ok(())
+		}
+		/// Delegate the vote on a topic to another registered voter, replacing any earlier
+		/// delegation on that topic. The delegate's ballot carries the delegator's weight
+		/// unless the delegator casts their own ballot. A question delegated on its own is
+		/// taken out of the delegation of the whole ballot, and a chain of delegates on a
+		/// question follows each delegate's delegation of the whole ballot where they did
+		/// not delegate the question. Delegations can be changed until voting closes.
+		#[pallet::call_index(2)]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(6 + MAX_QUESTIONS as u64 + (1 + 2 * MAX_QUESTIONS as u64) * T::MaxDelegationDepth::get() as u64, 3).ref_time())]
+		pub fn delegate_vote(origin: OriginFor<T>, election_id: ElectionId, topic: DelegationTopic, delegate: AccountIdOf<T>) -> DispatchResult {
+			let delegator = ensure_signed(origin)?;
+			ensure!(
+				!T::ElectionInfo::is_election_closed_for_voting(&election_id),
+				Error::<T>::VotingAlreadyClosed
+			);
+			ensure!(<Voters<T>>::contains_key(election_id, &delegator), Error::<T>::VoterNotRegistered);
+			ensure!(<Voters<T>>::contains_key(election_id, &delegate), Error::<T>::VoterNotRegistered);
+			ensure!(delegator != delegate, Error::<T>::SelfDelegation);
+			if let DelegationTopic::Question(question) = topic {
+				ensure!(
+					(question as usize) < T::ElectionInfo::election_questions(&election_id).len(),
+					Error::<T>::InvalidTopic
+				);
+			}
+			Self::check_chain(election_id, &delegator, &delegate, topic)?;
+
+			// Update storage for delegation list for particular election id
+			let key = (delegator.clone(), topic);
+			let index = match <Delegations<T>>::get(election_id, &key) {
+				Some((_, index)) => index,
+				None => {
+					let index = <DelegatorCount<T>>::get(election_id);
+					<DelegatorCount<T>>::insert(election_id, index.checked_add(1).ok_or(ArithmeticError::Overflow)?);
+					<Delegators<T>>::insert(election_id, index, &key);
+					index
+				},
+			};
+			<Delegations<T>>::insert(election_id, &key, (&delegate, index));
+
+			// Emit an event.
+			Self::deposit_event(Event::VoteDelegated{election_id, delegator, topic, delegate});
+			// Return a successful DispatchResultWithPostInfo
+			Ok(())
+		}
+		/// Take back a delegation on a topic until voting closes
+		#[pallet::call_index(3)]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4, 4).ref_time())]
+		pub fn revoke_delegation(origin: OriginFor<T>, election_id: ElectionId, topic: DelegationTopic) -> DispatchResult {
+			let delegator = ensure_signed(origin)?;
+			ensure!(
+				!T::ElectionInfo::is_election_closed_for_voting(&election_id),
+				Error::<T>::VotingAlreadyClosed
+			);
+			let (_, index) = <Delegations<T>>::take(election_id, (&delegator, topic)).ok_or(Error::<T>::NotDelegated)?;
+			// Move the last delegator into the freed position
+			let last = <DelegatorCount<T>>::get(election_id).saturating_sub(1);
+			if let Some(moved) = <Delegators<T>>::take(election_id, last) {
+				if index != last {
+					<Delegations<T>>::mutate(election_id, &moved, |d| {
+						if let Some((_, i)) = d {
+							*i = index;
+						}
+					});
+					<Delegators<T>>::insert(election_id, index, moved);
+				}
+			}
+			<DelegatorCount<T>>::insert(election_id, last);
+
+			// Emit an event.
+			Self::deposit_event(Event::DelegationRevoked{election_id, delegator, topic});
+			// Return a successful DispatchResultWithPostInfo
+			Ok(())
+		}
+		/// Nominate a proxy to cast the ballot on the voter's behalf, e.g. a classified
+		/// service voter. The proxy acts once the registrar approves the nomination.
+		#[pallet::call_index(4)]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4, 1).ref_time())]
+		pub fn nominate_proxy(origin: OriginFor<T>, election_id: ElectionId, proxy: AccountIdOf<T>) -> DispatchResult {
+			let voter = ensure_signed(origin)?;
+			Self::ensure_voting_not_opened(election_id)?;
+			ensure!(<Voters<T>>::contains_key(election_id, &voter), Error::<T>::VoterNotRegistered);
+			ensure!(voter != proxy, Error::<T>::SelfProxy);
+			ensure!(
+				!<Proxies<T>>::contains_key(election_id, &voter),
+				Error::<T>::ProxyAlreadyApproved
+			);
+			// Update storage for proxy nominations for particular election id
+			<ProxyNominations<T>>::insert(election_id, &voter, &proxy);
+
+			// Emit an event.
+			Self::deposit_event(Event::ProxyNominated{election_id, voter, proxy});
+			// Return a successful DispatchResultWithPostInfo
+			Ok(())
+		}
+		/// Approve the proxy a voter nominated. An account can be proxy for one voter.
+		#[pallet::call_index(5)]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(5, 3).ref_time())]
+		pub fn approve_proxy(origin: OriginFor<T>, election_id: ElectionId, voter: AccountIdOf<T>) -> DispatchResult {
+			// Check if the sender is an approved origin or not
+			Self::ensure_registrar(origin, election_id, &voter)?;
+			Self::ensure_voting_not_opened(election_id)?;
+			let proxy = <ProxyNominations<T>>::get(election_id, &voter).ok_or(Error::<T>::NoProxyNominated)?;
+			ensure!(
+				!<ProxyPrincipals<T>>::contains_key(election_id, &proxy),
+				Error::<T>::ProxyAlreadyActing
+			);
+			// Update storage for proxy list for particular election id
+			<ProxyNominations<T>>::remove(election_id, &voter);
+			<Proxies<T>>::insert(election_id, &voter, &proxy);
+			<ProxyPrincipals<T>>::insert(election_id, &proxy, &voter);
+
+			// Emit an event.
+			Self::deposit_event(Event::ProxyApproved{election_id, voter, proxy});
+			// Return a successful DispatchResultWithPostInfo
+			Ok(())
+		}
+		/// Revoke the voter's proxy, whether still nominated or already approved, until
+		/// voting opens
+		#[pallet::call_index(6)]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4, 3).ref_time())]
+		pub fn revoke_proxy(origin: OriginFor<T>, election_id: ElectionId) -> DispatchResult {
+			let voter = ensure_signed(origin)?;
+			Self::ensure_voting_not_opened(election_id)?;
+			let proxy = match <Proxies<T>>::take(election_id, &voter) {
+				Some(proxy) => {
+					<ProxyPrincipals<T>>::remove(election_id, &proxy);
+					proxy
+				},
+				None => <ProxyNominations<T>>::take(election_id, &voter).ok_or(Error::<T>::NoProxyNominated)?,
+			};
+
+			// Emit an event.
+			Self::deposit_event(Event::ProxyRevoked{election_id, voter, proxy});
+			// Return a successful DispatchResultWithPostInfo
+			Ok(())
+		}
+		/// Assign a registered voter to the constituency or polling unit they vote in,
+		/// until voting opens
+		#[pallet::call_index(7)]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(5, 3).ref_time())]
+		pub fn assign_constituency(origin: OriginFor<T>, election_id: ElectionId, voter: AccountIdOf<T>, constituency: ConstituencyId) -> DispatchResult {
+			// Check if the sender is an approved origin or not
+			Self::ensure_registrar(origin, election_id, &voter)?;
+			Self::ensure_voting_not_opened(election_id)?;
+			// Move the voter's registration to the constituency's count
+			let approved = <ApprovedVoters<T>>::get(election_id, &voter) as u32;
+			if let Some(previous) = <VoterConstituencies<T>>::get(election_id, &voter) {
+				<ConstituencyRegistrations<T>>::mutate(election_id, previous, |(registered, approvals)| {
+					*registered = registered.saturating_sub(1);
+					*approvals = approvals.saturating_sub(approved);
+				});
+			}
+			<ConstituencyRegistrations<T>>::mutate(election_id, constituency, |(registered, approvals)| {
+				*registered = registered.saturating_add(1);
+				*approvals = approvals.saturating_add(approved);
+			});
+			<VoterConstituencies<T>>::insert(election_id, &voter, constituency);
+
+			// Emit an event.
+			Self::deposit_event(Event::ConstituencyAssigned{election_id, voter, constituency});
+			// Return a successful DispatchResultWithPostInfo
+			Ok(())
+		}
+		/// Approve a voter's registration once the registrar of their region has checked
+		/// it, until voting opens. Only approved voters can cast a ballot or have their vote
+		/// delegated, and approvals are counted in the participation statistics.
+		#[pallet::call_index(8)]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(5, 4).ref_time())]
+		pub fn approve_voter(origin: OriginFor<T>, election_id: ElectionId, voter: AccountIdOf<T>) -> DispatchResult {
+			// Check if the sender is an approved origin or not
+			Self::ensure_registrar(origin, election_id, &voter)?;
+			Self::ensure_voting_not_opened(election_id)?;
+			ensure!(
+				!<ApprovedVoters<T>>::get(election_id, &voter),
+				Error::<T>::VoterAlreadyApproved
+			);
+			<ApprovedVoters<T>>::insert(election_id, &voter, true);
+			<ApprovedCount<T>>::mutate(election_id, |count| *count = count.saturating_add(1));
+			if let Some(region) = <VoterRegions<T>>::get(election_id, &voter) {
+				<RegionRegistrations<T>>::mutate(election_id, region, |(_, approved)| *approved = approved.saturating_add(1));
+			}
+			if let Some(constituency) = <VoterConstituencies<T>>::get(election_id, &voter) {
+				<ConstituencyRegistrations<T>>::mutate(election_id, constituency, |(_, approved)| *approved = approved.saturating_add(1));
+			}
+
+			// Emit an event.
+			Self::deposit_event(Event::VoterApproved{election_id, voter});
+			// Return a successful DispatchResultWithPostInfo
+			Ok(())
+		}
 	}
 }
+
+impl<T: Config> Pallet<T> {
+	/// Check the origin is a registrar for the region the voter registered in
+	fn ensure_registrar(origin: OriginFor<T>, election_id: ElectionId, voter: &AccountIdOf<T>) -> DispatchResult {
+		let region = <VoterRegions<T>>::get(election_id, voter).ok_or(Error::<T>::VoterNotRegistered)?;
+		T::RegistrarOrigin::ensure_origin(origin, &RoleScope::Region(region))?;
+		Ok(())
+	}
+
+	fn ensure_voting_not_opened(election_id: ElectionId) -> DispatchResult {
+		ensure!(
+			!T::ElectionInfo::is_election_open_for_voting(&election_id) &&
+				!T::ElectionInfo::is_election_closed_for_voting(&election_id),
+			Error::<T>::VotingAlreadyOpened
+		);
+		Ok(())
+	}
+
+	/// Follow the delegations on the topic from `delegate` and reject the new delegation
+	/// when the chain leads back to `delegator` or runs longer than `MaxDelegationDepth`.
+	/// A delegation of the whole ballot also carries every question the delegator has
+	/// not delegated on its own, so the chain of each of those questions is followed too.
+	fn check_chain(election_id: ElectionId, delegator: &AccountIdOf<T>, delegate: &AccountIdOf<T>, topic: DelegationTopic) -> DispatchResult {
+		Self::check_topic_chain(election_id, delegator, delegate, topic)?;
+		if topic == DelegationTopic::All {
+			let questions = T::ElectionInfo::election_questions(&election_id).len() as QuestionIndex;
+			for question in 0..questions {
+				let topic = DelegationTopic::Question(question);
+				if !<Delegations<T>>::contains_key(election_id, (delegator, topic)) {
+					Self::check_topic_chain(election_id, delegator, delegate, topic)?;
+				}
+			}
+		}
+		Ok(())
+	}
+
+	/// Follow the chain of delegates on one topic from `delegate`
+	fn check_topic_chain(election_id: ElectionId, delegator: &AccountIdOf<T>, delegate: &AccountIdOf<T>, topic: DelegationTopic) -> DispatchResult {
+		let mut current = delegate.clone();
+		for _ in 0..T::MaxDelegationDepth::get() {
+			ensure!(current != *delegator, Error::<T>::DelegationCycle);
+			match Self::next_delegate(election_id, &current, topic) {
+				Some(next) => current = next,
+				None => return Ok(()),
+			}
+		}
+		Err(Error::<T>::DelegationTooDeep.into())
+	}
+
+	/// Delegate a voter passes a topic on to: their delegate on the question, or else
+	/// on the whole ballot
+	fn next_delegate(election_id: ElectionId, account: &AccountIdOf<T>, topic: DelegationTopic) -> Option<AccountIdOf<T>> {
+		<Delegations<T>>::get(election_id, (account, topic))
+			.or_else(|| match topic {
+				DelegationTopic::Question(_) => <Delegations<T>>::get(election_id, (account, DelegationTopic::All)),
+				DelegationTopic::All => None,
+			})
+			.map(|(delegate, _)| delegate)
+	}
+}
+
+impl<T: Config> VoterInfo<AccountIdOf<T>> for Pallet<T> {
+	fn is_voter(election_id: &ElectionId, account: &AccountIdOf<T>) -> bool {
+		<Voters<T>>::contains_key(election_id, account)
+	}
+	fn voter_weight(election_id: &ElectionId, account: &AccountIdOf<T>) -> Option<VoteCount> {
+		<Voters<T>>::get(election_id, account)
+	}
+	fn is_approved(election_id: &ElectionId, account: &AccountIdOf<T>) -> bool {
+		<ApprovedVoters<T>>::get(election_id, account)
+	}
+	fn voter_count(election_id: &ElectionId) -> u32 {
+		<VoterCount<T>>::get(election_id)
+	}
+	fn total_weight(election_id: &ElectionId) -> VoteCount {
+		<TotalWeight<T>>::get(election_id)
+	}
+	fn delegator_count(election_id: &ElectionId) -> u32 {
+		<DelegatorCount<T>>::get(election_id)
+	}
+	fn delegator(election_id: &ElectionId, index: u32) -> Option<(AccountIdOf<T>, DelegationTopic)> {
+		<Delegators<T>>::get(election_id, index)
+	}
+	fn max_delegation_depth() -> u32 {
+		T::MaxDelegationDepth::get()
+	}
+	fn proxy_principal(election_id: &ElectionId, proxy: &AccountIdOf<T>) -> Option<AccountIdOf<T>> {
+		<ProxyPrincipals<T>>::get(election_id, proxy)
+	}
+	fn constituency_of(election_id: &ElectionId, account: &AccountIdOf<T>) -> Option<ConstituencyId> {
+		<VoterConstituencies<T>>::get(election_id, account)
+	}
+	fn region_of(election_id: &ElectionId, account: &AccountIdOf<T>) -> Option<RegionId> {
+		<VoterRegions<T>>::get(election_id, account)
+	}
+	fn registrations(election_id: &ElectionId) -> Participation {
+		let count = |(registered, approved): (u32, u32)| ParticipationCount{registered, approved, voted: 0};
+		let mut regions: Vec<_> = <RegionRegistrations<T>>::iter_prefix(election_id)
+			.map(|(region, counts)| (region, count(counts)))
+			.collect();
+		regions.sort_by_key(|(region, _)| *region);
+		let mut constituencies: Vec<_> = <ConstituencyRegistrations<T>>::iter_prefix(election_id)
+			.map(|(constituency, counts)| (constituency, count(counts)))
+			.collect();
+		constituencies.sort_by_key(|(constituency, _)| *constituency);
+		Participation{
+			total: count((<VoterCount<T>>::get(election_id), <ApprovedCount<T>>::get(election_id))),
+			regions,
+			constituencies,
+		}
+	}
+	fn resolve_delegate<F: Fn(&AccountIdOf<T>) -> bool>(election_id: &ElectionId, delegator: &AccountIdOf<T>, topic: DelegationTopic, has_voted: F) -> Option<AccountIdOf<T>> {
+		let mut current = <Delegations<T>>::get(election_id, (delegator, topic))?.0;
+		for _ in 0..T::MaxDelegationDepth::get() {
+			if has_voted(&current) {
+				return Some(current)
+			}
+			current = Self::next_delegate(*election_id, &current, topic)?;
+		}
+		None
+	}
+}
+
+pub trait VoterInfo<AccountId> {
+	fn is_voter(election_id: &ElectionId, account: &AccountId) -> bool;
+	fn voter_weight(election_id: &ElectionId, account: &AccountId) -> Option<VoteCount>;
+	/// Registration checked and approved by a registrar; only approved voters may vote
+	fn is_approved(election_id: &ElectionId, account: &AccountId) -> bool;
+	fn voter_count(election_id: &ElectionId) -> u32;
+	fn total_weight(election_id: &ElectionId) -> VoteCount;
+	fn delegator_count(election_id: &ElectionId) -> u32;
+	/// Delegating voter at a position and the topic of their delegation
+	fn delegator(election_id: &ElectionId, index: u32) -> Option<(AccountId, DelegationTopic)>;
+	fn max_delegation_depth() -> u32;
+	/// Voter an approved proxy casts for
+	fn proxy_principal(election_id: &ElectionId, proxy: &AccountId) -> Option<AccountId>;
+	fn constituency_of(election_id: &ElectionId, account: &AccountId) -> Option<ConstituencyId>;
+	/// Registration region or embassy the voter registered in
+	fn region_of(election_id: &ElectionId, account: &AccountId) -> Option<RegionId>;
+	/// Voters registered and approved, overall and by region and constituency. The
+	/// voted counts are left at zero for the ballot pallet to fill in.
+	fn registrations(election_id: &ElectionId) -> Participation;
+	/// First voter who cast a ballot along the delegator's chain of delegates on the
+	/// topic, within `MaxDelegationDepth` hops. `None` when the delegator did not
+	/// delegate the topic or the chain ends without one, in which case the delegated
+	/// vote lapses.
+	fn resolve_delegate<F: Fn(&AccountId) -> bool>(election_id: &ElectionId, delegator: &AccountId, topic: DelegationTopic, has_voted: F) -> Option<AccountId>;
+}
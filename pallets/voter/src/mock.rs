@@ -1,5 +1,7 @@
-use crate as pallet_template;
-use frame_support::traits::{ConstU16, ConstU64};
+use crate as pallet_voter;
+use frame_support::traits::{AsEnsureOriginWithArg, ConstU16, ConstU32, ConstU64, EnsureOriginWithArg};
+use frame_system::{EnsureRoot, RawOrigin};
+use primitives::{RegionId, RoleScope};
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
@@ -17,7 +19,8 @@ frame_support::construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system,
-		TemplateModule: pallet_template,
+		Election: pallet_election,
+		Voter: pallet_voter,
 	}
 );
 
@@ -45,14 +48,56 @@ impl frame_system::Config for Test {
 	type SystemWeightInfo = ();
 	type SS58Prefix = ConstU16<42>;
 	type OnSetCode = ();
-	type MaxConsumers = frame_support::traits::ConstU32<16>;
+	type MaxConsumers = ConstU32<16>;
 }
 
-impl pallet_template::Config for Test {
+impl pallet_election::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
+	type ElectionCommissionApproveOrigin = AsEnsureOriginWithArg<EnsureRoot<u64>>;
+	type CreateElectionOrigin = EnsureRoot<u64>;
+	type VotingWindowOrigin = EnsureRoot<u64>;
+}
+
+pub const MAX_DEPTH: u32 = 3;
+
+impl pallet_voter::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type ElectionInfo = Election;
+	type RegistrarOrigin = EnsureRegistrar;
+	type MaxDelegationDepth = ConstU32<MAX_DEPTH>;
+}
+
+/// Account of the registrar of a region
+pub fn registrar(region: RegionId) -> u64 {
+	100 + region as u64
+}
+
+/// Registrars of one region each, as the roles pallet grants them
+pub struct EnsureRegistrar;
+
+impl EnsureOriginWithArg<RuntimeOrigin, RoleScope> for EnsureRegistrar {
+	type Success = ();
+
+	fn try_origin(o: RuntimeOrigin, scope: &RoleScope) -> Result<(), RuntimeOrigin> {
+		let raw: Result<RawOrigin<u64>, RuntimeOrigin> = o.clone().into();
+		match (raw, scope) {
+			(Ok(RawOrigin::Signed(who)), RoleScope::Region(region)) if who == registrar(*region) => Ok(()),
+			_ => Err(o),
+		}
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn try_successful_origin(scope: &RoleScope) -> Result<RuntimeOrigin, ()> {
+		match scope {
+			RoleScope::Region(region) => Ok(RuntimeOrigin::signed(registrar(*region))),
+			_ => Err(()),
+		}
+	}
 }
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	frame_system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+	let mut ext: sp_io::TestExternalities = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap().into();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
 }
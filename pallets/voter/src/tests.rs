@@ -1,27 +1,221 @@
-use crate::{mock::*, Error, Event};
+use crate::{mock::*, Error, Event, VoterInfo};
 use frame_support::{assert_noop, assert_ok};
+use pallet_election::ElectionOpenForVoterRegistration;
+use primitives::{
+	referendum::{Question, Questions},
+	CountingMethod, DelegationTopic, ElectionConfig, ParticipationCount, RegionId,
+};
+use sp_runtime::{DispatchError, Perbill};
+
+fn question() -> Question {
+	Question {
+		text: b"Adopt the amendment?".to_vec().try_into().unwrap(),
+		options: vec![b"Yes".to_vec().try_into().unwrap(), b"No".to_vec().try_into().unwrap()].try_into().unwrap(),
+		min_turnout: Perbill::zero(),
+		approval: Perbill::from_percent(50),
+	}
+}
+
+/// Referendum 1 with the given number of questions, registered for voting and open
+/// for voter registration. The election pallet issues ids for the two apart, so the
+/// registration window is opened on the same id directly.
+fn election(questions: usize) {
+	let referendum = ElectionConfig { counting_method: CountingMethod::Referendum, ..Default::default() };
+	assert_ok!(Election::register_election_for_voting(RuntimeOrigin::root(), referendum));
+	let questions: Questions = vec![question(); questions].try_into().unwrap();
+	assert_ok!(Election::set_election_questions(RuntimeOrigin::root(), 1, questions));
+	ElectionOpenForVoterRegistration::<Test>::insert(1, true);
+}
+
+fn register(voters: &[u64], region: RegionId) {
+	for voter in voters {
+		assert_ok!(Voter::register_voter(RuntimeOrigin::signed(*voter), 1, region, String::new(), String::new()));
+	}
+}
+
+#[test]
+fn register_voter_counts_registrations() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Voter::register_voter(RuntimeOrigin::signed(1), 1, 7, String::new(), String::new()),
+			Error::<Test>::InvalidElectionIdOrNotOpenForRegistration
+		);
+		election(0);
+		register(&[1], 7);
+		System::assert_last_event(Event::VoterRegistered.into());
+		assert_eq!(Voter::voters(1, 1), Some(1));
+		assert_eq!(Voter::region_of(1, 1), Some(7));
+		assert_eq!(Voter::voter_count(1), 1);
+		assert_eq!(Voter::total_weight(1), 1);
+		assert_eq!(Voter::region_registrations(1, 7), (1, 0));
+		assert_noop!(
+			Voter::register_voter(RuntimeOrigin::signed(1), 1, 7, String::new(), String::new()),
+			Error::<Test>::VoterAlreadyRegistered
+		);
+	});
+}
 
 #[test]
-fn it_works_for_default_value() {
+fn approve_voter_by_the_registrar_of_their_region() {
 	new_test_ext().execute_with(|| {
-		// Go past genesis block so events get deposited
-		System::set_block_number(1);
-		// Dispatch a signed extrinsic.
-		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(1), 42));
-		// Read pallet storage and assert an expected result.
-		assert_eq!(TemplateModule::something(), Some(42));
-		// Assert that the correct event was deposited
-		System::assert_last_event(Event::SomethingStored { something: 42, who: 1 }.into());
+		election(0);
+		register(&[1, 2], 7);
+		assert_noop!(Voter::approve_voter(RuntimeOrigin::signed(registrar(8)), 1, 1), DispatchError::BadOrigin);
+		assert_noop!(Voter::approve_voter(RuntimeOrigin::signed(registrar(7)), 1, 3), Error::<Test>::VoterNotRegistered);
+		assert_ok!(Voter::assign_constituency(RuntimeOrigin::signed(registrar(7)), 1, 1, 4));
+
+		assert_ok!(Voter::approve_voter(RuntimeOrigin::signed(registrar(7)), 1, 1));
+		System::assert_last_event(Event::VoterApproved { election_id: 1, voter: 1 }.into());
+		assert!(<Voter as VoterInfo<u64>>::is_approved(&1, &1));
+		assert!(!<Voter as VoterInfo<u64>>::is_approved(&1, &2));
+		assert_eq!(Voter::approved_count(1), 1);
+		assert_eq!(Voter::region_registrations(1, 7), (2, 1));
+		assert_eq!(Voter::constituency_registrations(1, 4), (1, 1));
+		assert_noop!(Voter::approve_voter(RuntimeOrigin::signed(registrar(7)), 1, 1), Error::<Test>::VoterAlreadyApproved);
+
+		// The approval moves with the voter to another constituency
+		assert_ok!(Voter::assign_constituency(RuntimeOrigin::signed(registrar(7)), 1, 1, 5));
+		System::assert_last_event(Event::ConstituencyAssigned { election_id: 1, voter: 1, constituency: 5 }.into());
+		assert_eq!(Voter::constituency_registrations(1, 4), (0, 0));
+		assert_eq!(Voter::constituency_registrations(1, 5), (1, 1));
+		let participation = <Voter as VoterInfo<u64>>::registrations(&1);
+		assert_eq!(participation.total, ParticipationCount { registered: 2, approved: 1, voted: 0 });
+		assert_eq!(participation.regions, vec![(7, ParticipationCount { registered: 2, approved: 1, voted: 0 })]);
+
+		assert_ok!(Election::election_open_for_voting(RuntimeOrigin::root(), 1));
+		assert_noop!(Voter::approve_voter(RuntimeOrigin::signed(registrar(7)), 1, 2), Error::<Test>::VotingAlreadyOpened);
 	});
 }
 
 #[test]
-fn correct_error_for_none_value() {
+fn set_voter_weight_updates_the_total() {
 	new_test_ext().execute_with(|| {
-		// Ensure the expected error is thrown when no value is present.
+		election(0);
+		register(&[1, 2], 7);
+		assert_noop!(Voter::set_voter_weight(RuntimeOrigin::signed(1), 1, 1, 10), DispatchError::BadOrigin);
+		assert_noop!(Voter::set_voter_weight(RuntimeOrigin::signed(registrar(7)), 1, 1, 0), Error::<Test>::InvalidWeight);
+		assert_ok!(Voter::set_voter_weight(RuntimeOrigin::signed(registrar(7)), 1, 1, 10));
+		System::assert_last_event(Event::VoterWeightSet { election_id: 1, voter: 1, weight: 10 }.into());
+		assert_eq!(Voter::total_weight(1), 11);
+		assert_ok!(Voter::set_voter_weight(RuntimeOrigin::signed(registrar(7)), 1, 1, 5));
+		assert_eq!(Voter::voters(1, 1), Some(5));
+		assert_eq!(Voter::total_weight(1), 6);
+	});
+}
+
+#[test]
+fn delegate_vote_by_topic() {
+	new_test_ext().execute_with(|| {
+		election(2);
+		register(&[1, 2, 3, 4], 7);
+		assert_noop!(
+			Voter::delegate_vote(RuntimeOrigin::signed(1), 1, DelegationTopic::Question(2), 2),
+			Error::<Test>::InvalidTopic
+		);
+		assert_noop!(Voter::delegate_vote(RuntimeOrigin::signed(1), 1, DelegationTopic::All, 1), Error::<Test>::SelfDelegation);
+		assert_noop!(Voter::delegate_vote(RuntimeOrigin::signed(1), 1, DelegationTopic::All, 9), Error::<Test>::VoterNotRegistered);
+
+		assert_ok!(Voter::delegate_vote(RuntimeOrigin::signed(1), 1, DelegationTopic::All, 2));
+		System::assert_last_event(Event::VoteDelegated { election_id: 1, delegator: 1, topic: DelegationTopic::All, delegate: 2 }.into());
+		assert_ok!(Voter::delegate_vote(RuntimeOrigin::signed(2), 1, DelegationTopic::Question(0), 3));
+		// 2 -> 1 -> 2 on the whole ballot
+		assert_noop!(Voter::delegate_vote(RuntimeOrigin::signed(2), 1, DelegationTopic::All, 1), Error::<Test>::DelegationCycle);
+		// 3 -> 1, whose whole-ballot delegate 2 passes question 0 back to 3
 		assert_noop!(
-			TemplateModule::cause_error(RuntimeOrigin::signed(1)),
-			Error::<Test>::NoneValue
+			Voter::delegate_vote(RuntimeOrigin::signed(3), 1, DelegationTopic::Question(0), 1),
+			Error::<Test>::DelegationCycle
 		);
+
+		assert_ok!(Voter::delegate_vote(RuntimeOrigin::signed(4), 1, DelegationTopic::Question(0), 2));
+		assert_ok!(Voter::delegate_vote(RuntimeOrigin::signed(4), 1, DelegationTopic::Question(1), 1));
+		assert_eq!(Voter::delegator_count(1), 4);
+		// Question 0 goes to 2, who passes it on to 3
+		assert_eq!(<Voter as VoterInfo<u64>>::resolve_delegate(&1, &4, DelegationTopic::Question(0), |a| *a == 3), Some(3));
+		// Question 1 goes to 1, who passes it on with the whole ballot to 2
+		assert_eq!(<Voter as VoterInfo<u64>>::resolve_delegate(&1, &4, DelegationTopic::Question(1), |a| *a == 2), Some(2));
+		// The chain ends at 2 on the whole ballot, and the vote lapses if 2 did not vote
+		assert_eq!(<Voter as VoterInfo<u64>>::resolve_delegate(&1, &1, DelegationTopic::All, |a| *a == 4), None);
+
+		// Delegating a topic again replaces the delegate in place
+		assert_ok!(Voter::delegate_vote(RuntimeOrigin::signed(4), 1, DelegationTopic::Question(1), 3));
+		assert_eq!(Voter::delegator_count(1), 4);
+		assert_eq!(Voter::delegation(1, (4, DelegationTopic::Question(1))), Some((3, 3)));
+	});
+}
+
+#[test]
+fn whole_ballot_delegation_follows_each_question() {
+	new_test_ext().execute_with(|| {
+		election(2);
+		register(&[1, 2, 3], 7);
+		assert_ok!(Voter::delegate_vote(RuntimeOrigin::signed(2), 1, DelegationTopic::Question(1), 1));
+		// 1 -> 2 on the whole ballot would pass question 1 back to 1
+		assert_noop!(Voter::delegate_vote(RuntimeOrigin::signed(1), 1, DelegationTopic::All, 2), Error::<Test>::DelegationCycle);
+
+		// Once 1 delegates question 1 on its own, their whole ballot no longer carries it
+		assert_ok!(Voter::delegate_vote(RuntimeOrigin::signed(1), 1, DelegationTopic::Question(1), 3));
+		assert_ok!(Voter::delegate_vote(RuntimeOrigin::signed(1), 1, DelegationTopic::All, 2));
+		assert_eq!(<Voter as VoterInfo<u64>>::resolve_delegate(&1, &1, DelegationTopic::All, |a| *a == 2), Some(2));
+		assert_eq!(<Voter as VoterInfo<u64>>::resolve_delegate(&1, &1, DelegationTopic::Question(1), |a| *a == 3), Some(3));
+	});
+}
+
+#[test]
+fn delegation_chains_are_bounded() {
+	new_test_ext().execute_with(|| {
+		election(0);
+		register(&[1, 2, 3, 4, 5], 7);
+		assert_ok!(Voter::delegate_vote(RuntimeOrigin::signed(3), 1, DelegationTopic::All, 4));
+		assert_ok!(Voter::delegate_vote(RuntimeOrigin::signed(2), 1, DelegationTopic::All, 3));
+		assert_ok!(Voter::delegate_vote(RuntimeOrigin::signed(1), 1, DelegationTopic::All, 2));
+		assert_eq!(MAX_DEPTH, 3);
+		assert_noop!(Voter::delegate_vote(RuntimeOrigin::signed(5), 1, DelegationTopic::All, 1), Error::<Test>::DelegationTooDeep);
+	});
+}
+
+#[test]
+fn revoke_delegation_moves_the_last_delegator() {
+	new_test_ext().execute_with(|| {
+		election(0);
+		register(&[1, 2, 3, 4], 7);
+		for delegator in [1, 2, 3] {
+			assert_ok!(Voter::delegate_vote(RuntimeOrigin::signed(delegator), 1, DelegationTopic::All, 4));
+		}
+		assert_noop!(Voter::revoke_delegation(RuntimeOrigin::signed(4), 1, DelegationTopic::All), Error::<Test>::NotDelegated);
+		assert_ok!(Voter::revoke_delegation(RuntimeOrigin::signed(1), 1, DelegationTopic::All));
+		System::assert_last_event(Event::DelegationRevoked { election_id: 1, delegator: 1, topic: DelegationTopic::All }.into());
+		assert_eq!(Voter::delegator_count(1), 2);
+		assert_eq!(Voter::delegation(1, (1, DelegationTopic::All)), None);
+		assert_eq!(Voter::delegator(1, 0), Some((3, DelegationTopic::All)));
+		assert_eq!(Voter::delegation(1, (3, DelegationTopic::All)), Some((4, 0)));
+		assert_eq!(Voter::delegator(1, 2), None);
+
+		assert_ok!(Election::election_open_for_voting(RuntimeOrigin::root(), 1));
+		assert_ok!(Election::election_close_for_voting(RuntimeOrigin::root(), 1));
+		assert_noop!(Voter::revoke_delegation(RuntimeOrigin::signed(2), 1, DelegationTopic::All), Error::<Test>::VotingAlreadyClosed);
+	});
+}
+
+#[test]
+fn proxies_are_nominated_approved_and_revoked() {
+	new_test_ext().execute_with(|| {
+		election(0);
+		register(&[1, 2, 3], 7);
+		assert_noop!(Voter::nominate_proxy(RuntimeOrigin::signed(1), 1, 1), Error::<Test>::SelfProxy);
+		assert_noop!(Voter::approve_proxy(RuntimeOrigin::signed(registrar(7)), 1, 1), Error::<Test>::NoProxyNominated);
+		assert_ok!(Voter::nominate_proxy(RuntimeOrigin::signed(1), 1, 2));
+		assert_ok!(Voter::nominate_proxy(RuntimeOrigin::signed(3), 1, 2));
+		assert_ok!(Voter::approve_proxy(RuntimeOrigin::signed(registrar(7)), 1, 1));
+		System::assert_last_event(Event::ProxyApproved { election_id: 1, voter: 1, proxy: 2 }.into());
+		assert_eq!(Voter::proxy(1, 1), Some(2));
+		assert_eq!(Voter::proxy_principal(1, 2), Some(1));
+		assert_noop!(Voter::nominate_proxy(RuntimeOrigin::signed(1), 1, 3), Error::<Test>::ProxyAlreadyApproved);
+		assert_noop!(Voter::approve_proxy(RuntimeOrigin::signed(registrar(7)), 1, 3), Error::<Test>::ProxyAlreadyActing);
+
+		assert_ok!(Voter::revoke_proxy(RuntimeOrigin::signed(1), 1));
+		System::assert_last_event(Event::ProxyRevoked { election_id: 1, voter: 1, proxy: 2 }.into());
+		assert_eq!(Voter::proxy_principal(1, 2), None);
+		assert_noop!(Voter::revoke_proxy(RuntimeOrigin::signed(1), 1), Error::<Test>::NoProxyNominated);
+		assert_ok!(Voter::approve_proxy(RuntimeOrigin::signed(registrar(7)), 1, 3));
 	});
 }
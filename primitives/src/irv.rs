@@ -0,0 +1,154 @@
+use codec::{Decode, Encode};
+use sp_runtime::RuntimeDebug;
+use scale_info::TypeInfo;
+use sp_std::vec::Vec;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use crate::{CandidateId, VoteCount};
+
+// Instant-runoff counting. A round is accumulated ranking by ranking and then
+// finished, so the pallet can spread it over several blocks and store every
+// round for audit.
+
+/// What a round of instant-runoff decided
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum IrvOutcome {
+	Elected(CandidateId),
+	Eliminated(CandidateId),
+}
+
+/// Result of one round of instant-runoff
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct IrvRound {
+	/// Votes held by every continuing candidate, ordered by candidate id
+	pub counts: Vec<(CandidateId, VoteCount)>,
+	/// Votes on ballots that rank no continuing candidate
+	pub exhausted: VoteCount,
+	pub outcome: IrvOutcome,
+}
+
+/// Votes gathered so far in a round that has not been decided yet
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct RoundCount {
+	pub counts: Vec<(CandidateId, VoteCount)>,
+	pub exhausted: VoteCount,
+}
+
+impl RoundCount {
+	pub fn new(continuing: &[CandidateId]) -> RoundCount {
+		let mut counts: Vec<(CandidateId, VoteCount)> = continuing.iter().map(|c| (*c, 0)).collect();
+		counts.sort_by_key(|(c, _)| *c);
+		counts.dedup_by_key(|(c, _)| *c);
+		RoundCount { counts, exhausted: 0 }
+	}
+
+	/// Credit `votes` ballots with this ranking to their highest continuing preference
+	pub fn add(&mut self, ranking: &[CandidateId], votes: VoteCount) {
+		let counts = &mut self.counts;
+		match ranking.iter().find_map(|c| counts.binary_search_by_key(c, |(id, _)| *id).ok()) {
+			Some(i) => counts[i].1 = counts[i].1.saturating_add(votes),
+			None => self.exhausted = self.exhausted.saturating_add(votes),
+		}
+	}
+
+	/// Elect the majority candidate or eliminate the weakest one. `history` holds the
+	/// earlier rounds for tie-breaking. Returns `None` when no candidate is continuing.
+	pub fn finish(self, history: &[IrvRound]) -> Option<IrvRound> {
+		let RoundCount { counts, exhausted } = self;
+		let active = counts.iter().fold(0 as VoteCount, |acc, (_, n)| acc.saturating_add(*n));
+		let (leader, leader_votes) =
+			counts.iter().copied().max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(&a.0)))?;
+		let outcome = if counts.len() == 1 || leader_votes.saturating_mul(2) > active {
+			IrvOutcome::Elected(leader)
+		} else {
+			IrvOutcome::Eliminated(lowest(&counts, history))
+		};
+		Some(IrvRound { counts, exhausted, outcome })
+	}
+}
+
+/// Count a whole round at once. `rankings` yields each distinct ranking with the
+/// number of ballots that cast it.
+pub fn count_round<'a, I>(rankings: I, continuing: &[CandidateId], history: &[IrvRound]) -> Option<IrvRound>
+where
+	I: IntoIterator<Item = (&'a [CandidateId], VoteCount)>,
+{
+	let mut round = RoundCount::new(continuing);
+	for (ranking, votes) in rankings {
+		round.add(ranking, votes);
+	}
+	round.finish(history)
+}
+
+/// Candidate with the fewest votes. Ties are broken by the fewest votes in the
+/// latest earlier round that separates them, then against the highest candidate id.
+fn lowest(counts: &[(CandidateId, VoteCount)], history: &[IrvRound]) -> CandidateId {
+	let fewest = counts.iter().map(|(_, n)| *n).min().unwrap_or(0);
+	let mut tied: Vec<CandidateId> = counts.iter().filter(|(_, n)| *n == fewest).map(|(c, _)| *c).collect();
+	for round in history.iter().rev() {
+		if tied.len() == 1 {
+			break
+		}
+		let votes_of = |c: &CandidateId| {
+			round.counts.iter().find(|(id, _)| id == c).map(|(_, n)| *n).unwrap_or(0)
+		};
+		let least = tied.iter().map(votes_of).min().unwrap_or(0);
+		tied.retain(|c| votes_of(c) == least);
+	}
+	*tied.iter().max().expect("counts is non-empty")
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn run(rankings: &[(&[CandidateId], VoteCount)], candidates: &[CandidateId]) -> Vec<IrvRound> {
+		let mut continuing = candidates.to_vec();
+		let mut rounds: Vec<IrvRound> = Vec::new();
+		while let Some(round) = count_round(rankings.iter().copied(), &continuing, &rounds) {
+			let outcome = round.outcome.clone();
+			rounds.push(round);
+			match outcome {
+				IrvOutcome::Elected(_) => break,
+				IrvOutcome::Eliminated(c) => continuing.retain(|x| *x != c),
+			}
+		}
+		rounds
+	}
+
+	#[test]
+	fn test_majority_in_first_round() {
+		let rounds = run(&[(&[1, 2], 6), (&[2, 1], 4)], &[1, 2]);
+		assert_eq!(rounds.len(), 1);
+		assert_eq!(rounds[0].outcome, IrvOutcome::Elected(1));
+	}
+
+	#[test]
+	fn test_transfers_after_elimination() {
+		// 3 is eliminated first and its ballots go to 2, which then overtakes 1
+		let rounds = run(&[(&[1], 8), (&[2], 7), (&[3, 2], 5)], &[1, 2, 3]);
+		assert_eq!(rounds[0].outcome, IrvOutcome::Eliminated(3));
+		assert_eq!(rounds[1].counts, vec![(1, 8), (2, 12)]);
+		assert_eq!(rounds[1].outcome, IrvOutcome::Elected(2));
+	}
+
+	#[test]
+	fn test_exhausted_ballots_and_unranked_candidates() {
+		// 4 has no first preferences and goes first; ballots for 3 then exhaust
+		let rounds = run(&[(&[1], 5), (&[2], 4), (&[3], 3)], &[1, 2, 3, 4]);
+		assert_eq!(rounds[0].outcome, IrvOutcome::Eliminated(4));
+		assert_eq!(rounds[1].outcome, IrvOutcome::Eliminated(3));
+		assert_eq!(rounds[2].exhausted, 3);
+		assert_eq!(rounds[2].outcome, IrvOutcome::Elected(1));
+	}
+
+	#[test]
+	fn test_ties_broken_by_earlier_rounds() {
+		// 2 and 3 tie on 4 in round two; 3 had fewer votes in round one
+		let rounds = run(&[(&[1], 6), (&[2], 4), (&[3], 3), (&[4, 3], 1)], &[1, 2, 3, 4]);
+		assert_eq!(rounds[0].outcome, IrvOutcome::Eliminated(4));
+		assert_eq!(rounds[1].outcome, IrvOutcome::Eliminated(3));
+	}
+}
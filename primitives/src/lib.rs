@@ -1,31 +1,473 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use codec::{Decode, Encode};
+use codec::{Decode, Encode, MaxEncodedLen};
 use sp_runtime::RuntimeDebug;
+use frame_support::{traits::ConstU32, BoundedVec};
 use scale_info::{TypeInfo};
+use sp_std::{vec, vec::Vec};
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 use curve25519_dalek_ng::{scalar::Scalar, ristretto::CompressedRistretto};
 use bulletproofs::{BulletproofGens, PedersenGens, RangeProof};
 
 pub mod zkp;
+pub mod irv;
+pub mod stv;
+pub mod schulze;
+pub mod referendum;
+pub mod voting_method;
 
 /// The type for identifying the elections
 pub type ElectionId = u64;
 /// The type for identifying the canditates
 pub type CandidateId = u64;
+/// The type for identifying a constituency or polling unit within an election
+pub type ConstituencyId = u32;
+/// The type for identifying a voter registration region or embassy
+pub type RegionId = u32;
+/// The type for identifying the incidents reported by observers
+pub type IncidentId = u32;
 /// The type for identifying the ZKP Commitment Value
 pub type ZKPCommitmentValue = u64;
 /// The type for identifying the ZKP Commitment Value
 pub type VoteCount = u128;
+/// Candidate id marked for "None of the Above"; valid in every election, never elected
+pub const NOTA_CANDIDATE: CandidateId = CandidateId::MAX;
+/// Candidate id marked for a blank ballot, an explicit abstention; valid in every election
+pub const BLANK_CANDIDATE: CandidateId = CandidateId::MAX - 1;
+/// Candidate id marked for the write-in slot; the name travels encrypted with the ballot
+pub const WRITE_IN_CANDIDATE: CandidateId = CandidateId::MAX - 2;
+/// Maximum length of an encrypted write-in name
+pub const MAX_WRITE_IN_LEN: u32 = 256;
+/// Maximum length of a decrypted, normalised write-in name
+pub const MAX_WRITE_IN_NAME_LEN: u32 = 64;
+/// Maximum number of distinct names in the write-in results
+pub const MAX_WRITE_IN_NAMES: u32 = 256;
+/// Maximum length of the trustees' proof over the write-in results
+pub const MAX_WRITE_IN_PROOF_LEN: u32 = 16 * 1024;
+/// Write-in name encrypted to the trustees' key
+pub type EncryptedWriteIn = BoundedVec<u8, ConstU32<MAX_WRITE_IN_LEN>>;
+/// Maximum length of the sealed credential cast with an anonymous ballot
+pub const MAX_SEALED_CREDENTIAL_LEN: u32 = 512;
+/// Length of the secret randomness a voting client derives a ballot's blinding scalar from
+pub const MAX_ZKP_RANDOMNESS_LEN: u32 = 32;
+/// Secret randomness of a ballot, published only when the ballot is spoiled
+pub type ZKPRandomness = BoundedVec<u8, ConstU32<MAX_ZKP_RANDOMNESS_LEN>>;
+/// Voter's account and their signature over the ballot, encrypted to the trustees' key
+pub type SealedCredential = BoundedVec<u8, ConstU32<MAX_SEALED_CREDENTIAL_LEN>>;
+pub type WriteInName = BoundedVec<u8, ConstU32<MAX_WRITE_IN_NAME_LEN>>;
+/// The type for indexing the ballots cast in an election
+pub type BallotIndex = u32;
+/// Maximum number of preferences on a ranked ballot
+pub const MAX_RANKED_CHOICES: u32 = 16;
+/// Candidates in order of preference, most preferred first
+pub type RankedChoices = BoundedVec<CandidateId, ConstU32<MAX_RANKED_CHOICES>>;
+/// Candidates approved on an approval ballot, bounded like a ranked ballot
+pub type ApprovedChoices = BoundedVec<CandidateId, ConstU32<MAX_RANKED_CHOICES>>;
+/// Candidates with the score the voter gave them, bounded like a ranked ballot
+pub type ScoredChoices = BoundedVec<(CandidateId, u32), ConstU32<MAX_RANKED_CHOICES>>;
+
+/// Maximum length of a candidate's name
+pub const MAX_CANDIDATE_NAME_LEN: u32 = 128;
+/// Maximum length of a candidate's information
+pub const MAX_CANDIDATE_INFO_LEN: u32 = 1024;
+pub type CandidateName = BoundedVec<u8, ConstU32<MAX_CANDIDATE_NAME_LEN>>;
+pub type CandidateDetails = BoundedVec<u8, ConstU32<MAX_CANDIDATE_INFO_LEN>>;
+
+/// Prefix of the offchain index keys under which disclosure documents are stored
+pub const DISCLOSURE_INDEX_PREFIX: &[u8] = b"candidate_disclosure";
+
+/// Offchain index key of a candidate's disclosure document, by its content hash
+pub fn disclosure_index_key<Hash: Encode>(document_hash: &Hash) -> Vec<u8> {
+	(DISCLOSURE_INDEX_PREFIX, document_hash).encode()
+}
 
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
-#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 #[scale_info(skip_type_params(T))]
 pub struct CandidateInfo {
 	pub id: CandidateId,
-	pub name: Vec<u8>,
-	pub info: Vec<u8>,
+	pub name: CandidateName,
+	pub info: CandidateDetails,
+}
+
+/// How the ballots of an election are counted
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Default, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum CountingMethod {
+	/// One candidate per ballot, most votes wins
+	#[default]
+	Plurality,
+	/// Ranked ballots, lowest candidate eliminated each round until one has a majority
+	InstantRunoff,
+	/// Ranked ballots filling several seats with a Droop quota and surplus transfers
+	SingleTransferableVote,
+	/// Voters approve any number of candidates, most approvals wins
+	Approval,
+	/// Voters score candidates from 0 to `max_score`, highest total wins
+	Score { max_score: u32 },
+	/// Ranked ballots, candidates get points by position, most points wins
+	Borda,
+	/// Ranked ballots, the candidate no other beats by a stronger path of pairwise wins
+	Schulze,
+	/// Every ballot answers each of the election's questions, counted question by question
+	Referendum,
+	/// Voters spread votes over candidates, `n` votes on one costing `n²` of `budget` credits
+	Quadratic { budget: u32 },
+}
+
+/// Options chosen by the election commission when an election is registered for voting
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ElectionConfig {
+	/// Voters may cast again while voting is open and only their latest ballot is counted
+	pub allow_revoting: bool,
+	/// Ballots are cast from any account with the voter's credential sealed to the
+	/// trustees, so a voter's ballots are not publicly linkable. The trustees resolve
+	/// which ballot of each voter is the latest once voting closes. Requires revoting.
+	pub anonymous: bool,
+	/// Voters may write in a name instead of marking a candidate (plurality only)
+	pub allow_write_ins: bool,
+	pub counting_method: CountingMethod,
+	/// Number of candidates to elect
+	pub seats: u32,
+}
+
+impl ElectionConfig {
+	/// Whether the counting method can be run with these options
+	pub fn is_valid(&self) -> bool {
+		let method_ok = match self.counting_method {
+			CountingMethod::SingleTransferableVote => self.seats > 0,
+			CountingMethod::Score { max_score } => max_score > 0,
+			CountingMethod::Quadratic { budget } => budget > 0,
+			_ => true,
+		};
+		let anonymous_ok = !self.anonymous || (self.allow_revoting && !self.allow_write_ins &&
+			!matches!(self.counting_method, CountingMethod::Quadratic { .. }));
+		method_ok && anonymous_ok && (!self.allow_write_ins || self.counting_method == CountingMethod::Plurality)
+	}
+}
+
+/// What a voter's delegation covers
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum DelegationTopic {
+	/// The whole ballot, except the questions delegated on their own
+	All,
+	/// One question of a referendum
+	Question(referendum::QuestionIndex),
+}
+
+/// What an election official's role extends over
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum RoleScope {
+	/// Every election
+	Global,
+	/// One election, with all of its constituencies
+	Election(ElectionId),
+	Constituency(ElectionId, ConstituencyId),
+	/// Voter registration region or embassy
+	Region(RegionId),
+}
+
+impl RoleScope {
+	/// The next wider scope; a role held over it extends to this one
+	pub fn parent(&self) -> Option<RoleScope> {
+		match self {
+			RoleScope::Global => None,
+			RoleScope::Constituency(election_id, _) => Some(RoleScope::Election(*election_id)),
+			RoleScope::Election(_) | RoleScope::Region(_) => Some(RoleScope::Global),
+		}
+	}
+}
+
+/// Kind of irregularity an observer reports
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum IncidentCategory {
+	Intimidation,
+	VoteBuying,
+	BallotTampering,
+	/// Polling station closed, late or inaccessible
+	PollingAccess,
+	/// Voters wrongly turned away or allowed to vote
+	VoterEligibility,
+	/// Breach of counting or collation procedure
+	Counting,
+	Violence,
+	Other,
+}
+
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum IncidentSeverity {
+	Low,
+	Medium,
+	High,
+	/// The integrity of the result in the constituency is in doubt
+	Critical,
+}
+
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum IncidentStatus {
+	/// Reported and not yet looked at by the commission
+	Open,
+	/// The commission is looking into it
+	Acknowledged,
+	Resolved,
+}
+
+/// Irregularity reported by an accredited observer
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Incident<AccountId, Hash, BlockNumber> {
+	pub election_id: ElectionId,
+	pub constituency: ConstituencyId,
+	pub reporter: AccountId,
+	pub category: IncidentCategory,
+	pub severity: IncidentSeverity,
+	/// Hash of the evidence, such as photos or statements, kept off-chain
+	pub evidence: Hash,
+	pub reported_at: BlockNumber,
+	pub status: IncidentStatus,
+	/// Hash of the commission's findings, once resolved
+	pub resolution: Option<Hash>,
+}
+
+/// Whether a candidate id is reserved for an option other than a registered candidate
+pub fn is_reserved_candidate(candidate_id: CandidateId) -> bool {
+	candidate_id >= WRITE_IN_CANDIDATE
+}
+
+/// Options on every ballot besides the candidates. They are counted apart from the
+/// candidates and can never win.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum Abstention {
+	NoneOfTheAbove,
+	Blank,
+}
+
+impl Abstention {
+	/// The abstention a reserved candidate id stands for
+	pub fn from_candidate(candidate_id: CandidateId) -> Option<Abstention> {
+		match candidate_id {
+			NOTA_CANDIDATE => Some(Abstention::NoneOfTheAbove),
+			BLANK_CANDIDATE => Some(Abstention::Blank),
+			_ => None,
+		}
+	}
+}
+
+/// Ballots cast for each abstention option in an election
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug, TypeInfo)]
+pub struct AbstentionCount {
+	pub none_of_the_above: VoteCount,
+	pub blank: VoteCount,
+}
+
+/// Voters registered, approved by their registrar, and who cast a ballot
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Default, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ParticipationCount {
+	pub registered: u32,
+	pub approved: u32,
+	pub voted: u32,
+}
+
+/// Participation in an election, overall and by registration region and constituency.
+/// Voters without a constituency are only in the overall count.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug, TypeInfo)]
+pub struct Participation {
+	pub total: ParticipationCount,
+	/// Regions in id order
+	pub regions: Vec<(RegionId, ParticipationCount)>,
+	/// Constituencies in id order
+	pub constituencies: Vec<(ConstituencyId, ParticipationCount)>,
+}
+
+/// Votes counted in one constituency
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug, TypeInfo)]
+pub struct ConstituencyResult {
+	pub constituency: ConstituencyId,
+	/// Votes counted, abstentions and delegated weight included
+	pub turnout: VoteCount,
+	/// Votes per candidate; first preferences under the ranked methods
+	pub totals: Vec<(CandidateId, VoteCount)>,
+}
+
+/// Why a candidate stopped standing after the ballots were set
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum RemovalReason {
+	Withdrawn,
+	Disqualified,
+}
+
+/// What happens to the votes of a candidate who stopped standing
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum VoteRule {
+	/// Votes for the candidate are void; ranked ballots move to the next preference
+	Void,
+	/// The poll is countermanded and held again once voting is reopened
+	Countermand,
+	/// The poll is started afresh at once without the candidate
+	Repoll,
+}
+
+/// Candidate who stopped standing after the ballots were set, with the votes cast
+/// for them that were kept out of the results
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct RemovedCandidate {
+	pub candidate_id: CandidateId,
+	pub reason: RemovalReason,
+	pub rule: VoteRule,
+	pub voided: VoteCount,
+}
+
+/// Final results of an election, as hashed for the commissioners to sign
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug, TypeInfo)]
+pub struct ResultsSummary {
+	pub winners: Vec<CandidateId>,
+	/// Votes per candidate, in candidate id order
+	pub totals: Vec<(CandidateId, VoteCount)>,
+	/// Constituencies in id order; ballots of voters without one are only in the totals
+	pub constituencies: Vec<ConstituencyResult>,
+	/// Votes counted, abstentions and delegated weight included
+	pub turnout: VoteCount,
+	pub abstentions: AbstentionCount,
+	/// Candidates removed after the ballots were set, in candidate id order
+	pub removals: Vec<RemovedCandidate>,
+}
+
+/// Results certified by the commissioners' signatures over their digest
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct CertifiedResults<Hash, BlockNumber> {
+	pub digest: Hash,
+	pub certified_at: BlockNumber,
+	pub results: ResultsSummary,
+}
+
+/// What a voter marked on their ballot
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum BallotChoice {
+	Single(CandidateId),
+	Ranked(RankedChoices),
+	Approval(ApprovedChoices),
+	Scored(ScoredChoices),
+	Answers(referendum::Answers),
+}
+
+impl BallotChoice {
+	/// Whether the ballot marks the write-in slot
+	pub fn is_write_in(&self) -> bool {
+		*self == BallotChoice::Single(WRITE_IN_CANDIDATE)
+	}
+
+	/// NOTA and blank are cast as a single reserved candidate id, so they go through
+	/// the same sealed commitment as a vote for a candidate
+	pub fn abstention(&self) -> Option<Abstention> {
+		match self {
+			BallotChoice::Single(candidate_id) => Abstention::from_candidate(*candidate_id),
+			_ => None,
+		}
+	}
+
+	/// Every candidate marked on the ballot
+	pub fn candidates(&self) -> Vec<CandidateId> {
+		match self {
+			BallotChoice::Single(candidate_id) => vec![*candidate_id],
+			BallotChoice::Ranked(choices) | BallotChoice::Approval(choices) => choices.to_vec(),
+			BallotChoice::Scored(scores) => scores.iter().map(|(c, _)| *c).collect(),
+			BallotChoice::Answers(_) => Vec::new(),
+		}
+	}
+}
+
+/// A ballot as stored until the election is tallied
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct CastBallot<Hash> {
+	pub choice: BallotChoice,
+	pub tracking_hash: Hash,
+	/// Replaced by a later ballot of the same voter, so it is skipped by the tally
+	pub superseded: bool,
+	/// Voting weight of the voter when the ballot was cast
+	pub weight: VoteCount,
+	/// Constituency of the voter and its re-poll generation when the ballot was cast
+	pub scope: Option<(ConstituencyId, u32)>,
+}
+
+/// Progress of the tally of an election whose voting window is closed
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum TallyStatus {
+	/// Delegators before `next` have had their weight added to the ballot of the
+	/// delegate who votes for them
+	Delegations { next: u32 },
+	/// Ballots before `next` have been aggregated
+	Counting { next: BallotIndex },
+	/// Instant-runoff rounds before `round` have been decided and the rankings
+	/// before `next` have been counted for `round`
+	Rounds { round: u32, next: u32 },
+	/// STV stages before `stage` have been decided and the rankings before `next`
+	/// have been transferred and counted for `stage`
+	Stages { stage: u32, next: u32 },
+	Complete,
+}
+
+/// Write-in names decrypted after mixing, normalised and aggregated by the trustees
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct WriteInResults {
+	pub totals: BoundedVec<(WriteInName, VoteCount), ConstU32<MAX_WRITE_IN_NAMES>>,
+	/// Proof of correct mixing and decryption, checked by observers off-chain
+	pub proof: BoundedVec<u8, ConstU32<MAX_WRITE_IN_PROOF_LEN>>,
+}
+
+/// How the trustees resolved an anonymous ballot from its sealed credential
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum BallotResolution {
+	/// Latest ballot of a registered voter, counted with their weight and any weight
+	/// delegated to them. The region is counted in the participation statistics.
+	Counted { weight: VoteCount, region: Option<RegionId> },
+	/// A later ballot of the same voter replaces it
+	Superseded,
+	/// The credential does not open to a registered voter's signature over the ballot,
+	/// or names another constituency than the one declared
+	Invalid,
+}
+
+/// Opening of a ballot the voter challenged instead of casting (Benaloh audit).
+/// Anyone holding it can rebuild the commitment offline and compare the tracking hash.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct SpoiledBallot {
+	pub candidate: CandidateId,
+	pub zkp_value: ZKPCommitmentValue,
+	pub zkp_randomness: ZKPRandomness,
+}
+
+/// Commitment to the voter's weight cast with a ballot, with a proof that it opens to
+/// the weight registered for the voter. The weight is public, in the voter pallet and
+/// on the stored ballot; the commitment does not hide it. It fixes the tracking hash
+/// the voter checks a spoiled opening against, and the proof is bound to the ballot
+/// it is cast with, so it cannot be replayed on another ballot.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct SealedWeight {
+	/// Compressed Pedersen commitment `weight * B + r * B_blinding`
+	pub commitment: [u8; 32],
+	/// Compressed nonce commitment `R` followed by the response `s`
+	pub proof: [u8; 64],
 }
 
 //Implement the ZKP Module:
@@ -0,0 +1,103 @@
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{traits::ConstU32, BoundedVec};
+use sp_runtime::{PerThing, Perbill, RuntimeDebug};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use crate::VoteCount;
+
+// Questions put to voters alongside, or instead of, a candidate race. Every
+// ballot answers each question of its election with one option, each question is
+// counted on its own, and its thresholds are applied when the results are certified.
+
+/// Position of a question on an election's ballot
+pub type QuestionIndex = u32;
+/// Position of an option within its question
+pub type OptionIndex = u32;
+/// Maximum number of questions on one ballot
+pub const MAX_QUESTIONS: u32 = 16;
+/// Maximum number of options of one question
+pub const MAX_OPTIONS: u32 = 16;
+/// Maximum length of the text of a question or option
+pub const MAX_QUESTION_TEXT: u32 = 256;
+pub type QuestionText = BoundedVec<u8, ConstU32<MAX_QUESTION_TEXT>>;
+/// The questions of an election, in ballot order
+pub type Questions = BoundedVec<Question, ConstU32<MAX_QUESTIONS>>;
+/// The option chosen for each question, in ballot order
+pub type Answers = BoundedVec<OptionIndex, ConstU32<MAX_QUESTIONS>>;
+
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Question {
+	pub text: QuestionText,
+	/// A yes/no referendum has the options "Yes" and "No"
+	pub options: BoundedVec<QuestionText, ConstU32<MAX_OPTIONS>>,
+	/// Share of the registered voting weight that must answer for the result to stand
+	pub min_turnout: Perbill,
+	/// Share of the answers the leading option must exceed to carry
+	pub approval: Perbill,
+}
+
+/// Result of a question once its thresholds are applied
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum QuestionOutcome {
+	Carried(OptionIndex),
+	/// No option is ahead by more than the approval threshold
+	NotCarried,
+	/// Too few of the registered voters answered
+	TurnoutNotMet,
+}
+
+/// Apply a question's thresholds to its counts, one per option, given the total
+/// voting weight registered. A tie for the lead never carries.
+pub fn outcome(question: &Question, counts: &[VoteCount], electorate: VoteCount) -> QuestionOutcome {
+	let answered = counts.iter().fold(0 as VoteCount, |acc, n| acc.saturating_add(*n));
+	if answered < question.min_turnout.mul_ceil(electorate) {
+		return QuestionOutcome::TurnoutNotMet
+	}
+	let leading = counts.iter().copied().max().unwrap_or(0);
+	let mut leaders = counts.iter().enumerate().filter(|(_, n)| **n == leading);
+	match (leaders.next(), leaders.next()) {
+		(Some((option, _)), None) if leading > question.approval.mul_floor(answered) => {
+			QuestionOutcome::Carried(option as OptionIndex)
+		},
+		_ => QuestionOutcome::NotCarried,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn question(options: u32, min_turnout: u32, approval: u32) -> Question {
+		let option: QuestionText = b"option".to_vec().try_into().unwrap();
+		Question {
+			text: b"question".to_vec().try_into().unwrap(),
+			options: vec![option; options as usize].try_into().unwrap(),
+			min_turnout: Perbill::from_percent(min_turnout),
+			approval: Perbill::from_percent(approval),
+		}
+	}
+
+	#[test]
+	fn test_simple_majority() {
+		let q = question(2, 0, 50);
+		assert_eq!(outcome(&q, &[6, 4], 100), QuestionOutcome::Carried(0));
+		assert_eq!(outcome(&q, &[5, 5], 100), QuestionOutcome::NotCarried);
+	}
+
+	#[test]
+	fn test_supermajority() {
+		let q = question(2, 0, 66);
+		assert_eq!(outcome(&q, &[4, 6], 100), QuestionOutcome::NotCarried);
+		assert_eq!(outcome(&q, &[3, 7], 100), QuestionOutcome::Carried(1));
+	}
+
+	#[test]
+	fn test_turnout_threshold() {
+		let q = question(3, 40, 0);
+		assert_eq!(outcome(&q, &[20, 10, 9], 100), QuestionOutcome::TurnoutNotMet);
+		assert_eq!(outcome(&q, &[20, 10, 10], 100), QuestionOutcome::Carried(0));
+	}
+}
@@ -0,0 +1,155 @@
+use codec::{Decode, Encode};
+use sp_runtime::RuntimeDebug;
+use scale_info::TypeInfo;
+use sp_std::{vec, vec::Vec};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use crate::{CandidateId, VoteCount, MAX_RANKED_CHOICES};
+
+// Schulze method. While voting is open the chain keeps, per election, how many
+// ballots rank each candidate and how many rank one candidate above another. A
+// ranked candidate is preferred to every unranked one, so the number of ballots
+// preferring `a` to `b` is `ranked(a) - above(b, a)`. After close the strongest
+// paths are computed over that matrix with winning votes as link strength.
+
+/// Counts touched by one ranked ballot: each ranked candidate and each ordered pair
+pub const MAX_PREFERENCE_ENTRIES: u32 = MAX_RANKED_CHOICES * (MAX_RANKED_CHOICES + 1) / 2;
+
+/// Number of ballots preferring each candidate to each other one
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug, TypeInfo)]
+pub struct PairwiseMatrix {
+	/// Candidates in id order, indexing both dimensions of `preferences`
+	pub candidates: Vec<CandidateId>,
+	/// `preferences[i][j]` ballots prefer `candidates[i]` to `candidates[j]`
+	pub preferences: Vec<Vec<VoteCount>>,
+}
+
+/// Every ordered pair `(a, b)` with `a` ranked above `b` on the ballot
+pub fn ranked_pairs(ranking: &[CandidateId]) -> Vec<(CandidateId, CandidateId)> {
+	ranking
+		.iter()
+		.enumerate()
+		.flat_map(|(i, a)| ranking[i + 1..].iter().map(move |b| (*a, *b)))
+		.collect()
+}
+
+/// Build the full matrix from the ballots ranking each candidate and the ballots
+/// ranking one candidate above another
+pub fn matrix<R, A>(candidates: &[CandidateId], ranked: R, above: A) -> PairwiseMatrix
+where
+	R: Fn(CandidateId) -> VoteCount,
+	A: Fn(CandidateId, CandidateId) -> VoteCount,
+{
+	let mut candidates = candidates.to_vec();
+	candidates.sort();
+	candidates.dedup();
+	let preferences = candidates
+		.iter()
+		.map(|a| {
+			candidates
+				.iter()
+				.map(|b| if a == b { 0 } else { ranked(*a).saturating_sub(above(*b, *a)) })
+				.collect()
+		})
+		.collect();
+	PairwiseMatrix { candidates, preferences }
+}
+
+/// Strength of the strongest path between every pair of candidates
+pub fn strongest_paths(matrix: &PairwiseMatrix) -> Vec<Vec<VoteCount>> {
+	let d = &matrix.preferences;
+	let n = matrix.candidates.len();
+	let mut p = vec![vec![0 as VoteCount; n]; n];
+	for i in 0..n {
+		for j in 0..n {
+			if i != j && d[i][j] > d[j][i] {
+				p[i][j] = d[i][j];
+			}
+		}
+	}
+	for i in 0..n {
+		for j in 0..n {
+			if i == j {
+				continue
+			}
+			for k in 0..n {
+				if k != i && k != j {
+					p[j][k] = p[j][k].max(p[j][i].min(p[i][k]));
+				}
+			}
+		}
+	}
+	p
+}
+
+/// Candidates no other candidate beats by a stronger path. Usually one; every
+/// tied winner is returned, in id order.
+pub fn winners(matrix: &PairwiseMatrix) -> Vec<CandidateId> {
+	let p = strongest_paths(matrix);
+	let n = matrix.candidates.len();
+	(0..n)
+		.filter(|i| (0..n).all(|j| p[*i][j] >= p[j][*i]))
+		.map(|i| matrix.candidates[i])
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn run(rankings: &[(&[CandidateId], VoteCount)], candidates: &[CandidateId]) -> PairwiseMatrix {
+		let ranked = |c: CandidateId| {
+			rankings.iter().filter(|(r, _)| r.contains(&c)).map(|(_, n)| *n).sum::<VoteCount>()
+		};
+		let above = |a: CandidateId, b: CandidateId| {
+			rankings
+				.iter()
+				.filter(|(r, _)| ranked_pairs(r).contains(&(a, b)))
+				.map(|(_, n)| *n)
+				.sum::<VoteCount>()
+		};
+		matrix(candidates, ranked, above)
+	}
+
+	#[test]
+	fn test_unranked_candidates_are_least_preferred() {
+		let m = run(&[(&[2], 3), (&[1, 3], 2)], &[1, 2, 3]);
+		assert_eq!(m.preferences, vec![vec![0, 2, 2], vec![3, 0, 3], vec![0, 2, 0]]);
+	}
+
+	#[test]
+	fn test_cycle_broken_at_weakest_link() {
+		// 1 beats 2 by 6-3, 2 beats 3 by 7-2, 3 beats 1 by 5-4; the weakest link drops
+		let m = run(&[(&[1, 2, 3], 4), (&[2, 3, 1], 3), (&[3, 1, 2], 2)], &[1, 2, 3]);
+		assert_eq!(winners(&m), vec![1]);
+	}
+
+	#[test]
+	fn test_wikipedia_example() {
+		// The 45-voter example from the Schulze method article; E wins
+		let (a, b, c, d, e) = (1, 2, 3, 4, 5);
+		let m = run(
+			&[
+				(&[a, c, b, e, d], 5),
+				(&[a, d, e, c, b], 5),
+				(&[b, e, d, a, c], 8),
+				(&[c, a, b, e, d], 3),
+				(&[c, a, e, b, d], 7),
+				(&[c, b, a, d, e], 2),
+				(&[d, c, e, b, a], 7),
+				(&[e, b, a, d, c], 8),
+			],
+			&[a, b, c, d, e],
+		);
+		assert_eq!(m.preferences[0], vec![0, 20, 26, 30, 22]);
+		assert_eq!(strongest_paths(&m)[4], vec![25, 28, 28, 31, 0]);
+		assert_eq!(winners(&m), vec![e]);
+	}
+
+	#[test]
+	fn test_tie_returns_every_winner() {
+		let m = run(&[(&[1, 2], 1), (&[2, 1], 1)], &[1, 2]);
+		assert_eq!(winners(&m), vec![1, 2]);
+	}
+}
@@ -0,0 +1,302 @@
+use codec::{Decode, Encode};
+use sp_runtime::{
+	traits::{One, Saturating, Zero},
+	FixedPointNumber, FixedU128, RuntimeDebug,
+};
+use scale_info::TypeInfo;
+use sp_std::vec::Vec;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use crate::{CandidateId, VoteCount};
+
+// Single Transferable Vote with a Droop quota and weighted inclusive Gregory
+// surplus transfers. Ballot values are FixedU128 and every rounding is a
+// truncation, so every node arrives at exactly the same stages.
+
+/// One counting stage: the totals it started from and what it decided
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct StvStage {
+	/// Votes held by every continuing candidate, ordered by candidate id
+	pub totals: Vec<(CandidateId, FixedU128)>,
+	/// Value of ballots that rank no continuing candidate
+	pub exhausted: FixedU128,
+	/// Candidates elected at this stage, strongest first
+	pub elected: Vec<CandidateId>,
+	/// Candidate excluded at this stage, whose ballots move on at their current value
+	pub excluded: Option<CandidateId>,
+}
+
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct StvResult {
+	pub quota: VoteCount,
+	pub stages: Vec<StvStage>,
+	/// Candidates in the order they were elected
+	pub elected: Vec<CandidateId>,
+}
+
+/// Value of the ballots sharing one ranking and the preference holding them; the
+/// ballots of a ranking always move together
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct StvParcel {
+	/// Value of each ballot in the parcel
+	pub value: FixedU128,
+	/// Position in the ranking of the candidate holding the parcel
+	pub holder: Option<u32>,
+}
+
+impl StvParcel {
+	/// Parcel of a ranking before the first stage, at its highest continuing preference
+	pub fn new(ranking: &[CandidateId], continuing: &[CandidateId]) -> StvParcel {
+		let mut parcel = StvParcel { value: FixedU128::one(), holder: None };
+		parcel.pass_on(ranking, continuing);
+		parcel
+	}
+
+	fn held_by(&self, ranking: &[CandidateId]) -> Option<CandidateId> {
+		self.holder.and_then(|i| ranking.get(i as usize).copied())
+	}
+
+	fn total(&self, votes: VoteCount) -> FixedU128 {
+		FixedU128::saturating_from_integer(votes).saturating_mul(self.value)
+	}
+
+	/// Move to the next continuing preference after the current holder
+	fn pass_on(&mut self, ranking: &[CandidateId], continuing: &[CandidateId]) {
+		let from = self.holder.map_or(0, |i| i as usize + 1);
+		self.holder = (from..ranking.len()).find(|i| continuing.contains(&ranking[*i])).map(|i| i as u32);
+	}
+}
+
+/// Stage that has not been decided yet: the surpluses and exclusion decided by the
+/// previous stage are transferred ranking by ranking while the totals are gathered,
+/// so the pallet can spread a stage over several blocks
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct StageCount {
+	/// Candidates still in the count, ordered by candidate id
+	pub continuing: Vec<CandidateId>,
+	/// Candidates whose ballots move on in this stage, with the share of their value
+	/// they move on at
+	pub transfers: Vec<(CandidateId, FixedU128)>,
+	pub totals: Vec<(CandidateId, FixedU128)>,
+	pub exhausted: FixedU128,
+	/// Ballots held by a continuing candidate, counted in the first stage for the quota
+	pub valid: VoteCount,
+}
+
+/// What a stage decided, and the stage that follows unless every seat is filled
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct StageOutcome {
+	pub stage: StvStage,
+	pub quota: VoteCount,
+	pub next: Option<StageCount>,
+}
+
+impl StageCount {
+	/// First stage, over the standing candidates
+	pub fn first(candidates: &[CandidateId]) -> StageCount {
+		let mut continuing = candidates.to_vec();
+		continuing.sort();
+		continuing.dedup();
+		StageCount::new(continuing, Vec::new())
+	}
+
+	fn new(continuing: Vec<CandidateId>, transfers: Vec<(CandidateId, FixedU128)>) -> StageCount {
+		let totals = continuing.iter().map(|c| (*c, FixedU128::zero())).collect();
+		StageCount { continuing, transfers, totals, exhausted: FixedU128::zero(), valid: 0 }
+	}
+
+	/// Transfer the parcel of `votes` ballots with this ranking if its holder's ballots
+	/// move on in this stage, and credit it to its holder. A ranking seen for the first
+	/// time has no parcel yet. Returns the parcel to keep for the next stage.
+	pub fn add(&mut self, ranking: &[CandidateId], votes: VoteCount, parcel: Option<StvParcel>) -> StvParcel {
+		let mut parcel = parcel.unwrap_or_else(|| StvParcel::new(ranking, &self.continuing));
+		if let Some(holder) = parcel.held_by(ranking) {
+			if let Some((_, ratio)) = self.transfers.iter().find(|(c, _)| *c == holder) {
+				parcel.value = parcel.value.saturating_mul(*ratio);
+				parcel.pass_on(ranking, &self.continuing);
+			}
+		}
+		let total = parcel.total(votes);
+		let totals = &mut self.totals;
+		match parcel.held_by(ranking).and_then(|c| totals.binary_search_by_key(&c, |(id, _)| *id).ok()) {
+			Some(i) => {
+				totals[i].1 = totals[i].1.saturating_add(total);
+				self.valid = self.valid.saturating_add(votes);
+			},
+			None => self.exhausted = self.exhausted.saturating_add(total),
+		}
+		parcel
+	}
+
+	/// Elect the candidates reaching the quota, or exclude the weakest one. The quota is
+	/// set by the first stage, which passes `None`. `elected` is the number of seats
+	/// filled by the earlier stages and `history` holds them for tie-breaking.
+	pub fn finish(self, quota: Option<VoteCount>, seats: u32, elected: u32, history: &[StvStage]) -> StageOutcome {
+		let StageCount { mut continuing, totals, exhausted, valid, .. } = self;
+		let quota = quota.unwrap_or_else(|| valid / (seats as VoteCount).saturating_add(1) + 1);
+		let quota_value = FixedU128::saturating_from_integer(quota);
+		let remaining = seats.saturating_sub(elected) as usize;
+		let mut stage = StvStage { totals: totals.clone(), exhausted, elected: Vec::new(), excluded: None };
+		let mut transfers = Vec::new();
+
+		let mut strongest = totals.clone();
+		strongest.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+		if continuing.len() <= remaining {
+			// Every continuing candidate fills one of the remaining seats
+			stage.elected = strongest.iter().map(|(c, _)| *c).collect();
+			continuing.clear();
+		} else {
+			strongest.retain(|(_, total)| *total >= quota_value);
+			strongest.truncate(remaining);
+			if strongest.is_empty() {
+				let weakest = lowest(&totals, history);
+				continuing.retain(|c| *c != weakest);
+				transfers.push((weakest, FixedU128::one()));
+				stage.excluded = Some(weakest);
+			} else {
+				for (c, total) in strongest.iter() {
+					continuing.retain(|x| x != c);
+					// Every ballot the candidate holds moves on at value * surplus / total
+					let surplus = total.saturating_sub(quota_value);
+					let ratio = FixedU128::checked_from_rational(surplus.into_inner(), total.into_inner())
+						.unwrap_or_else(FixedU128::zero);
+					transfers.push((*c, ratio));
+				}
+				stage.elected = strongest.iter().map(|(c, _)| *c).collect();
+			}
+		}
+		let filled = (elected as usize).saturating_add(stage.elected.len());
+		let next = if filled < seats as usize && !continuing.is_empty() {
+			Some(StageCount::new(continuing, transfers))
+		} else {
+			None
+		};
+		StageOutcome { stage, quota, next }
+	}
+}
+
+/// Count an STV election at once. `rankings` yields each distinct ranking with the
+/// number of ballots that cast it.
+pub fn count<'a, I>(rankings: I, candidates: &[CandidateId], seats: u32) -> StvResult
+where
+	I: IntoIterator<Item = (&'a [CandidateId], VoteCount)>,
+{
+	let rankings: Vec<(&[CandidateId], VoteCount)> = rankings.into_iter().collect();
+	let mut parcels: Vec<Option<StvParcel>> = rankings.iter().map(|_| None).collect();
+	let mut quota = None;
+	let mut stages: Vec<StvStage> = Vec::new();
+	let mut elected: Vec<CandidateId> = Vec::new();
+	let mut next = Some(StageCount::first(candidates)).filter(|s| seats > 0 && !s.continuing.is_empty());
+	while let Some(mut stage) = next {
+		for ((ranking, votes), parcel) in rankings.iter().zip(parcels.iter_mut()) {
+			*parcel = Some(stage.add(ranking, *votes, parcel.take()));
+		}
+		let outcome = stage.finish(quota, seats, elected.len() as u32, &stages);
+		quota = Some(outcome.quota);
+		elected.extend(outcome.stage.elected.iter().copied());
+		stages.push(outcome.stage);
+		next = outcome.next;
+	}
+	let quota = quota.unwrap_or_else(|| {
+		let mut first = StageCount::first(candidates);
+		for (ranking, votes) in rankings.iter() {
+			first.add(ranking, *votes, None);
+		}
+		first.valid / (seats as VoteCount).saturating_add(1) + 1
+	});
+	StvResult { quota, stages, elected }
+}
+
+/// Candidate with the fewest votes. Ties are broken by the fewest votes in the
+/// latest earlier stage that separates them, then against the highest candidate id.
+fn lowest(totals: &[(CandidateId, FixedU128)], history: &[StvStage]) -> CandidateId {
+	let fewest = totals.iter().map(|(_, t)| *t).min().unwrap_or_else(FixedU128::zero);
+	let mut tied: Vec<CandidateId> = totals.iter().filter(|(_, t)| *t == fewest).map(|(c, _)| *c).collect();
+	for stage in history.iter().rev() {
+		if tied.len() == 1 {
+			break
+		}
+		let votes_of = |c: &CandidateId| {
+			stage.totals.iter().find(|(id, _)| id == c).map(|(_, t)| *t).unwrap_or_else(FixedU128::zero)
+		};
+		let least = tied.iter().map(votes_of).min().unwrap_or_else(FixedU128::zero);
+		tied.retain(|c| votes_of(c) == least);
+	}
+	*tied.iter().max().expect("totals is non-empty")
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn run(rankings: &[(&[CandidateId], VoteCount)], candidates: &[CandidateId], seats: u32) -> StvResult {
+		count(rankings.iter().copied(), candidates, seats)
+	}
+
+	#[test]
+	fn test_surplus_is_transferred_at_reduced_value() {
+		// Quota is 17 / 3 + 1 = 6; 1's surplus of 4 moves on at 0.4 per ballot
+		let result = run(&[(&[1, 2], 10), (&[3], 4), (&[2], 3)], &[1, 2, 3], 2);
+		assert_eq!(result.quota, 6);
+		assert_eq!(result.stages[0].elected, vec![1]);
+		assert_eq!(result.stages[1].totals, vec![
+			(2, FixedU128::saturating_from_integer(7u32)),
+			(3, FixedU128::saturating_from_integer(4u32)),
+		]);
+		assert_eq!(result.elected, vec![1, 2]);
+	}
+
+	#[test]
+	fn test_weakest_is_excluded_when_nobody_reaches_quota() {
+		// Quota is 20 / 3 + 1 = 7; 4 goes first and its ballots elect 2
+		let result = run(&[(&[1], 6), (&[2], 5), (&[3], 5), (&[4, 2], 4)], &[1, 2, 3, 4], 2);
+		assert_eq!(result.stages[0].excluded, Some(4));
+		assert_eq!(result.stages[1].elected, vec![2]);
+		assert_eq!(result.elected, vec![2, 1]);
+	}
+
+	#[test]
+	fn test_elected_candidates_are_skipped_on_transfer() {
+		// Both 1 and 2 reach quota at once; 1's surplus skips 2 and reaches 3
+		let result = run(&[(&[1, 2, 3], 12), (&[2], 8), (&[3], 1), (&[4], 2)], &[1, 2, 3, 4], 3);
+		assert_eq!(result.quota, 6);
+		assert_eq!(result.stages[0].elected, vec![1, 2]);
+		assert_eq!(result.stages[1].totals, vec![
+			(3, FixedU128::saturating_from_integer(7u32)),
+			(4, FixedU128::saturating_from_integer(2u32)),
+		]);
+		assert_eq!(result.elected, vec![1, 2, 3]);
+	}
+
+	#[test]
+	fn test_stages_resume_from_stored_state() {
+		// Each ranking is counted in its own batch, with the stage and parcels stored between
+		let rankings: &[(&[CandidateId], VoteCount)] = &[(&[1, 2], 10), (&[3], 4), (&[2], 3)];
+		let mut parcels: Vec<Option<Vec<u8>>> = vec![None; rankings.len()];
+		let mut stored = Some(StageCount::first(&[1, 2, 3]).encode());
+		let (mut quota, mut stages) = (None, Vec::new());
+		while let Some(bytes) = stored {
+			let mut stage = StageCount::decode(&mut &bytes[..]).unwrap();
+			for ((ranking, votes), parcel) in rankings.iter().zip(parcels.iter_mut()) {
+				let previous = parcel.take().map(|p| StvParcel::decode(&mut &p[..]).unwrap());
+				*parcel = Some(stage.add(ranking, *votes, previous).encode());
+				stage = StageCount::decode(&mut &stage.encode()[..]).unwrap();
+			}
+			let elected = stages.iter().map(|s: &StvStage| s.elected.len() as u32).sum();
+			let outcome = stage.finish(quota, 2, elected, &stages);
+			quota = Some(outcome.quota);
+			stages.push(outcome.stage);
+			stored = outcome.next.map(|s| s.encode());
+		}
+		assert_eq!(stages, run(rankings, &[1, 2, 3], 2).stages);
+	}
+
+	#[test]
+	fn test_remaining_seats_filled_by_continuing_candidates() {
+		let result = run(&[(&[1], 3), (&[2], 2)], &[1, 2, 3], 3);
+		assert_eq!(result.stages.len(), 1);
+		assert_eq!(result.elected, vec![1, 2, 3]);
+	}
+}
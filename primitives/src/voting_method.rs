@@ -0,0 +1,283 @@
+use codec::{Decode, Encode};
+use sp_runtime::RuntimeDebug;
+use scale_info::TypeInfo;
+use sp_std::{boxed::Box, vec, vec::Vec};
+use crate::{BallotChoice, CandidateId, CountingMethod, VoteCount};
+
+// Voting methods whose result is a sum of points per candidate. A method checks
+// the shape of a ballot when it is cast, turns it into points when it is tallied
+// and picks the winners from the totals once every ballot is counted.
+
+/// Why a ballot was rejected by its election's voting method
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum BallotError {
+	/// The ballot is not the shape the method counts
+	WrongBallotType,
+	/// The ballot marks no candidate
+	Empty,
+	/// The ballot marks a candidate twice
+	Duplicate,
+	/// A score is above the election's maximum
+	ScoreOutOfRange,
+	/// The votes on a quadratic ballot cost more credits than the budget
+	OverBudget,
+}
+
+pub trait VotingMethod {
+	/// Check that the ballot has the shape this method counts
+	fn validate(&self, choice: &BallotChoice) -> Result<(), BallotError>;
+
+	/// Points the ballot adds to each candidate's total
+	fn accumulate(&self, choice: &BallotChoice) -> Vec<(CandidateId, VoteCount)>;
+
+	/// Candidates filling `seats` (at least one), highest total first. A tie at the
+	/// last seat goes to the lowest candidate id.
+	fn result(&self, totals: &[(CandidateId, VoteCount)], seats: u32) -> Vec<CandidateId> {
+		let mut ranked = totals.to_vec();
+		ranked.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+		ranked.into_iter().take(seats.max(1) as usize).map(|(c, _)| c).collect()
+	}
+}
+
+/// One candidate per ballot, one point each
+pub struct Plurality;
+
+/// Any number of candidates per ballot, one point each
+pub struct Approval;
+
+/// Every marked candidate gets the score the voter gave, up to `max_score`
+pub struct Score {
+	pub max_score: u32,
+}
+
+/// Every marked candidate gets the votes the voter gave; `n` votes cost `n²`
+/// credits and a ballot may spend at most `budget`
+pub struct Quadratic {
+	pub budget: u32,
+}
+
+/// Ranked ballots; with `candidates` standing the first preference gets
+/// `candidates - 1` points, the next one less, and unranked candidates none
+pub struct Borda {
+	pub candidates: u32,
+}
+
+impl VotingMethod for Plurality {
+	fn validate(&self, choice: &BallotChoice) -> Result<(), BallotError> {
+		match choice {
+			BallotChoice::Single(_) => Ok(()),
+			_ => Err(BallotError::WrongBallotType),
+		}
+	}
+
+	fn accumulate(&self, choice: &BallotChoice) -> Vec<(CandidateId, VoteCount)> {
+		match choice {
+			BallotChoice::Single(candidate_id) => vec![(*candidate_id, 1)],
+			_ => Vec::new(),
+		}
+	}
+}
+
+impl VotingMethod for Approval {
+	fn validate(&self, choice: &BallotChoice) -> Result<(), BallotError> {
+		match choice {
+			BallotChoice::Approval(approved) => check_distinct(approved),
+			_ => Err(BallotError::WrongBallotType),
+		}
+	}
+
+	fn accumulate(&self, choice: &BallotChoice) -> Vec<(CandidateId, VoteCount)> {
+		match choice {
+			BallotChoice::Approval(approved) => approved.iter().map(|c| (*c, 1)).collect(),
+			_ => Vec::new(),
+		}
+	}
+}
+
+impl VotingMethod for Score {
+	fn validate(&self, choice: &BallotChoice) -> Result<(), BallotError> {
+		match choice {
+			BallotChoice::Scored(scores) => {
+				let marked: Vec<CandidateId> = scores.iter().map(|(c, _)| *c).collect();
+				check_distinct(&marked)?;
+				if scores.iter().any(|(_, s)| *s > self.max_score) {
+					return Err(BallotError::ScoreOutOfRange)
+				}
+				Ok(())
+			},
+			_ => Err(BallotError::WrongBallotType),
+		}
+	}
+
+	fn accumulate(&self, choice: &BallotChoice) -> Vec<(CandidateId, VoteCount)> {
+		match choice {
+			BallotChoice::Scored(scores) => scores.iter().map(|(c, s)| (*c, *s as VoteCount)).collect(),
+			_ => Vec::new(),
+		}
+	}
+}
+
+impl VotingMethod for Quadratic {
+	fn validate(&self, choice: &BallotChoice) -> Result<(), BallotError> {
+		match choice {
+			BallotChoice::Scored(votes) => {
+				let marked: Vec<CandidateId> = votes.iter().map(|(c, _)| *c).collect();
+				check_distinct(&marked)?;
+				match credits_spent(votes) {
+					Some(spent) if spent <= self.budget as u64 => Ok(()),
+					_ => Err(BallotError::OverBudget),
+				}
+			},
+			_ => Err(BallotError::WrongBallotType),
+		}
+	}
+
+	fn accumulate(&self, choice: &BallotChoice) -> Vec<(CandidateId, VoteCount)> {
+		match choice {
+			BallotChoice::Scored(votes) => votes.iter().map(|(c, n)| (*c, *n as VoteCount)).collect(),
+			_ => Vec::new(),
+		}
+	}
+}
+
+impl VotingMethod for Borda {
+	fn validate(&self, choice: &BallotChoice) -> Result<(), BallotError> {
+		match choice {
+			BallotChoice::Ranked(ranking) => check_distinct(ranking),
+			_ => Err(BallotError::WrongBallotType),
+		}
+	}
+
+	fn accumulate(&self, choice: &BallotChoice) -> Vec<(CandidateId, VoteCount)> {
+		match choice {
+			BallotChoice::Ranked(ranking) => ranking
+				.iter()
+				.enumerate()
+				.map(|(i, c)| (*c, self.candidates.saturating_sub(1).saturating_sub(i as u32) as VoteCount))
+				.collect(),
+			_ => Vec::new(),
+		}
+	}
+}
+
+/// The summing method for an election, or `None` for the ranked methods counted
+/// in rounds or pairwise and for referendums. `candidates` is the number of candidates standing.
+pub fn for_counting(method: CountingMethod, candidates: u32) -> Option<Box<dyn VotingMethod>> {
+	match method {
+		CountingMethod::Plurality => Some(Box::new(Plurality)),
+		CountingMethod::Approval => Some(Box::new(Approval)),
+		CountingMethod::Score { max_score } => Some(Box::new(Score { max_score })),
+		CountingMethod::Borda => Some(Box::new(Borda { candidates })),
+		CountingMethod::Quadratic { budget } => Some(Box::new(Quadratic { budget })),
+		CountingMethod::InstantRunoff |
+		CountingMethod::SingleTransferableVote |
+		CountingMethod::Schulze |
+		CountingMethod::Referendum => None,
+	}
+}
+
+/// Credits a quadratic ballot spends, the sum of the squared votes
+pub fn credits_spent(votes: &[(CandidateId, u32)]) -> Option<u64> {
+	votes.iter().try_fold(0u64, |acc, (_, n)| acc.checked_add((*n as u64).checked_mul(*n as u64)?))
+}
+
+/// Ballot marks at least one candidate and none of them twice
+pub fn check_distinct(marked: &[CandidateId]) -> Result<(), BallotError> {
+	if marked.is_empty() {
+		return Err(BallotError::Empty)
+	}
+	if marked.iter().enumerate().any(|(i, c)| marked[..i].contains(c)) {
+		return Err(BallotError::Duplicate)
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn tally(method: &dyn VotingMethod, ballots: &[BallotChoice]) -> Vec<(CandidateId, VoteCount)> {
+		let mut totals: Vec<(CandidateId, VoteCount)> = Vec::new();
+		for ballot in ballots {
+			assert_eq!(method.validate(ballot), Ok(()));
+			for (candidate_id, points) in method.accumulate(ballot) {
+				match totals.iter_mut().find(|(c, _)| *c == candidate_id) {
+					Some((_, total)) => *total += points,
+					None => totals.push((candidate_id, points)),
+				}
+			}
+		}
+		totals.sort();
+		totals
+	}
+
+	#[test]
+	fn test_approval_counts_every_marked_candidate() {
+		let ballots = [
+			BallotChoice::Approval(vec![1, 2].try_into().unwrap()),
+			BallotChoice::Approval(vec![2, 3].try_into().unwrap()),
+			BallotChoice::Approval(vec![2].try_into().unwrap()),
+		];
+		let totals = tally(&Approval, &ballots);
+		assert_eq!(totals, vec![(1, 1), (2, 3), (3, 1)]);
+		assert_eq!(Approval.result(&totals, 2), vec![2, 1]);
+		assert_eq!(
+			Approval.validate(&BallotChoice::Approval(vec![1, 1].try_into().unwrap())),
+			Err(BallotError::Duplicate)
+		);
+	}
+
+	#[test]
+	fn test_score_rejects_out_of_range() {
+		let method = Score { max_score: 5 };
+		let ballots = [
+			BallotChoice::Scored(vec![(1, 5), (2, 1)].try_into().unwrap()),
+			BallotChoice::Scored(vec![(1, 0), (2, 4)].try_into().unwrap()),
+		];
+		let totals = tally(&method, &ballots);
+		assert_eq!(totals, vec![(1, 5), (2, 5)]);
+		assert_eq!(method.result(&totals, 1), vec![1]);
+		assert_eq!(
+			method.validate(&BallotChoice::Scored(vec![(1, 6)].try_into().unwrap())),
+			Err(BallotError::ScoreOutOfRange)
+		);
+	}
+
+	#[test]
+	fn test_borda_points_by_position() {
+		let method = Borda { candidates: 3 };
+		let ballots = [
+			BallotChoice::Ranked(vec![1, 2, 3].try_into().unwrap()),
+			BallotChoice::Ranked(vec![2, 3].try_into().unwrap()),
+			BallotChoice::Ranked(vec![3].try_into().unwrap()),
+		];
+		let totals = tally(&method, &ballots);
+		assert_eq!(totals, vec![(1, 2), (2, 3), (3, 3)]);
+		assert_eq!(method.result(&totals, 1), vec![2]);
+	}
+
+	#[test]
+	fn test_quadratic_budget() {
+		let method = Quadratic { budget: 10 };
+		let ballots = [
+			BallotChoice::Scored(vec![(1, 3), (2, 1)].try_into().unwrap()),
+			BallotChoice::Scored(vec![(2, 2), (3, 2)].try_into().unwrap()),
+		];
+		assert_eq!(tally(&method, &ballots), vec![(1, 3), (2, 3), (3, 2)]);
+		assert_eq!(
+			method.validate(&BallotChoice::Scored(vec![(1, 3), (2, 2)].try_into().unwrap())),
+			Err(BallotError::OverBudget)
+		);
+		assert_eq!(credits_spent(&[(1, u32::MAX), (2, u32::MAX)]), None);
+	}
+
+	#[test]
+	fn test_wrong_ballot_type() {
+		assert_eq!(Plurality.validate(&BallotChoice::Single(1)), Ok(()));
+		assert_eq!(
+			Plurality.validate(&BallotChoice::Approval(vec![1].try_into().unwrap())),
+			Err(BallotError::WrongBallotType)
+		);
+		assert!(for_counting(CountingMethod::InstantRunoff, 3).is_none());
+	}
+}
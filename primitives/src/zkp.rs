@@ -2,16 +2,85 @@ use bulletproofs::{BulletproofGens, PedersenGens, RangeProof};
 use merlin::Transcript;
 use curve25519_dalek_ng::{scalar::Scalar, ristretto::CompressedRistretto};
 use sha2::{Digest, Sha512};
-use crate::{CandidateId, ZKPCommitmentValue, PedersenCommitment, ZKPModule, Vote, VotingSystem};
+use crate::{CandidateId, ZKPCommitmentValue, PedersenCommitment, ZKPModule, SealedWeight, Vote, VoteCount, VotingSystem};
 
 // Define the ZKP Protocol:
 // https://doc-internal.dalek.rs/bulletproofs/struct.RangeProof.html
 
+/// Transcript label every vote proof is made and verified under
+pub const VOTE_TRANSCRIPT: &[u8] = b"ZKPExample";
+/// Transcript label of the proofs that a ballot's commitment opens to the voter's weight
+pub const WEIGHT_TRANSCRIPT: &[u8] = b"ZKPWeight";
+
+/// Blinding scalar a voting client derives from its secret randomness
+pub fn blinding(zkp_randomness: &[u8]) -> Scalar {
+    let hash = Sha512::digest(zkp_randomness);
+    let mut hash_bytes = [0u8; 64];
+    hash_bytes.copy_from_slice(hash.as_slice());
+    Scalar::from_bytes_mod_order_wide(&hash_bytes)
+}
+
+/// Pedersen commitment to a value, as rebuilt from a spoiled ballot's opening
+pub fn commit(value: VoteCount, zkp_randomness: &[u8]) -> [u8; 32] {
+    PedersenGens::default().commit(Scalar::from(value), blinding(zkp_randomness)).compress().to_bytes()
+}
+
+/// Fiat-Shamir challenge of a weight proof over the ballot it is cast with
+fn weight_challenge(commitment: &[u8; 32], weight: VoteCount, nonce: &[u8; 32], context: &[u8]) -> Scalar {
+    let mut transcript = Transcript::new(WEIGHT_TRANSCRIPT);
+    transcript.append_message(b"context", context);
+    transcript.append_message(b"commitment", commitment);
+    transcript.append_message(b"weight", &weight.to_le_bytes());
+    transcript.append_message(b"nonce", nonce);
+    let mut challenge = [0u8; 64];
+    transcript.challenge_bytes(b"challenge", &mut challenge);
+    Scalar::from_bytes_mod_order_wide(&challenge)
+}
+
+/// Commit to the voter's weight and prove, as a voting client does, that the
+/// commitment opens to it: a Schnorr proof of knowledge of `r` with
+/// `C - weight * B = r * B_blinding`. `context` is the encoded ballot.
+#[cfg(feature = "std")]
+pub fn prove_weight(weight: VoteCount, zkp_randomness: &[u8], context: &[u8]) -> SealedWeight {
+    let pc_gens = PedersenGens::default();
+    let r = blinding(zkp_randomness);
+    let commitment = pc_gens.commit(Scalar::from(weight), r).compress().to_bytes();
+    let k = blinding(&[zkp_randomness, context].concat());
+    let nonce = (k * pc_gens.B_blinding).compress().to_bytes();
+    let s = k + weight_challenge(&commitment, weight, &nonce, context) * r;
+    let mut proof = [0u8; 64];
+    proof[..32].copy_from_slice(&nonce);
+    proof[32..].copy_from_slice(s.as_bytes());
+    SealedWeight { commitment, proof }
+}
+
+/// Check that a commitment cast with a ballot opens to `weight`, the voter's
+/// registered weight
+pub fn verify_weight(sealed: &SealedWeight, weight: VoteCount, context: &[u8]) -> bool {
+    let pc_gens = PedersenGens::default();
+    let mut nonce = [0u8; 32];
+    nonce.copy_from_slice(&sealed.proof[..32]);
+    let mut response = [0u8; 32];
+    response.copy_from_slice(&sealed.proof[32..]);
+    let (commitment, nonce_point, s) = match (
+        CompressedRistretto(sealed.commitment).decompress(),
+        CompressedRistretto(nonce).decompress(),
+        Scalar::from_canonical_bytes(response),
+    ) {
+        (Some(c), Some(n), Some(s)) => (c, n, s),
+        _ => return false,
+    };
+    let c = weight_challenge(&sealed.commitment, weight, &nonce, context);
+    s * pc_gens.B_blinding == nonce_point + c * (commitment - Scalar::from(weight) * pc_gens.B)
+}
+
 impl PedersenCommitment {
     pub fn new(value: u64, randomness: Scalar) -> PedersenCommitment {
         PedersenCommitment { value, randomness }
     }
 
+    /// Proving draws from the thread rng, so it is only run by clients
+    #[cfg(feature = "std")]
     pub fn generate_proof_of_knowledge(
         &self,
         pc_gens: &PedersenGens,
@@ -32,7 +101,7 @@ impl PedersenCommitment {
         .expect("Failed to generate range proof");
 
         // Verification requires a transcript with identical initial state:
-        let mut verifier_transcript = Transcript::new(b"ZKPExample");
+        let mut verifier_transcript = Transcript::new(VOTE_TRANSCRIPT);
         if proof.verify_single(&bp_gens, &pc_gens, &mut verifier_transcript, &committed_value, 64).is_ok() {
             Some((proof, committed_value))
         } else {
@@ -65,6 +134,7 @@ impl ZKPModule {
         PedersenCommitment::new(value, randomness)
     }
 
+    #[cfg(feature = "std")]
     pub fn generate_proof_of_knowledge(
         &self,
         commitment: &PedersenCommitment,
@@ -97,6 +167,7 @@ impl VotingSystem {
         }
     }
 
+    #[cfg(feature = "std")]
     pub fn cast_vote(
         &self, 
         candidate: CandidateId, 
@@ -191,5 +262,15 @@ mod tests {
             &vote,
         ));
     }
+
+    #[test]
+    fn test_weight_proof_binds_weight_and_ballot() {
+        let sealed = prove_weight(3, b"hello world", b"ballot");
+
+        assert!(verify_weight(&sealed, 3, b"ballot"));
+        assert!(!verify_weight(&sealed, 4, b"ballot"));
+        assert!(!verify_weight(&sealed, 3, b"another ballot"));
+        assert_eq!(sealed.commitment, commit(3, b"hello world"));
+    }
 }
 
@@ -29,7 +29,8 @@ use sp_version::RuntimeVersion;
 pub use frame_support::{
 	construct_runtime, parameter_types,
 	traits::{
-		ConstU128, ConstU32, ConstU64, ConstU8, KeyOwnerProofSystem, Randomness, StorageInfo,
+		ConstU128, ConstU32, ConstU64, ConstU8, EitherOfDiverse, KeyOwnerProofSystem, Randomness,
+		StorageInfo,
 	},
 	weights::{
 		constants::{
@@ -40,12 +41,15 @@ pub use frame_support::{
 	StorageValue,
 };
 pub use frame_system::Call as SystemCall;
+use frame_system::EnsureRoot;
 pub use pallet_balances::Call as BalancesCall;
 pub use pallet_timestamp::Call as TimestampCall;
 use pallet_transaction_payment::{ConstFeeMultiplier, CurrencyAdapter, Multiplier};
 #[cfg(any(feature = "std", test))]
 pub use sp_runtime::BuildStorage;
 pub use sp_runtime::{Perbill, Permill};
+pub use pallet_roles::{EnsureRole, Role};
+pub use primitives::RoleScope;
 
 /// Import the template pallet.
 pub use pallet_template;
@@ -281,6 +285,173 @@ impl pallet_template::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 }
 
+parameter_types! {
+	pub const CommissionMotionDuration: BlockNumber = 3 * DAYS;
+	pub const CommissionMaxProposals: u32 = 100;
+	pub const CommissionMaxMembers: u32 = 16;
+}
+
+/// The chief election commissioner and the election commissioners. The chief is the
+/// collective's prime member, whose vote counts for the commissioners who abstain.
+pub type CommissionCollective = pallet_collective::Instance1;
+impl pallet_collective::Config<CommissionCollective> for Runtime {
+	type RuntimeOrigin = RuntimeOrigin;
+	type Proposal = RuntimeCall;
+	type RuntimeEvent = RuntimeEvent;
+	type MotionDuration = CommissionMotionDuration;
+	type MaxProposals = CommissionMaxProposals;
+	type MaxMembers = CommissionMaxMembers;
+	type DefaultVote = pallet_collective::PrimeDefaultVote;
+	type WeightInfo = pallet_collective::weights::SubstrateWeight<Runtime>;
+	type SetMembersOrigin = CommissionTwoThirds;
+}
+
+/// Root, as long as the chain spec sets a sudo key, or a motion of the commission
+/// passed by `Threshold`
+pub type CommissionOrigin<Threshold> = EitherOfDiverse<EnsureRoot<AccountId>, Threshold>;
+/// More than half of the commissioners
+pub type CommissionMajority = CommissionOrigin<
+	pallet_collective::EnsureProportionMoreThan<AccountId, CommissionCollective, 1, 2>,
+>;
+/// At least two thirds of the commissioners
+pub type CommissionTwoThirds = CommissionOrigin<
+	pallet_collective::EnsureProportionAtLeast<AccountId, CommissionCollective, 2, 3>,
+>;
+parameter_types! {
+	pub const TribunalMotionDuration: BlockNumber = 7 * DAYS;
+	pub const TribunalMaxProposals: u32 = 100;
+	pub const TribunalMaxMembers: u32 = 16;
+}
+
+/// The judges of the election tribunal, who decide election petitions apart from the
+/// commission whose results are challenged. The commission appoints them.
+pub type TribunalCollective = pallet_collective::Instance2;
+impl pallet_collective::Config<TribunalCollective> for Runtime {
+	type RuntimeOrigin = RuntimeOrigin;
+	type Proposal = RuntimeCall;
+	type RuntimeEvent = RuntimeEvent;
+	type MotionDuration = TribunalMotionDuration;
+	type MaxProposals = TribunalMaxProposals;
+	type MaxMembers = TribunalMaxMembers;
+	type DefaultVote = pallet_collective::PrimeDefaultVote;
+	type WeightInfo = pallet_collective::weights::SubstrateWeight<Runtime>;
+	type SetMembersOrigin = CommissionTwoThirds;
+}
+
+/// Root, as long as the chain spec sets a sudo key, or a motion passed by more than
+/// half of the tribunal's judges
+pub type TribunalOrigin = EitherOfDiverse<
+	EnsureRoot<AccountId>,
+	pallet_collective::EnsureProportionMoreThan<AccountId, TribunalCollective, 1, 2>,
+>;
+
+/// Signatures of at least three quarters of the commissioners certify a result
+pub struct CommissionSignatures;
+impl frame_support::traits::Get<u32> for CommissionSignatures {
+	fn get() -> u32 {
+		let commissioners = Commission::members().len() as u32;
+		(commissioners.saturating_mul(3).saturating_add(3) / 4).max(1)
+	}
+}
+
+parameter_types! {
+	pub const CommissionRole: Role = Role::Commission;
+	pub const ReturningOfficerRole: Role = Role::ReturningOfficer;
+	pub const RegistrarRole: Role = Role::Registrar;
+	pub const TrusteeRole: Role = Role::Trustee;
+}
+
+/// A commissioner granted the commission role over the election, or a commission majority
+pub type CommissionerOrigin = EnsureRole<Runtime, CommissionRole, CommissionMajority>;
+/// A returning officer for the election or constituency, acting alone, or the commission
+pub type ReturningOfficerOrigin = EnsureRole<Runtime, ReturningOfficerRole, CommissionMajority>;
+/// The registrar of the voter's region or embassy, or the commission
+pub type RegistrarOrigin = EnsureRole<Runtime, RegistrarRole, CommissionMajority>;
+/// A trustee of the election, or the commission
+pub type TrusteeOrigin = EnsureRole<Runtime, TrusteeRole, CommissionMajority>;
+
+impl pallet_roles::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RoleAdminOrigin = CommissionMajority;
+}
+
+impl pallet_observer::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type ElectionCommissionApproveOrigin = CommissionerOrigin;
+	type Roles = Roles;
+	type ElectionInfo = Election;
+	type MaxOpenIncidentsPerObserver = ConstU32<20>;
+}
+
+impl pallet_election::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type ElectionCommissionApproveOrigin = CommissionerOrigin;
+	type CreateElectionOrigin = CommissionMajority;
+	type VotingWindowOrigin = CommissionTwoThirds;
+}
+
+impl pallet_voter::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type ElectionInfo = Election;
+	type RegistrarOrigin = RegistrarOrigin;
+	type MaxDelegationDepth = ConstU32<8>;
+}
+
+parameter_types! {
+	/// Candidates polling under a sixth of the votes forfeit their deposit
+	pub CandidateDepositForfeitThreshold: Perbill = Perbill::from_rational(1u32, 6u32);
+}
+
+impl pallet_candidate::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type ElectionInfo = Election;
+	type ElectionCommissionApproveOrigin = CommissionerOrigin;
+	type ReturningOfficerOrigin = ReturningOfficerOrigin;
+	type Currency = Balances;
+	type NominationDeposit = ConstU128<{ 1000 * EXISTENTIAL_DEPOSIT }>;
+	type DepositForfeitThreshold = CandidateDepositForfeitThreshold;
+	type ElectionResults = Ballot;
+	type MaxCandidatesPerElection = ConstU32<64>;
+	type Randomness = RandomnessCollectiveFlip;
+	type CandidateWithdrawal = Party;
+	type UnixTime = Timestamp;
+	type ElectionControl = Election;
+}
+
+impl pallet_ballot::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type ElectionInfo = Election;
+	type CandidateLookup = Candidate;
+	type VoterInfo = Voter;
+	type ElectionCommissionApproveOrigin = CommissionerOrigin;
+	type CertifyOrigin = pallet_collective::EnsureMember<AccountId, CommissionCollective>;
+	type RequiredSignatures = CommissionSignatures;
+	type TrusteeOrigin = TrusteeOrigin;
+	type MaxBallotsPerTally = ConstU32<1000>;
+	type ElectionControl = Election;
+}
+
+impl pallet_party::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type ElectionCommissionApproveOrigin = CommissionMajority;
+	type ElectionInfo = Election;
+	type CandidateLookup = Candidate;
+	type CandidateAccounts = Candidate;
+	type ElectionResults = Ballot;
+}
+
+impl pallet_dispute::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type TribunalOrigin = TribunalOrigin;
+	type Currency = Balances;
+	type PetitionBond = ConstU128<{ 10_000 * EXISTENTIAL_DEPOSIT }>;
+	type PetitionPeriod = ConstU32<{ 45 * DAYS }>;
+	type CandidateAccounts = Candidate;
+	type ElectionInfo = Election;
+	type ElectionResults = Ballot;
+	type ElectionControl = Election;
+}
+
 // Create the runtime by composing the FRAME pallets that were previously configured.
 construct_runtime!(
 	pub struct Runtime
@@ -297,8 +468,18 @@ construct_runtime!(
 		Balances: pallet_balances,
 		TransactionPayment: pallet_transaction_payment,
 		Sudo: pallet_sudo,
+		Commission: pallet_collective::<Instance1>,
+		Tribunal: pallet_collective::<Instance2>,
 		// Include the custom logic from the pallet-template in the runtime.
 		TemplateModule: pallet_template,
+		Election: pallet_election,
+		Voter: pallet_voter,
+		Candidate: pallet_candidate,
+		Ballot: pallet_ballot,
+		Party: pallet_party,
+		Dispute: pallet_dispute,
+		Roles: pallet_roles,
+		Observer: pallet_observer,
 	}
 );
 
@@ -486,6 +667,46 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_ballot_runtime_api::BallotApi<Block, Hash, BlockNumber> for Runtime {
+		fn ballot_receipt(election_id: primitives::ElectionId, tracking_hash: Hash) -> Option<BlockNumber> {
+			Ballot::ballot_receipt(election_id, tracking_hash)
+		}
+		fn spoiled_ballot(election_id: primitives::ElectionId, tracking_hash: Hash) -> Option<primitives::SpoiledBallot> {
+			Ballot::spoiled_ballot(election_id, tracking_hash)
+		}
+		fn pairwise_matrix(election_id: primitives::ElectionId) -> Option<primitives::schulze::PairwiseMatrix> {
+			(!Ballot::results_embargoed(election_id)).then(|| Ballot::pairwise_matrix(election_id))
+		}
+		fn abstentions(election_id: primitives::ElectionId) -> Option<primitives::AbstentionCount> {
+			(!Ballot::results_embargoed(election_id)).then(|| Ballot::abstentions(election_id))
+		}
+		fn certified_results(
+			election_id: primitives::ElectionId,
+		) -> Option<primitives::CertifiedResults<Hash, BlockNumber>> {
+			Ballot::certified_results(election_id)
+		}
+		fn participation(election_id: primitives::ElectionId) -> primitives::Participation {
+			Ballot::participation(election_id)
+		}
+		fn ballots_per_block(
+			election_id: primitives::ElectionId,
+			from: BlockNumber,
+			to: BlockNumber,
+		) -> Vec<(BlockNumber, u32)> {
+			Ballot::ballots_per_block(election_id, from, to)
+		}
+	}
+
+	impl pallet_observer_runtime_api::ObserverApi<Block, AccountId, Hash, BlockNumber> for Runtime {
+		fn open_incidents(
+			election_id: primitives::ElectionId,
+			after: Option<primitives::IncidentId>,
+			limit: u32,
+		) -> Vec<(primitives::IncidentId, primitives::Incident<AccountId, Hash, BlockNumber>)> {
+			Observer::open_incidents(election_id, after, limit)
+		}
+	}
+
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentCallApi<Block, Balance, RuntimeCall>
 		for Runtime
 	{